@@ -6,7 +6,7 @@ use rust_graphql_resolver::{
     schema::{
         field::Field,
         query::Query,
-        resolve::{BoxedValue, QLApiParam, QLContext},
+        resolve::{BoxedValue, QLApiParam, QLContext, QLVariables},
         Schema,
     },
 };
@@ -61,7 +61,13 @@ fn main() {
             is_male
         }
         "#;
-    let result1 = execute(QLContext::default(), request1, &schema, None).unwrap();
+    let result1 = execute(
+        QLContext::default(),
+        request1,
+        &schema,
+        None,
+        QLVariables::default(),
+    );
     println!(
         "result1: {}",
         serde_json::ser::to_string_pretty(&result1).unwrap()
@@ -81,7 +87,13 @@ fn main() {
         is_male
     }
     "#;
-    let result2 = execute(QLContext::default(), request2, &schema, None).unwrap();
+    let result2 = execute(
+        QLContext::default(),
+        request2,
+        &schema,
+        None,
+        QLVariables::default(),
+    );
     println!(
         "result2: {}",
         serde_json::ser::to_string_pretty(&result2).unwrap()
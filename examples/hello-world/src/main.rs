@@ -6,7 +6,7 @@ use rust_graphql_resolver::{
     schema::{
         field::Field,
         query::Query,
-        resolve::{BoxedValue, QLApiParam, QLContext},
+        resolve::{BoxedValue, QLApiParam, QLContext, QLVariables},
         Schema,
     },
 };
@@ -53,7 +53,13 @@ fn main() {
             } 
         }
         "#;
-        let result = execute(QLContext::default(), request, &schema, None).unwrap();
+        let result = execute(
+            QLContext::default(),
+            request,
+            &schema,
+            None,
+            QLVariables::default(),
+        );
         println!(
             "result: {}",
             serde_json::ser::to_string_pretty(&result).unwrap()
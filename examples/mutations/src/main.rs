@@ -1,9 +1,7 @@
 use std::{
-    array::IntoIter,
-    cell::RefCell,
-    collections::{BTreeMap, HashMap},
+    collections::HashMap,
     iter::FromIterator,
-    rc::Rc,
+    sync::{Arc, Mutex},
 };
 
 use rust_graphql_resolver::{
@@ -13,17 +11,18 @@ use rust_graphql_resolver::{
     },
     error::{BuildResult, Error, Result},
     execute,
+    indexmap::IndexMap,
     schema::{
         field::{Field, InputFieldType},
         mutation::Mutation,
         query::Query,
-        resolve::{ApiResolveFunc, BoxedValue, QLApiParam, QLContext},
+        resolve::{ApiResolveFunc, BoxedValue, QLApiParam, QLContext, QLVariables},
         Schema,
     },
     value::DataValue,
 };
 
-type Storage = Rc<RefCell<HashMap<String, DataValue>>>;
+type Storage = Arc<Mutex<HashMap<String, DataValue>>>;
 
 fn build_schema(datas: Storage) -> BuildResult<Schema> {
     SchemaBuilder::new("mutations_schema")
@@ -72,7 +71,7 @@ fn create_query_func(datas: Storage) -> Box<dyn ApiResolveFunc> {
                     ))
                 }
             };
-            match datas.borrow().get(id) {
+            match datas.lock().unwrap().get(id) {
                 Some(res) => Ok(Box::new(res.to_owned())),
                 None => Ok(Box::new(DataValue::Null)),
             }
@@ -90,17 +89,17 @@ fn create_mutation_func(datas: Storage) -> Box<dyn ApiResolveFunc> {
                 .arguments
                 .get(&"id".to_string())
                 .ok_or(Error::NotFoundError("argument: 'id'".to_string()))?;
-            let foo = parameter
+            let foo_value = parameter
                 .arguments
                 .get(&"foo".to_string())
                 .ok_or(Error::NotFoundError("argument: 'foo'".to_string()))?;
-            match (id, foo) {
+            match (id, foo_value) {
                 (DataValue::String(i), DataValue::String(f)) => {
-                    let v = DataValue::Object(BTreeMap::from_iter(IntoIter::new([
+                    let v = DataValue::Object(IndexMap::from_iter(IntoIterator::into_iter([
                         ("id".to_string(), DataValue::ID(i.to_owned())),
                         ("foo".to_string(), DataValue::String(f.to_owned())),
                     ])));
-                    datas.borrow_mut().insert(i.to_owned(), v.clone());
+                    datas.lock().unwrap().insert(i.to_owned(), v.clone());
                     Ok(Box::new(v))
                 }
                 _ => Err(Error::DataTypeMisMatchError(
@@ -115,7 +114,7 @@ fn create_mutation_func(datas: Storage) -> Box<dyn ApiResolveFunc> {
 }
 
 fn mutation_and_query() {
-    let datas = Rc::new(RefCell::new(HashMap::new()));
+    let datas = Arc::new(Mutex::new(HashMap::new()));
     let schema = build_schema(datas).unwrap();
     let context = QLContext::default();
 
@@ -131,19 +130,25 @@ fn mutation_and_query() {
         }
     }
     "#;
-    let result1 = execute(context.clone(), request1, &schema, None).unwrap();
+    let result1 = execute(
+        context.clone(),
+        request1,
+        &schema,
+        None,
+        QLVariables::default(),
+    );
     println!(
         "result: {}",
         serde_json::to_string_pretty(&result1).unwrap()
     );
 
     let request2 = r#"
-    {
+    query ($second: ID!) {
         get1:sample(id: "1"){
             id
             foo
         }
-        get2:sample(id: "2"){
+        get2:sample(id: $second){
             id
             foo
         }
@@ -153,7 +158,11 @@ fn mutation_and_query() {
         }
     }
     "#;
-    let result2 = execute(context.clone(), request2, &schema, None).unwrap();
+    let variables2 = QLVariables::from_iter(IntoIterator::into_iter([(
+        "second".to_string(),
+        DataValue::String("2".to_string()),
+    )]));
+    let result2 = execute(context.clone(), request2, &schema, None, variables2);
     println!(
         "result: {}",
         serde_json::to_string_pretty(&result2).unwrap()
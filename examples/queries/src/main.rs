@@ -1,4 +1,4 @@
-use std::{array::IntoIter, collections::BTreeMap, iter::FromIterator};
+use std::iter::FromIterator;
 
 use chrono::{DateTime, Utc};
 use rust_graphql_resolver::{
@@ -6,14 +6,17 @@ use rust_graphql_resolver::{
         field::{CustomTypeBuilder, QLEnumBuilder, QLInputBuilder},
         query::QueryBuilder,
         schema::SchemaBuilder,
+        types::ObjectType,
     },
-    error::{BuildResult, Error, Result},
+    error::{BuildResult, Result},
     execute,
-    macros::GraphQLDataValue,
+    macros::{GraphQLDataValue, GraphQLFromDataValue, GraphQLObject},
     schema::{
         field::{CustomType, Field, FieldType, InputField, QLInput},
         query::Query,
-        resolve::{ApiResolveFunc, BoxedValue, FieldResolveFunc, QLApiParam, QLContext},
+        resolve::{
+            ApiResolveFunc, BoxedValue, FieldResolveFunc, QLApiParam, QLContext, QLVariables,
+        },
         Schema,
     },
     value::DataValue,
@@ -30,7 +33,20 @@ struct FullObject {
     color: String,
 }
 
-#[derive(Debug, Clone, GraphQLDataValue)]
+/// the `SearchFullObjectInput` argument, fields which are not provided don't filter
+#[derive(Debug, Clone, GraphQLFromDataValue)]
+struct SearchInput {
+    id: Option<String>,
+    str: Option<String>,
+    int: Option<i64>,
+    float: Option<f64>,
+    bool: Option<bool>,
+    datetime: Option<DateTime<Utc>>,
+    color: Option<String>,
+}
+
+/// extra columns, resolved on demand
+#[derive(Debug, Clone, GraphQLDataValue, GraphQLObject)]
 struct ExtraObject {
     col1: String,
     col2: i64,
@@ -45,19 +61,14 @@ fn build_schema(datas: Vec<FullObject>) -> BuildResult<Schema> {
                 .add_value("Green")
                 .build(),
         )
-        .add_object(
-            CustomTypeBuilder::new("ExtraObject")
-                .add_field("col1", Field::basic_str())
-                .add_field("col2", Field::basic_int())
-                .build(),
-        )
+        .add_object_with_status(ExtraObject::object_type)?
         .add_object_with_status(|sch| -> BuildResult<CustomType> {
             CustomTypeBuilder::new("FullObject")
                 .add_field("id", Field::basic_id())
-                .add_field("str", Field::basic_str())
-                .add_field("int", Field::basic_int())
-                .add_field("float", Field::basic_float())
-                .add_field("bool", Field::basic_bool())
+                .add_field("str_value", Field::basic_str())
+                .add_field("int_value", Field::basic_int())
+                .add_field("float_value", Field::basic_float())
+                .add_field("bool_value", Field::basic_bool())
                 .add_field("datetime", Field::basic_datetime())
                 .add_field("color", Field::simple("color", sch.get_enum_type("Color")?))
                 .add_field(
@@ -90,6 +101,7 @@ fn build_schema(datas: Vec<FullObject>) -> BuildResult<Schema> {
             ))));
             QueryBuilder::new()
                 .set_type(field_type)
+                .add_argument("condition", sch.get_input_type("SearchFullObjectInput")?)
                 .set_resolve(create_func(datas.clone()))
                 .build()
         })?
@@ -113,10 +125,7 @@ fn extra_resolve() -> Box<dyn FieldResolveFunc> {
                 _ => 0,
             };
 
-            Ok(Box::new(ExtraObject {
-                col1: col1,
-                col2: col2,
-            }))
+            Ok(Box::new(ExtraObject { col1, col2 }))
         },
     )
 }
@@ -126,47 +135,25 @@ fn create_func(datas: Vec<FullObject>) -> Box<dyn ApiResolveFunc> {
     Box::new(
         move |_context: &mut QLContext, parameter: &QLApiParam| -> Result<BoxedValue> {
             println!("[debug] invoke every times...");
-            let condition = parameter
-                .arguments
-                .get(&"condition".to_string())
-                .ok_or(Error::NotFoundError("argument: 'condition'".to_string()))?;
-            match condition {
-                DataValue::Object(map) => query_data(datas.clone(), map),
-                _ => Err(Error::DataTypeMisMatchError(
-                    "input condition".to_string(),
-                    "not match".to_string(),
-                )),
-            }
+            let condition = parameter.arguments.parse::<SearchInput>("condition")?;
+            query_data(datas.clone(), &condition)
         },
     )
 }
 
-fn query_data(datas: Vec<FullObject>, map: &BTreeMap<String, DataValue>) -> Result<BoxedValue> {
+fn query_data(datas: Vec<FullObject>, condition: &SearchInput) -> Result<BoxedValue> {
     let target: Vec<FullObject> = datas
         .iter()
-        .cloned()
-        .filter(|dv| -> bool {
-            let mut p = true;
-            if let Some(DataValue::String(id)) = map.get("id") {
-                p = dv.id == id.to_owned() && p;
-            }
-            if let Some(DataValue::String(s)) = map.get("str") {
-                p = dv.str_value == s.to_owned() && p;
-            }
-            if let Some(DataValue::Int(i)) = map.get("int") {
-                p = dv.int_value == i.to_owned() && p;
-            }
-            if let Some(DataValue::Float(f)) = map.get("flost") {
-                p = dv.float_value == f.to_owned() && p;
-            }
-            if let Some(DataValue::Boolean(b)) = map.get("bool") {
-                p = dv.bool_value == b.to_owned() && p;
-            }
-            if let Some(DataValue::String(color)) = map.get("color") {
-                p = dv.color == color.to_owned() && p;
-            }
-            p
+        .filter(|&dv| -> bool {
+            condition.id.as_ref().is_none_or(|id| dv.id == *id)
+                && condition.str.as_ref().is_none_or(|s| dv.str_value == *s)
+                && condition.int.is_none_or(|i| dv.int_value == i)
+                && condition.float.is_none_or(|f| dv.float_value == f)
+                && condition.bool.is_none_or(|b| dv.bool_value == b)
+                && condition.datetime.is_none_or(|dt| dv.datetime == dt)
+                && condition.color.as_ref().is_none_or(|c| dv.color == *c)
         })
+        .cloned()
         .collect();
     Ok(Box::new(target))
 }
@@ -225,7 +212,7 @@ fn query() {
     let datas = init_data();
     let schema = build_schema(datas).unwrap();
 
-    let context1 = QLContext::from_iter(IntoIter::new([
+    let context1 = QLContext::from_iter(IntoIterator::into_iter([
         (
             "col1".to_string(),
             DataValue::String("col1: strings".to_string()),
@@ -249,13 +236,13 @@ fn query() {
         } 
     }
     "#;
-    let result1 = execute(context1, request1, &schema, None).unwrap();
+    let result1 = execute(context1, request1, &schema, None, QLVariables::default());
     println!(
         "result: {}",
         serde_json::ser::to_string_pretty(&result1).unwrap()
     );
 
-    let context2 = QLContext::from_iter(IntoIter::new([
+    let context2 = QLContext::from_iter(IntoIterator::into_iter([
         (
             "col1".to_string(),
             DataValue::String("col1: stringsxxx".to_string()),
@@ -279,7 +266,7 @@ fn query() {
         } 
     }
     "#;
-    let result2 = execute(context2, request2, &schema, None).unwrap();
+    let result2 = execute(context2, request2, &schema, None, QLVariables::default());
     println!(
         "result: {}",
         serde_json::ser::to_string_pretty(&result2).unwrap()
@@ -296,7 +283,13 @@ fn query() {
         } 
     }
     "#;
-    let result3 = execute(QLContext::default(), request3, &schema, None).unwrap();
+    let result3 = execute(
+        QLContext::default(),
+        request3,
+        &schema,
+        None,
+        QLVariables::default(),
+    );
     println!(
         "result: {}",
         serde_json::ser::to_string_pretty(&result3).unwrap()
@@ -1,52 +1,739 @@
 use proc_macro::TokenStream;
 use proc_macro2::TokenStream as TokenStream2;
 use quote::quote;
-use syn::{parse_macro_input, DeriveInput};
+use syn::{ext::IdentExt, parse_macro_input, DeriveInput};
 
-/// Derive DataValue for struct
+/// Derive DataValue for struct or enum.
+/// Named fields are object fields, newtype structs are their value, tuple structs are lists,
+/// and unit structs are null. Variants of fieldless enums are enum values, variants of other
+/// enums are union members with a `__typename` named by the variant.
+///
+/// Supported attributes:
+/// * `#[graphql(rename = "...")]` on fields and variants, `#[graphql(rename_all = "camelCase")]` on the type
+/// * `#[graphql(skip)]` leaves a field out
+/// * `#[graphql(flatten)]` merges the fields of a nested object
+/// * `#[graphql(with = "path")]` converts a field with a `fn(&T) -> DataValue`
 /// ```compile_fail
 /// #[derive(Debug, Clone, GraphQLDataValue)]
+/// #[graphql(rename_all = "camelCase")]
 /// struct HelloWorld {
 ///     hello: String,
-///     greeting: String,
+///     greeting_word: String,
 /// }
 /// ```
-#[proc_macro_derive(GraphQLDataValue)]
+#[proc_macro_derive(GraphQLDataValue, attributes(graphql))]
 pub fn graphql_data_value(input: TokenStream) -> TokenStream {
     let ast = parse_macro_input!(input as DeriveInput);
+    let quoted_code = match &ast.data {
+        syn::Data::Struct(struct_data) => struct_data_value(&ast, struct_data),
+        syn::Data::Enum(enum_data) => enum_data_value(&ast, enum_data),
+        syn::Data::Union(_) => Err(syn::Error::new_spanned(
+            &ast,
+            "#[derive(GraphQLDataValue)] is only defined for structs and enums",
+        )),
+    };
+    match quoted_code {
+        Ok(quoted_code) => proc_macro::TokenStream::from(quoted_code),
+        Err(err) => proc_macro::TokenStream::from(err.to_compile_error()),
+    }
+}
+
+/// add `bound` to every type parameter
+fn add_trait_bounds(generics: &syn::Generics, bound: syn::TypeParamBound) -> syn::Generics {
+    let mut generics = generics.clone();
+    for param in generics.params.iter_mut() {
+        if let syn::GenericParam::Type(type_param) = param {
+            type_param.bounds.push(bound.clone());
+        }
+    }
+    generics
+}
+
+/// statements inserting the named fields into `map`, `access` gives a reference to a field
+fn insert_fields<F>(
+    fields: &syn::FieldsNamed,
+    rename_all: Option<&syn::LitStr>,
+    access: F,
+) -> syn::Result<TokenStream2>
+where
+    F: Fn(&syn::Ident) -> TokenStream2,
+{
+    let mut quotes = TokenStream2::new();
+    for field in fields.named.iter() {
+        let attrs = GraphQLAttrs::parse(&field.attrs)?;
+        if attrs.skip {
+            continue;
+        }
+        let field_ident = field.ident.as_ref().unwrap();
+        let data_value = attrs.data_value(access(field_ident));
+        if attrs.flatten {
+            quotes.extend(quote! {
+                if let DataValue::Object(fields) = #data_value {
+                    map.extend(fields);
+                }
+            });
+        } else {
+            let field_name = attrs.name(field_ident, rename_all)?;
+            quotes.extend(quote! {
+                map.insert(#field_name.to_string(), #data_value);
+            });
+        }
+    }
+    Ok(quotes)
+}
+
+fn struct_data_value(
+    ast: &DeriveInput,
+    struct_data: &syn::DataStruct,
+) -> syn::Result<TokenStream2> {
     let struct_name = &ast.ident;
+    let attrs = GraphQLAttrs::parse(&ast.attrs)?;
+    let generics = add_trait_bounds(
+        &ast.generics,
+        syn::parse_quote!(rust_graphql_resolver::value::ToDataValue),
+    );
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let body = match &struct_data.fields {
+        syn::Fields::Named(fields) => {
+            let inserts = insert_fields(fields, attrs.rename_all.as_ref(), |field_ident| {
+                quote! { &self.#field_ident }
+            })?;
+            quote! {
+                #[allow(unused_mut)]
+                let mut map = IndexMap::new();
+                #inserts
+                DataValue::Object(map)
+            }
+        }
+        syn::Fields::Unnamed(fields) if fields.unnamed.len() == 1 => {
+            let field_attrs = GraphQLAttrs::parse(&fields.unnamed[0].attrs)?;
+            field_attrs.data_value(quote! { &self.0 })
+        }
+        syn::Fields::Unnamed(fields) => {
+            let mut items = vec![];
+            for (i, field) in fields.unnamed.iter().enumerate() {
+                let field_attrs = GraphQLAttrs::parse(&field.attrs)?;
+                if !field_attrs.skip {
+                    let index = syn::Index::from(i);
+                    items.push(field_attrs.data_value(quote! { &self.#index }));
+                }
+            }
+            quote! { DataValue::List(vec![#(#items),*]) }
+        }
+        syn::Fields::Unit => quote! { DataValue::Null },
+    };
+
+    Ok(quote! {
+        impl #impl_generics rust_graphql_resolver::value::ToDataValue for #struct_name #ty_generics #where_clause {
+            fn to_data_value(&self) -> rust_graphql_resolver::value::DataValue {
+                use rust_graphql_resolver::value::DataValue;
+                use rust_graphql_resolver::indexmap::IndexMap;
+
+                #body
+            }
+        }
+    })
+}
+
+/// Derive FromDataValue for struct, each field is built from the object field of the same name.
+/// `rename`, `rename_all` and `flatten` are read like `GraphQLDataValue`,
+/// and `#[graphql(skip)]` fields are `Default::default()`
+/// ```compile_fail
+/// #[derive(Debug, Clone, GraphQLFromDataValue)]
+/// struct SearchInput {
+///     name: String,
+///     limit: Option<i64>,
+/// }
+/// ```
+#[proc_macro_derive(GraphQLFromDataValue, attributes(graphql))]
+pub fn graphql_from_data_value(input: TokenStream) -> TokenStream {
+    let ast = parse_macro_input!(input as DeriveInput);
+    match from_data_value(&ast) {
+        Ok(quoted_code) => proc_macro::TokenStream::from(quoted_code),
+        Err(err) => proc_macro::TokenStream::from(err.to_compile_error()),
+    }
+}
+
+fn from_data_value(ast: &DeriveInput) -> syn::Result<TokenStream2> {
+    let struct_name = &ast.ident;
+    let type_name = struct_name.to_string();
+    let attrs = GraphQLAttrs::parse(&ast.attrs)?;
+    let generics = add_trait_bounds(
+        &ast.generics,
+        syn::parse_quote!(rust_graphql_resolver::value::FromDataValue),
+    );
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let fields = match &ast.data {
+        syn::Data::Struct(struct_data) => &struct_data.fields,
+        _ => {
+            return Err(syn::Error::new_spanned(
+                ast,
+                "#[derive(GraphQLFromDataValue)] is only defined for structs",
+            ))
+        }
+    };
+    let body = match fields {
+        syn::Fields::Named(fields) => {
+            let mut field_quotes = TokenStream2::new();
+            for field in fields.named.iter() {
+                let field_attrs = GraphQLAttrs::parse(&field.attrs)?;
+                let field_ident = field.ident.as_ref().unwrap();
+                if let Some(with) = &field_attrs.with {
+                    return Err(syn::Error::new_spanned(
+                        with,
+                        "`with` is only supported by #[derive(GraphQLDataValue)]",
+                    ));
+                }
+                field_quotes.extend(if field_attrs.skip {
+                    quote! { #field_ident: Default::default(), }
+                } else if field_attrs.flatten {
+                    quote! {
+                        #field_ident: rust_graphql_resolver::value::FromDataValue::from_data_value(value)?,
+                    }
+                } else {
+                    let field_name = field_attrs.name(field_ident, attrs.rename_all.as_ref())?;
+                    quote! {
+                        #field_ident: rust_graphql_resolver::value::from_data_value_field(map, #field_name)?,
+                    }
+                });
+            }
+            quote! {
+                let map = match value {
+                    rust_graphql_resolver::value::DataValue::Object(map) => map,
+                    _ => {
+                        return Err(rust_graphql_resolver::error::Error::DataTypeMisMatchError(
+                            #type_name.to_string(),
+                            value.get_type_name(),
+                        ))
+                    }
+                };
+                Ok(Self {
+                    #field_quotes
+                })
+            }
+        }
+        syn::Fields::Unnamed(fields) if fields.unnamed.len() == 1 => quote! {
+            Ok(Self(rust_graphql_resolver::value::FromDataValue::from_data_value(value)?))
+        },
+        syn::Fields::Unit => quote! { Ok(Self) },
+        syn::Fields::Unnamed(fields) => return Err(syn::Error::new_spanned(
+            fields,
+            "#[derive(GraphQLFromDataValue)] is not defined for tuple structs with several fields",
+        )),
+    };
 
-    if let syn::Data::Struct(struct_data) = ast.data {
-        let field_quotes = struct_data
-            .fields
+    Ok(quote! {
+        impl #impl_generics rust_graphql_resolver::value::FromDataValue for #struct_name #ty_generics #where_clause {
+            fn from_data_value(
+                value: &rust_graphql_resolver::value::DataValue,
+            ) -> rust_graphql_resolver::error::Result<Self> {
+                #body
+            }
+        }
+    })
+}
+
+/// the `#[graphql(...)]` attributes of a type, a field or a variant
+#[derive(Default)]
+struct GraphQLAttrs {
+    rename: Option<String>,
+    rename_all: Option<syn::LitStr>,
+    description: Option<String>,
+    resolve: Option<syn::Path>,
+    async_resolve: Option<syn::Path>,
+    skip: bool,
+    flatten: bool,
+    with: Option<syn::Path>,
+}
+
+impl GraphQLAttrs {
+    fn parse(attrs: &[syn::Attribute]) -> syn::Result<Self> {
+        let mut result = Self::default();
+        for attr in attrs.iter().filter(|attr| attr.path.is_ident("graphql")) {
+            let list = match attr.parse_meta()? {
+                syn::Meta::List(list) => list,
+                meta => return Err(syn::Error::new_spanned(meta, "expected #[graphql(...)]")),
+            };
+            for nested in list.nested.iter() {
+                let (path, value) = match nested {
+                    syn::NestedMeta::Meta(syn::Meta::NameValue(syn::MetaNameValue {
+                        path,
+                        lit: syn::Lit::Str(value),
+                        ..
+                    })) => (path, value),
+                    syn::NestedMeta::Meta(syn::Meta::Path(path)) if path.is_ident("skip") => {
+                        result.skip = true;
+                        continue;
+                    }
+                    syn::NestedMeta::Meta(syn::Meta::Path(path)) if path.is_ident("flatten") => {
+                        result.flatten = true;
+                        continue;
+                    }
+                    _ => {
+                        return Err(syn::Error::new_spanned(
+                            nested,
+                            "expected `key = \"value\"`, `skip` or `flatten`",
+                        ))
+                    }
+                };
+                if path.is_ident("rename") {
+                    result.rename = Some(value.value());
+                } else if path.is_ident("rename_all") {
+                    rename_case("", value)?;
+                    result.rename_all = Some(value.clone());
+                } else if path.is_ident("description") {
+                    result.description = Some(value.value());
+                } else if path.is_ident("resolve") {
+                    result.resolve = Some(value.parse()?);
+                } else if path.is_ident("async_resolve") {
+                    result.async_resolve = Some(value.parse()?);
+                } else if path.is_ident("with") {
+                    result.with = Some(value.parse()?);
+                } else {
+                    return Err(syn::Error::new_spanned(path, "unknown graphql attribute"));
+                }
+            }
+        }
+        Ok(result)
+    }
+
+    /// the graphql name of a field or a variant, from `rename` or the `rename_all` of its type
+    fn name(&self, ident: &syn::Ident, rename_all: Option<&syn::LitStr>) -> syn::Result<String> {
+        if let Some(rename) = &self.rename {
+            return Ok(rename.clone());
+        }
+        let name = ident.unraw().to_string();
+        match rename_all {
+            Some(rule) => rename_case(&name, rule),
+            None => Ok(name),
+        }
+    }
+
+    /// the `DataValue` of a field from a reference to it, converted by `with` if set
+    fn data_value(&self, value: TokenStream2) -> TokenStream2 {
+        match &self.with {
+            Some(with) => quote! { #with(#value) },
+            None => quote! { rust_graphql_resolver::value::ToDataValue::to_data_value(#value) },
+        }
+    }
+
+    /// the `description` attribute, or the doc comments
+    fn description(&self, attrs: &[syn::Attribute]) -> String {
+        if let Some(description) = &self.description {
+            return description.clone();
+        }
+        attrs
+            .iter()
+            .filter(|attr| attr.path.is_ident("doc"))
+            .filter_map(|attr| match attr.parse_meta() {
+                Ok(syn::Meta::NameValue(syn::MetaNameValue {
+                    lit: syn::Lit::Str(doc),
+                    ..
+                })) => Some(doc.value().trim().to_string()),
+                _ => None,
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+/// rename a snake_case field or a PascalCase variant by a `rename_all` rule
+fn rename_case(name: &str, rule: &syn::LitStr) -> syn::Result<String> {
+    let mut words = vec![];
+    let mut word = String::new();
+    let mut previous_lowercase = false;
+    for c in name.chars() {
+        if c == '_' || (c.is_uppercase() && previous_lowercase) {
+            words.push(std::mem::take(&mut word));
+        }
+        if c != '_' {
+            word.extend(c.to_lowercase());
+        }
+        previous_lowercase = c.is_lowercase() || c.is_ascii_digit();
+    }
+    words.push(word);
+    words.retain(|word| !word.is_empty());
+
+    let capitalize = |word: &String| -> String {
+        let mut chars = word.chars();
+        match chars.next() {
+            Some(first) => first.to_uppercase().chain(chars).collect(),
+            None => String::default(),
+        }
+    };
+    Ok(match rule.value().as_str() {
+        "lowercase" => words.concat(),
+        "UPPERCASE" => words.concat().to_uppercase(),
+        "camelCase" => words
             .iter()
-            .map(|ref field| -> TokenStream2 {
-                let field_ident = field.ident.clone().unwrap();
-                let field_name = field_ident.to_string();
+            .enumerate()
+            .map(|(i, word)| if i == 0 { word.clone() } else { capitalize(word) })
+            .collect(),
+        "PascalCase" => words.iter().map(capitalize).collect(),
+        "snake_case" => words.join("_"),
+        "SCREAMING_SNAKE_CASE" => words.join("_").to_uppercase(),
+        _ => {
+            return Err(syn::Error::new_spanned(
+                rule,
+                "unknown rename_all rule, expected one of \"lowercase\", \"UPPERCASE\", \"camelCase\", \"PascalCase\", \"snake_case\", \"SCREAMING_SNAKE_CASE\"",
+            ))
+        }
+    })
+}
+
+/// Derive the graphql object type of a struct, with `ObjectType` and `OutputType`.
+/// The field types come from the rust types: `Option<T>` is nullable, `Vec<T>` is a list,
+/// other types are non null. Nested objects must be added to the schema first, or declared
+/// with `SchemaBuilder::declare_object`; `add_object_type` declares the struct itself, so it can reference itself.
+/// Descriptions are taken from doc comments or `#[graphql(description = "...")]`,
+/// `#[graphql(rename = "...")]` renames the type or a field, `rename_all` and `skip` are read like `GraphQLDataValue`,
+/// and `#[graphql(resolve = "path")]` (or `async_resolve`) names a function returning the field resolver.
+/// Generic structs are not supported, a graphql type has a single name
+/// ```
+/// use macros::GraphQLObject;
+/// use rust_graphql_resolver::builder::schema::SchemaBuilder;
+///
+/// #[derive(Debug, Clone, GraphQLObject)]
+/// struct User {
+///     /// the user name
+///     name: String,
+///     friends: Vec<User>,
+/// }
+///
+/// let builder = SchemaBuilder::new("schema").add_object_type::<User>().unwrap();
+/// ```
+/// ```compile_fail
+/// use macros::GraphQLObject;
+///
+/// #[derive(GraphQLObject)]
+/// struct Page<T> {
+///     items: Vec<T>,
+/// }
+/// ```
+#[proc_macro_derive(GraphQLObject, attributes(graphql))]
+pub fn graphql_object(input: TokenStream) -> TokenStream {
+    let ast = parse_macro_input!(input as DeriveInput);
+    match object_type(&ast) {
+        Ok(quoted_code) => proc_macro::TokenStream::from(quoted_code),
+        Err(err) => proc_macro::TokenStream::from(err.to_compile_error()),
+    }
+}
+
+fn object_type(ast: &DeriveInput) -> syn::Result<TokenStream2> {
+    let struct_name = &ast.ident;
+    let attrs = GraphQLAttrs::parse(&ast.attrs)?;
+    let type_name = attrs
+        .rename
+        .clone()
+        .unwrap_or_else(|| struct_name.to_string());
+    let type_description = attrs.description(&ast.attrs);
+    if !ast.generics.params.is_empty() {
+        return Err(syn::Error::new_spanned(
+            &ast.generics,
+            "#[derive(GraphQLObject)] is not defined for generic structs",
+        ));
+    }
+
+    let fields = match &ast.data {
+        syn::Data::Struct(syn::DataStruct {
+            fields: syn::Fields::Named(fields),
+            ..
+        }) => fields,
+        _ => {
+            return Err(syn::Error::new_spanned(
+                ast,
+                "#[derive(GraphQLObject)] is only defined for structs with named fields",
+            ))
+        }
+    };
+    let mut field_quotes = TokenStream2::new();
+    for field in fields.named.iter() {
+        let field_attrs = GraphQLAttrs::parse(&field.attrs)?;
+        if field_attrs.skip {
+            continue;
+        }
+        let field_name =
+            field_attrs.name(field.ident.as_ref().unwrap(), attrs.rename_all.as_ref())?;
+        let field_description = field_attrs.description(&field.attrs);
+        let field_type = &field.ty;
+        let new_field = match (&field_attrs.resolve, &field_attrs.async_resolve) {
+            (Some(_), Some(_)) => {
+                return Err(syn::Error::new_spanned(
+                    field,
+                    "`resolve` and `async_resolve` can't be both set",
+                ))
+            }
+            (Some(resolve), None) => quote! {
+                rust_graphql_resolver::schema::field::Field::new(#field_name, field_type, #field_description, #resolve())
+            },
+            (None, Some(resolve)) => quote! {
+                rust_graphql_resolver::schema::field::Field::new_async(#field_name, field_type, #field_description, #resolve())
+            },
+            (None, None) => quote! {
+                rust_graphql_resolver::schema::field::Field::new(
+                    #field_name,
+                    field_type,
+                    #field_description,
+                    Box::new(rust_graphql_resolver::schema::resolve::DefaultFieldResolveFunc),
+                )
+            },
+        };
+        field_quotes.extend(quote! {
+            let field_type = <#field_type as rust_graphql_resolver::builder::types::OutputType>::field_type(schema)?;
+            builder = builder.add_field(#field_name, #new_field);
+        });
+    }
+
+    Ok(quote! {
+        impl rust_graphql_resolver::builder::types::OutputType for #struct_name {
+            fn field_type(
+                schema: &rust_graphql_resolver::builder::schema::SchemaBuilder,
+            ) -> rust_graphql_resolver::error::BuildResult<rust_graphql_resolver::schema::field::FieldType> {
+                Ok(rust_graphql_resolver::schema::field::FieldType::NonNullType(Box::new(
+                    schema.get_object_type(#type_name)?,
+                )))
+            }
+        }
+
+        impl rust_graphql_resolver::builder::types::ObjectType for #struct_name {
+            fn type_name() -> &'static str {
+                #type_name
+            }
+
+            fn object_type(
+                schema: &rust_graphql_resolver::builder::schema::SchemaBuilder,
+            ) -> rust_graphql_resolver::error::BuildResult<rust_graphql_resolver::schema::field::CustomType> {
+                let mut builder = rust_graphql_resolver::builder::field::CustomTypeBuilder::new(#type_name)
+                    .set_description(#type_description);
+                #field_quotes
+                builder.build_ok()
+            }
+        }
+    })
+}
+
+/// the graphql name of an enum variant, from `rename` or the `rename_all` of the enum
+fn variant_name(variant: &syn::Variant, rename_all: Option<&syn::LitStr>) -> syn::Result<String> {
+    GraphQLAttrs::parse(&variant.attrs)?.name(&variant.ident, rename_all)
+}
+
+fn is_fieldless(enum_data: &syn::DataEnum) -> bool {
+    enum_data
+        .variants
+        .iter()
+        .all(|variant| matches!(variant.fields, syn::Fields::Unit))
+}
+
+fn enum_data_value(ast: &DeriveInput, enum_data: &syn::DataEnum) -> syn::Result<TokenStream2> {
+    let enum_name = &ast.ident;
+    let attrs = GraphQLAttrs::parse(&ast.attrs)?;
+    let generics = add_trait_bounds(
+        &ast.generics,
+        syn::parse_quote!(rust_graphql_resolver::value::ToDataValue),
+    );
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let fieldless = is_fieldless(enum_data);
+
+    let mut arms = TokenStream2::new();
+    for variant in enum_data.variants.iter() {
+        let variant_ident = &variant.ident;
+        let variant_name = variant_name(variant, attrs.rename_all.as_ref())?;
+        let insert_typename = quote! {
+            map.insert("__typename".to_string(), DataValue::String(#variant_name.to_string()));
+        };
+        arms.extend(match &variant.fields {
+            syn::Fields::Unit if fieldless => quote! {
+                Self::#variant_ident => DataValue::String(#variant_name.to_string()),
+            },
+            syn::Fields::Unit => quote! {
+                Self::#variant_ident => {
+                    let mut map = IndexMap::new();
+                    #insert_typename
+                    DataValue::Object(map)
+                }
+            },
+            syn::Fields::Named(fields) => {
+                let inserts = insert_fields(fields, None, |field_ident| quote! { #field_ident })?;
+                let field_idents = fields.named.iter().map(|field| &field.ident);
                 quote! {
-                    (#field_name.to_string(), self.#field_ident.to_data_value()),
+                    #[allow(unused_variables)]
+                    Self::#variant_ident { #(#field_idents),* } => {
+                        let mut map = IndexMap::new();
+                        #inserts
+                        #insert_typename
+                        DataValue::Object(map)
+                    }
                 }
-            })
-            .reduce(|mut a, b| -> TokenStream2 {
-                a.extend(b);
-                a
-            })
-            .unwrap();
+            }
+            syn::Fields::Unnamed(fields) if fields.unnamed.len() == 1 => {
+                let field_attrs = GraphQLAttrs::parse(&fields.unnamed[0].attrs)?;
+                let data_value = field_attrs.data_value(quote! { value });
+                quote! {
+                    Self::#variant_ident(value) => match #data_value {
+                        DataValue::Object(mut map) => {
+                            #insert_typename
+                            DataValue::Object(map)
+                        }
+                        value => value,
+                    },
+                }
+            }
+            syn::Fields::Unnamed(fields) => {
+                return Err(syn::Error::new_spanned(
+                    fields,
+                    "tuple variants must hold exactly one object",
+                ))
+            }
+        });
+    }
 
-        let quoted_code = quote! {
-            impl rust_graphql_resolver::value::ToDataValue for #struct_name {
-                fn to_data_value(&self) -> rust_graphql_resolver::value::DataValue {
-                    use std::{array::IntoIter, collections::BTreeMap, iter::FromIterator};
+    Ok(quote! {
+        impl #impl_generics rust_graphql_resolver::value::ToDataValue for #enum_name #ty_generics #where_clause {
+            fn to_data_value(&self) -> rust_graphql_resolver::value::DataValue {
+                use rust_graphql_resolver::value::DataValue;
+                use rust_graphql_resolver::indexmap::IndexMap;
 
-                    rust_graphql_resolver::value::DataValue::Object(BTreeMap::from_iter(IntoIter::new([
-                        #field_quotes
-                    ])))
+                match self {
+                    #arms
                 }
             }
-        };
-        proc_macro::TokenStream::from(quoted_code)
-    } else {
-        // not a struct
-        panic!("#[derive(GraphQLDataValue)] is only defined for structs");
+        }
+    })
+}
+
+/// Derive the graphql enum type of a fieldless enum, with `EnumType` and `OutputType`.
+/// Descriptions of the enum and its values are taken from doc comments or `#[graphql(description = "...")]`,
+/// `#[graphql(rename = "...")]` renames the enum or a value
+/// ```compile_fail
+/// #[derive(Debug, Clone, GraphQLDataValue, GraphQLEnum)]
+/// enum Color {
+///     /// the color of fire
+///     Red,
+///     Green,
+/// }
+/// ```
+#[proc_macro_derive(GraphQLEnum, attributes(graphql))]
+pub fn graphql_enum(input: TokenStream) -> TokenStream {
+    let ast = parse_macro_input!(input as DeriveInput);
+    match enum_type(&ast) {
+        Ok(quoted_code) => proc_macro::TokenStream::from(quoted_code),
+        Err(err) => proc_macro::TokenStream::from(err.to_compile_error()),
+    }
+}
+
+fn enum_type(ast: &DeriveInput) -> syn::Result<TokenStream2> {
+    let enum_name = &ast.ident;
+    let attrs = GraphQLAttrs::parse(&ast.attrs)?;
+    let type_name = attrs
+        .rename
+        .clone()
+        .unwrap_or_else(|| enum_name.to_string());
+    let type_description = attrs.description(&ast.attrs);
+
+    let enum_data = match &ast.data {
+        syn::Data::Enum(enum_data) if is_fieldless(enum_data) => enum_data,
+        _ => {
+            return Err(syn::Error::new_spanned(
+                ast,
+                "#[derive(GraphQLEnum)] is only defined for fieldless enums",
+            ))
+        }
+    };
+    let mut value_quotes = TokenStream2::new();
+    for variant in enum_data.variants.iter() {
+        let variant_attrs = GraphQLAttrs::parse(&variant.attrs)?;
+        let variant_name = variant_name(variant, attrs.rename_all.as_ref())?;
+        let variant_description = variant_attrs.description(&variant.attrs);
+        value_quotes.extend(quote! {
+            .add_value_with_desc(#variant_name, #variant_description)
+        });
+    }
+
+    Ok(quote! {
+        impl rust_graphql_resolver::builder::types::OutputType for #enum_name {
+            fn field_type(
+                schema: &rust_graphql_resolver::builder::schema::SchemaBuilder,
+            ) -> rust_graphql_resolver::error::BuildResult<rust_graphql_resolver::schema::field::FieldType> {
+                Ok(rust_graphql_resolver::schema::field::FieldType::NonNullType(Box::new(
+                    schema.get_enum_type(#type_name)?,
+                )))
+            }
+        }
+
+        impl rust_graphql_resolver::builder::types::EnumType for #enum_name {
+            fn enum_type() -> rust_graphql_resolver::schema::field::QLEnum {
+                rust_graphql_resolver::builder::field::QLEnumBuilder::new(#type_name)
+                    .set_description(#type_description)
+                    #value_quotes
+                    .build()
+            }
+        }
+    })
+}
+
+/// Derive the graphql union type of an enum with data, with `UnionType` and `OutputType`.
+/// Each variant is a member object type of the same name (or `#[graphql(rename = "...")]`),
+/// which must be added to the schema too
+/// ```compile_fail
+/// #[derive(Debug, Clone, GraphQLDataValue, GraphQLUnion)]
+/// enum SearchResult {
+///     User(User),
+///     Pet(Pet),
+/// }
+/// ```
+#[proc_macro_derive(GraphQLUnion, attributes(graphql))]
+pub fn graphql_union(input: TokenStream) -> TokenStream {
+    let ast = parse_macro_input!(input as DeriveInput);
+    match union_type(&ast) {
+        Ok(quoted_code) => proc_macro::TokenStream::from(quoted_code),
+        Err(err) => proc_macro::TokenStream::from(err.to_compile_error()),
     }
 }
+
+fn union_type(ast: &DeriveInput) -> syn::Result<TokenStream2> {
+    let enum_name = &ast.ident;
+    let attrs = GraphQLAttrs::parse(&ast.attrs)?;
+    let type_name = attrs
+        .rename
+        .clone()
+        .unwrap_or_else(|| enum_name.to_string());
+    let type_description = attrs.description(&ast.attrs);
+
+    let enum_data = match &ast.data {
+        syn::Data::Enum(enum_data) => enum_data,
+        _ => {
+            return Err(syn::Error::new_spanned(
+                ast,
+                "#[derive(GraphQLUnion)] is only defined for enums",
+            ))
+        }
+    };
+    let member_names = enum_data
+        .variants
+        .iter()
+        .map(|variant| variant_name(variant, attrs.rename_all.as_ref()))
+        .collect::<syn::Result<Vec<_>>>()?;
+
+    Ok(quote! {
+        impl rust_graphql_resolver::builder::types::OutputType for #enum_name {
+            fn field_type(
+                schema: &rust_graphql_resolver::builder::schema::SchemaBuilder,
+            ) -> rust_graphql_resolver::error::BuildResult<rust_graphql_resolver::schema::field::FieldType> {
+                Ok(rust_graphql_resolver::schema::field::FieldType::NonNullType(Box::new(
+                    schema.get_union_type(#type_name)?,
+                )))
+            }
+        }
+
+        impl rust_graphql_resolver::builder::types::UnionType for #enum_name {
+            fn union_type() -> rust_graphql_resolver::schema::field::QLUnion {
+                rust_graphql_resolver::builder::field::QLUnionBuilder::new(#type_name)
+                    .set_description(#type_description)
+                    #(.add_type(#member_names))*
+                    .build()
+            }
+        }
+    })
+}
@@ -0,0 +1,264 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use crate::{
+    error::BuildError,
+    schema::{
+        field::{ArgumentMap, CustomType, FieldType, InputFieldType, QLEnum, StaticType},
+        meta::{MUTATION_TYPE_NAME, QUERY_TYPE_NAME, SUBSCRIPTION_TYPE_NAME},
+        read_lock, Schema,
+    },
+};
+
+/// Check the consistency of the schema types, every problem found is returned, in type name order
+pub(crate) fn check_schema(schema: &Schema) -> Vec<BuildError> {
+    let mut checker = Checker {
+        schema,
+        inline_types: BTreeSet::new(),
+        errors: vec![],
+    };
+
+    for (name, query) in sorted(&schema.queries) {
+        checker.check_field(QUERY_TYPE_NAME, name, &query.field_type, &query.arguments);
+    }
+    for (name, mutation) in sorted(schema.mutations.iter().flatten()) {
+        checker.check_field(
+            MUTATION_TYPE_NAME,
+            name,
+            &mutation.field_type,
+            &mutation.arguments,
+        );
+    }
+    for (name, subscription) in sorted(schema.subscritions.iter().flatten()) {
+        checker.check_field(
+            SUBSCRIPTION_TYPE_NAME,
+            name,
+            &subscription.field_type,
+            &subscription.arguments,
+        );
+    }
+    for (name, object) in sorted(&schema.objects) {
+        checker.check_object(name, &read_lock(object));
+    }
+    for (name, interface) in sorted(&schema.interfaces) {
+        let interface = read_lock(interface);
+        checker.check_name(name);
+        checker.check_not_empty(name, interface.fields.len());
+        for (field_name, field) in interface.fields.iter() {
+            checker.check_field(name, field_name, &field.field_type, &field.arguments);
+        }
+    }
+    for (name, input) in sorted(&schema.inputs) {
+        let input = read_lock(input);
+        checker.check_name(name);
+        checker.check_not_empty(name, input.fields.len());
+        checker.check_input_values(name, &input.fields);
+    }
+    for (name, enum_type) in sorted(&schema.enums) {
+        checker.check_enum(name, enum_type);
+    }
+    for (name, union_type) in sorted(&schema.unions) {
+        checker.check_name(name);
+        checker.check_not_empty(name, union_type.types.len());
+        for member in union_type.types.iter() {
+            if !schema.objects.contains_key(member) {
+                checker.push(BuildError::UnresolvedType(name.to_string(), member.clone()));
+            }
+        }
+    }
+    for name in sorted(&schema.scalars).map(|(name, _)| name) {
+        checker.check_name(name);
+    }
+    // the inline types are known once every field is walked
+    checker.check_unique_names();
+    checker.errors
+}
+
+/// the entries of a type map by name, the maps are hash maps
+fn sorted<'a, V: 'a>(
+    map: impl IntoIterator<Item = (&'a String, &'a V)>,
+) -> impl Iterator<Item = (&'a str, &'a V)> {
+    map.into_iter()
+        .map(|(name, value)| (name.as_str(), value))
+        .collect::<BTreeMap<_, _>>()
+        .into_iter()
+}
+
+/// a graphql name `/[_A-Za-z][_0-9A-Za-z]*/`, the `__` prefix is reserved for introspection
+fn is_valid_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    let valid_start = matches!(chars.next(), Some(c) if c == '_' || c.is_ascii_alphabetic());
+    valid_start && !name.starts_with("__") && chars.all(|c| c == '_' || c.is_ascii_alphanumeric())
+}
+
+struct Checker<'a> {
+    schema: &'a Schema,
+    /// names of the types defined where they are used instead of in the type maps,
+    /// the copies of an inline type have the same name
+    inline_types: BTreeSet<String>,
+    errors: Vec<BuildError>,
+}
+
+impl<'a> Checker<'a> {
+    fn push(&mut self, error: BuildError) {
+        self.errors.push(error);
+    }
+
+    /// a name is used by one type only, built-in scalars, root operation types and inline types included.
+    /// These errors come first
+    fn check_unique_names(&mut self) {
+        let schema = self.schema;
+        let mut counts: HashMap<&str, usize> = HashMap::new();
+        let mut roots = vec![QUERY_TYPE_NAME];
+        if schema.mutations.is_some() {
+            roots.push(MUTATION_TYPE_NAME);
+        }
+        if schema.subscritions.is_some() {
+            roots.push(SUBSCRIPTION_TYPE_NAME);
+        }
+        let names = schema
+            .objects
+            .keys()
+            .chain(schema.enums.keys())
+            .chain(schema.inputs.keys())
+            .chain(schema.interfaces.keys())
+            .chain(schema.unions.keys())
+            .chain(schema.scalars.keys())
+            .chain(self.inline_types.iter())
+            .map(String::as_str)
+            .chain(roots);
+        for name in names {
+            *counts.entry(name).or_default() += 1;
+        }
+        let mut duplicates = counts
+            .into_iter()
+            .filter(|(name, count)| *count > 1 || StaticType::from_name(name).is_some())
+            .map(|(name, _)| name)
+            .collect::<Vec<_>>();
+        duplicates.sort_unstable();
+        let duplicates = duplicates
+            .into_iter()
+            .map(|name| BuildError::DuplicateType(name.to_string()));
+        self.errors.splice(0..0, duplicates);
+    }
+
+    fn check_name(&mut self, coordinate: &str) {
+        let name = coordinate.rsplit('.').next().unwrap_or_default();
+        if !is_valid_name(name) {
+            self.push(BuildError::InvalidName(coordinate.to_string()));
+        }
+    }
+
+    fn check_not_empty(&mut self, name: &str, len: usize) {
+        if len == 0 {
+            self.push(BuildError::EmptyType(name.to_string()));
+        }
+    }
+
+    fn check_object(&mut self, name: &str, object: &CustomType) {
+        self.check_name(name);
+        self.check_not_empty(name, object.fields.len());
+        for interface in object.interfaces.iter() {
+            if !self.schema.interfaces.contains_key(interface) {
+                self.push(BuildError::UnresolvedType(
+                    name.to_string(),
+                    interface.clone(),
+                ));
+            }
+        }
+        for (field_name, field) in object.fields.iter() {
+            self.check_field(name, field_name, &field.field_type, &field.arguments);
+        }
+    }
+
+    fn check_enum(&mut self, name: &str, enum_type: &QLEnum) {
+        self.check_name(name);
+        self.check_not_empty(name, enum_type.values.len());
+        for value in enum_type.values.iter() {
+            // enum values cannot be confused with the other literals
+            if ["true", "false", "null"].contains(&value.value.as_str())
+                || !is_valid_name(&value.value)
+            {
+                self.push(BuildError::InvalidName(format!("{}.{}", name, value.value)));
+            }
+        }
+    }
+
+    fn check_field(
+        &mut self,
+        type_name: &str,
+        name: &str,
+        field_type: &FieldType,
+        arguments: &ArgumentMap,
+    ) {
+        let coordinate = format!("{}.{}", type_name, name);
+        self.check_name(&coordinate);
+        self.check_output_type(&coordinate, field_type);
+        for (argument_name, argument) in arguments.iter() {
+            let coordinate = format!("{}({}:)", coordinate, argument_name);
+            if !is_valid_name(argument_name) {
+                self.push(BuildError::InvalidName(coordinate.clone()));
+            }
+            self.check_input_type(&coordinate, &argument.field_type);
+        }
+    }
+
+    fn check_input_values(&mut self, type_name: &str, fields: &ArgumentMap) {
+        for (field_name, field) in fields.iter() {
+            let coordinate = format!("{}.{}", type_name, field_name);
+            self.check_name(&coordinate);
+            self.check_input_type(&coordinate, &field.field_type);
+        }
+    }
+
+    fn check_output_type(&mut self, coordinate: &str, field_type: &FieldType) {
+        match field_type {
+            FieldType::NonNullType(t) | FieldType::List(t) => self.check_output_type(coordinate, t),
+            // inline types are not in the type maps, they are checked where they are used
+            FieldType::CustomType(custom_type) => {
+                let name = &custom_type.name;
+                if self.schema.inputs.contains_key(name) {
+                    self.push(BuildError::InputTypeAsOutput(
+                        coordinate.to_string(),
+                        name.clone(),
+                    ));
+                } else {
+                    self.inline_types.insert(name.clone());
+                }
+                self.check_object(name, custom_type);
+            }
+            FieldType::Enum(enum_type) => {
+                self.inline_types.insert(enum_type.name.clone());
+                self.check_enum(&enum_type.name, enum_type);
+            }
+            FieldType::ReferenceCustom(weak) if weak.upgrade().is_none() => {
+                self.push(BuildError::DanglingReference(coordinate.to_string()))
+            }
+            FieldType::ReferenceInterface(weak) if weak.upgrade().is_none() => {
+                self.push(BuildError::DanglingReference(coordinate.to_string()))
+            }
+            _ => {}
+        }
+    }
+
+    fn check_input_type(&mut self, coordinate: &str, field_type: &InputFieldType) {
+        match field_type {
+            InputFieldType::NonNullType(t) | InputFieldType::List(t) => {
+                self.check_input_type(coordinate, t)
+            }
+            InputFieldType::ReferenceInput(weak) if weak.upgrade().is_none() => {
+                self.push(BuildError::DanglingReference(coordinate.to_string()))
+            }
+            InputFieldType::QLInput(input) => {
+                self.inline_types.insert(input.name.clone());
+                self.check_name(&input.name);
+                self.check_not_empty(&input.name, input.fields.len());
+                self.check_input_values(&input.name, &input.fields);
+            }
+            InputFieldType::Enum(enum_type) => {
+                self.inline_types.insert(enum_type.name.clone());
+                self.check_enum(&enum_type.name, enum_type);
+            }
+            _ => {}
+        }
+    }
+}
@@ -1,10 +1,17 @@
-use std::collections::BTreeMap;
+use std::{collections::BTreeMap, sync::Arc};
 
 use crate::{
-    error::BuildResult,
-    schema::field::{
-        CustomType, Field, FieldType, InputField, InputFieldType, QLEnum, QLEnumValue, QLInput,
+    error::{BuildError, BuildResult},
+    schema::{
+        field::{
+            CustomType, Field, FieldType, InputField, InputFieldType, QLEnum, QLEnumValue, QLInput,
+            QLInterface, QLScalar, QLUnion,
+        },
+        resolve::{
+            DefaultScalarConvertFunc, DefaultTypeResolveFunc, ScalarConvertFunc, TypeResolveFunc,
+        },
     },
+    value::DataValue,
 };
 
 pub struct CustomTypeBuilder {
@@ -18,6 +25,7 @@ impl CustomTypeBuilder {
                 name: name.to_string(),
                 fields: BTreeMap::new(),
                 description: String::default(),
+                interfaces: vec![],
             },
         }
     }
@@ -34,15 +42,233 @@ impl CustomTypeBuilder {
         FieldType::CustomType(self.status)
     }
 
+    pub fn add_field(mut self, name: &str, field: Field) -> Self {
+        self.status.fields.insert(name.to_string(), Arc::new(field));
+        self
+    }
+
+    /// Declare an argument of the field named `field`, which must be added before
+    pub fn add_field_argument(
+        self,
+        field: &str,
+        name: &str,
+        argument: InputFieldType,
+    ) -> BuildResult<Self> {
+        self.insert_field_argument(field, InputField::simple(name, argument))
+    }
+
+    /// `default` is passed to the resolve function when the argument is not provided
+    pub fn add_field_argument_with_default(
+        self,
+        field: &str,
+        name: &str,
+        argument: InputFieldType,
+        default: DataValue,
+    ) -> BuildResult<Self> {
+        self.insert_field_argument(
+            field,
+            InputField::simple_with_default(name, argument, default),
+        )
+    }
+
+    fn insert_field_argument(mut self, field: &str, argument: InputField) -> BuildResult<Self> {
+        let coordinate = format!("{}.{}", self.status.name, field);
+        let field = self
+            .status
+            .fields
+            .get_mut(field)
+            .ok_or(BuildError::NoSuchField(coordinate))?;
+        Arc::make_mut(field)
+            .arguments
+            .insert(argument.name.clone(), argument);
+        Ok(self)
+    }
+
+    pub fn set_description(mut self, desc: &str) -> Self {
+        self.status.description = desc.to_string();
+        self
+    }
+
+    /// Declare an interface implemented by this type, its fields must be added as well
+    pub fn add_interface(mut self, name: &str) -> Self {
+        self.status.interfaces.push(name.to_string());
+        self
+    }
+}
+
+/// Continue building a type, such as the one of `#[derive(GraphQLObject)]`
+impl From<CustomType> for CustomTypeBuilder {
+    fn from(custom_type: CustomType) -> Self {
+        Self {
+            status: custom_type,
+        }
+    }
+}
+
+pub struct QLInterfaceBuilder {
+    status: QLInterface,
+}
+
+impl QLInterfaceBuilder {
+    pub fn new(name: &str) -> Self {
+        Self {
+            status: QLInterface {
+                name: name.to_string(),
+                fields: BTreeMap::new(),
+                description: String::default(),
+                resolve_type: Box::new(DefaultTypeResolveFunc),
+            },
+        }
+    }
+
+    pub fn build(self) -> QLInterface {
+        self.status
+    }
+
+    pub fn build_ok(self) -> BuildResult<QLInterface> {
+        Ok(self.status)
+    }
+
     pub fn add_field(mut self, name: &str, field: Field) -> Self {
         self.status.fields.insert(name.to_string(), field);
         self
     }
 
+    /// Declare an argument of the field named `field`, which must be added before
+    pub fn add_field_argument(
+        self,
+        field: &str,
+        name: &str,
+        argument: InputFieldType,
+    ) -> BuildResult<Self> {
+        self.insert_field_argument(field, InputField::simple(name, argument))
+    }
+
+    /// `default` is passed to the resolve function when the argument is not provided
+    pub fn add_field_argument_with_default(
+        self,
+        field: &str,
+        name: &str,
+        argument: InputFieldType,
+        default: DataValue,
+    ) -> BuildResult<Self> {
+        self.insert_field_argument(
+            field,
+            InputField::simple_with_default(name, argument, default),
+        )
+    }
+
+    fn insert_field_argument(mut self, field: &str, argument: InputField) -> BuildResult<Self> {
+        let coordinate = format!("{}.{}", self.status.name, field);
+        let field = self
+            .status
+            .fields
+            .get_mut(field)
+            .ok_or(BuildError::NoSuchField(coordinate))?;
+        field.arguments.insert(argument.name.clone(), argument);
+        Ok(self)
+    }
+
+    pub fn set_description(mut self, desc: &str) -> Self {
+        self.status.description = desc.to_string();
+        self
+    }
+
+    /// Set the function picking the concrete type, `DefaultTypeResolveFunc` is used if not set
+    pub fn set_resolve_type(mut self, resolve_type: Box<dyn TypeResolveFunc>) -> Self {
+        self.status.resolve_type = resolve_type;
+        self
+    }
+}
+
+pub struct QLUnionBuilder {
+    status: QLUnion,
+}
+
+impl QLUnionBuilder {
+    pub fn new(name: &str) -> Self {
+        Self {
+            status: QLUnion {
+                name: name.to_string(),
+                types: vec![],
+                description: String::default(),
+                resolve_type: Box::new(DefaultTypeResolveFunc),
+            },
+        }
+    }
+
+    pub fn build(self) -> QLUnion {
+        self.status
+    }
+
+    pub fn build_ok(self) -> BuildResult<QLUnion> {
+        Ok(self.status)
+    }
+
+    /// Add a member object type by name
+    pub fn add_type(mut self, name: &str) -> Self {
+        self.status.types.push(name.to_string());
+        self
+    }
+
+    pub fn set_description(mut self, desc: &str) -> Self {
+        self.status.description = desc.to_string();
+        self
+    }
+
+    /// Set the function picking the concrete type, `DefaultTypeResolveFunc` is used if not set
+    pub fn set_resolve_type(mut self, resolve_type: Box<dyn TypeResolveFunc>) -> Self {
+        self.status.resolve_type = resolve_type;
+        self
+    }
+}
+
+pub struct QLScalarBuilder {
+    status: QLScalar,
+}
+
+impl QLScalarBuilder {
+    pub fn new(name: &str) -> Self {
+        Self {
+            status: QLScalar {
+                name: name.to_string(),
+                description: String::default(),
+                specified_by_url: None,
+                serialize: Box::new(DefaultScalarConvertFunc),
+                parse: Box::new(DefaultScalarConvertFunc),
+            },
+        }
+    }
+
+    pub fn build(self) -> QLScalar {
+        self.status
+    }
+
+    pub fn build_ok(self) -> BuildResult<QLScalar> {
+        Ok(self.status)
+    }
+
     pub fn set_description(mut self, desc: &str) -> Self {
         self.status.description = desc.to_string();
         self
     }
+
+    pub fn set_specified_by_url(mut self, url: &str) -> Self {
+        self.status.specified_by_url = Some(url.to_string());
+        self
+    }
+
+    /// Set the function converting the resolved values, values are kept as they are if not set
+    pub fn set_serialize(mut self, serialize: Box<dyn ScalarConvertFunc>) -> Self {
+        self.status.serialize = serialize;
+        self
+    }
+
+    /// Set the function checking and converting the input values, values are kept as they are if not set
+    pub fn set_parse(mut self, parse: Box<dyn ScalarConvertFunc>) -> Self {
+        self.status.parse = parse;
+        self
+    }
 }
 
 pub struct QLEnumBuilder {
@@ -122,6 +348,20 @@ impl QLInputBuilder {
         self
     }
 
+    /// Add a field of `field_type`, `default` is used when the field is not provided
+    pub fn add_field_with_default(
+        mut self,
+        name: &str,
+        field_type: InputFieldType,
+        default: DataValue,
+    ) -> Self {
+        self.status.fields.insert(
+            name.to_string(),
+            InputField::simple_with_default(name, field_type, default),
+        );
+        self
+    }
+
     pub fn set_description(mut self, desc: &str) -> Self {
         self.status.description = desc.to_string();
         self
@@ -1,5 +1,9 @@
+mod check;
 pub mod field;
 pub mod mutation;
 pub mod query;
 pub mod schema;
+pub mod sdl;
+pub mod subscription;
+pub mod types;
 pub mod value;
@@ -1,16 +1,23 @@
 use crate::{
     error::BuildResult,
     schema::{
-        field::{ArgumentMap, FieldType, InputFieldType, StaticType},
+        field::{ArgumentMap, FieldType, InputField, InputFieldType, StaticType},
         query::Query,
-        resolve::{ApiResolveFunc, DefaultApiResolveFunc},
+        resolve::{ApiResolveFunc, ApiResolver, AsyncApiResolveFunc, DefaultApiResolveFunc},
     },
+    value::DataValue,
 };
 
 pub struct QueryBuilder {
     status: Query,
 }
 
+impl Default for QueryBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl QueryBuilder {
     pub fn new() -> Self {
         Self {
@@ -30,7 +37,21 @@ impl QueryBuilder {
     pub fn add_argument(mut self, name: &str, argument_type: InputFieldType) -> Self {
         self.status
             .arguments
-            .insert(name.to_string(), argument_type);
+            .insert(name.to_string(), InputField::simple(name, argument_type));
+        self
+    }
+
+    /// `default` is passed to the resolve function when the argument is not provided
+    pub fn add_argument_with_default(
+        mut self,
+        name: &str,
+        argument_type: InputFieldType,
+        default: DataValue,
+    ) -> Self {
+        self.status.arguments.insert(
+            name.to_string(),
+            InputField::simple_with_default(name, argument_type, default),
+        );
         self
     }
 
@@ -40,7 +61,12 @@ impl QueryBuilder {
     }
 
     pub fn set_resolve(mut self, resolve: Box<dyn ApiResolveFunc>) -> Self {
-        self.status.resolve = resolve;
+        self.status.resolve = ApiResolver::Sync(resolve);
+        self
+    }
+
+    pub fn set_async_resolve(mut self, resolve: Box<dyn AsyncApiResolveFunc>) -> Self {
+        self.status.resolve = ApiResolver::Async(resolve);
         self
     }
 }
@@ -50,6 +76,6 @@ fn uninitialized_query() -> Query {
         field_type: FieldType::StaticType(StaticType::Boolean),
         arguments: ArgumentMap::default(),
         description: String::default(),
-        resolve: Box::new(DefaultApiResolveFunc),
+        resolve: ApiResolver::Sync(Box::new(DefaultApiResolveFunc)),
     }
 }
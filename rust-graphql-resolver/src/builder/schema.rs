@@ -1,15 +1,28 @@
-use std::{cell::RefCell, collections::HashMap, rc::Rc};
+use std::{
+    collections::HashMap,
+    sync::{Arc, OnceLock, RwLock},
+};
 
 use crate::{
     error::{BuildError, BuildResult},
     schema::{
-        field::{CustomType, FieldType, InputFieldType, QLEnum, QLInput},
+        field::{
+            CustomType, FieldType, InputFieldType, QLEnum, QLInput, QLInterface, QLScalar, QLUnion,
+        },
         mutation::{Mutation, MutationMap},
         query::{Query, QueryMap},
-        Schema,
+        subscription::{Subscription, SubscriptionMap},
+        write_lock, Schema,
     },
 };
 
+use super::{
+    check,
+    field::CustomTypeBuilder,
+    sdl::{self, ResolverMap},
+    types::ObjectType,
+};
+
 /// For building a Schema using interfaces
 pub struct SchemaBuilder {
     status: Schema,
@@ -26,13 +39,30 @@ impl SchemaBuilder {
                 objects: HashMap::default(),
                 enums: HashMap::default(),
                 inputs: HashMap::default(),
+                interfaces: HashMap::default(),
+                unions: HashMap::default(),
+                scalars: HashMap::default(),
+                meta: OnceLock::new(),
             },
         }
     }
 
-    /// Final action to build the Schema
+    /// Final action to build the Schema, fails with every problem found by `check`
     pub fn build(self) -> BuildResult<Schema> {
-        Ok(self.status)
+        let errors = self.check();
+        if errors.is_empty() {
+            // collected now rather than by the first request
+            self.status.meta();
+            Ok(self.status)
+        } else {
+            Err(BuildError::InvalidSchema(errors))
+        }
+    }
+
+    /// Check the consistency of the types: references resolve, names are unique and valid,
+    /// types are not empty and input types are not used as output types
+    pub fn check(&self) -> Vec<BuildError> {
+        check::check_schema(&self.status)
     }
 
     /// Add a query to Schema
@@ -61,11 +91,24 @@ impl SchemaBuilder {
         Ok(self)
     }
 
+    /// Add a subscription to Schema
+    pub fn add_subscription<F>(mut self, name: &str, subscription_func: F) -> BuildResult<Self>
+    where
+        F: Fn(&Self) -> BuildResult<Subscription>,
+    {
+        let subscription = subscription_func(&self)?;
+        self.status
+            .subscritions
+            .get_or_insert_with(SubscriptionMap::new)
+            .insert(name.to_string(), subscription);
+        Ok(self)
+    }
+
     /// Add a object (CustomType) to Schema for reference use
     pub fn add_object(mut self, custom_type: CustomType) -> Self {
         self.status
             .objects
-            .insert(custom_type.name.clone(), Rc::new(RefCell::new(custom_type)));
+            .insert(custom_type.name.clone(), Arc::new(RwLock::new(custom_type)));
         self
     }
 
@@ -76,15 +119,34 @@ impl SchemaBuilder {
         let custom_type = custom_type_func(&self)?;
         self.status
             .objects
-            .insert(custom_type.name.clone(), Rc::new(RefCell::new(custom_type)));
+            .insert(custom_type.name.clone(), Arc::new(RwLock::new(custom_type)));
         Ok(self)
     }
 
+    /// Declare a object type by name before it is added with `add_object_type`,
+    /// so object types can reference each other in any order
+    pub fn declare_object(mut self, name: &str) -> Self {
+        self.status
+            .objects
+            .entry(name.to_string())
+            .or_insert_with(|| Arc::new(RwLock::new(CustomTypeBuilder::new(name).build())));
+        self
+    }
+
+    /// Add the object type of a rust struct. It is declared first and filled in place,
+    /// so its fields can reference itself and the references taken while declared stay valid
+    pub fn add_object_type<T: ObjectType>(self) -> BuildResult<Self> {
+        let builder = self.declare_object(T::type_name());
+        let custom_type = T::object_type(&builder)?;
+        *write_lock(&builder.status.objects[T::type_name()]) = custom_type;
+        Ok(builder)
+    }
+
     /// Add a enum type to Schema for reference use
     pub fn add_enum(mut self, enum_type: QLEnum) -> Self {
         self.status
             .enums
-            .insert(enum_type.name.clone(), Rc::new(enum_type));
+            .insert(enum_type.name.clone(), Arc::new(enum_type));
         self
     }
 
@@ -92,7 +154,7 @@ impl SchemaBuilder {
     pub fn add_input_object(mut self, input_object: QLInput) -> Self {
         self.status.inputs.insert(
             input_object.name.clone(),
-            Rc::new(RefCell::new(input_object)),
+            Arc::new(RwLock::new(input_object)),
         );
         self
     }
@@ -104,7 +166,50 @@ impl SchemaBuilder {
         let input_type = input_object_func(&self)?;
         self.status
             .inputs
-            .insert(input_type.name.clone(), Rc::new(RefCell::new(input_type)));
+            .insert(input_type.name.clone(), Arc::new(RwLock::new(input_type)));
+        Ok(self)
+    }
+
+    /// Add a interface type to Schema for reference use
+    pub fn add_interface(mut self, interface_type: QLInterface) -> Self {
+        self.status.interfaces.insert(
+            interface_type.name.clone(),
+            Arc::new(RwLock::new(interface_type)),
+        );
+        self
+    }
+
+    pub fn add_interface_with_status<F>(mut self, interface_func: F) -> BuildResult<Self>
+    where
+        F: Fn(&Self) -> BuildResult<QLInterface>,
+    {
+        let interface_type = interface_func(&self)?;
+        self.status.interfaces.insert(
+            interface_type.name.clone(),
+            Arc::new(RwLock::new(interface_type)),
+        );
+        Ok(self)
+    }
+
+    /// Add a union type to Schema for reference use
+    pub fn add_union(mut self, union_type: QLUnion) -> Self {
+        self.status
+            .unions
+            .insert(union_type.name.clone(), Arc::new(union_type));
+        self
+    }
+
+    /// Add a custom scalar type to Schema for reference use
+    pub fn add_scalar(mut self, scalar: QLScalar) -> Self {
+        self.status
+            .scalars
+            .insert(scalar.name.clone(), Arc::new(scalar));
+        self
+    }
+
+    /// Add the types and root fields defined in SDL, with their resolve functions
+    pub fn add_sdl(mut self, sdl: &str, resolvers: ResolverMap) -> BuildResult<Self> {
+        sdl::load(&mut self.status, sdl, resolvers)?;
         Ok(self)
     }
 
@@ -113,9 +218,9 @@ impl SchemaBuilder {
         let ref_rc = self
             .status
             .objects
-            .get(&name.to_string())
+            .get(name)
             .ok_or(BuildError::NoSuchObjectType(name.to_string()))?;
-        Ok(FieldType::ReferenceCustom(Rc::downgrade(ref_rc)))
+        Ok(FieldType::ReferenceCustom(Arc::downgrade(ref_rc)))
     }
 
     /// Get the reference enum type
@@ -123,7 +228,7 @@ impl SchemaBuilder {
         let rc = self
             .status
             .enums
-            .get(&name.to_string())
+            .get(name)
             .ok_or(BuildError::NoSuchEnumType(name.to_string()))?
             .clone();
         Ok(FieldType::ReferenceEnum(rc))
@@ -134,7 +239,7 @@ impl SchemaBuilder {
         let rc = self
             .status
             .enums
-            .get(&name.to_string())
+            .get(name)
             .ok_or(BuildError::NoSuchEnumType(name.to_string()))?
             .clone();
         Ok(InputFieldType::ReferenceEnum(rc))
@@ -145,8 +250,51 @@ impl SchemaBuilder {
         let ref_rc = self
             .status
             .inputs
-            .get(&name.to_string())
+            .get(name)
             .ok_or(BuildError::NoSuchObjectType(name.to_string()))?;
-        Ok(InputFieldType::ReferenceInput(Rc::downgrade(ref_rc)))
+        Ok(InputFieldType::ReferenceInput(Arc::downgrade(ref_rc)))
+    }
+
+    /// Get the reference interface type
+    pub fn get_interface_type(&self, name: &str) -> BuildResult<FieldType> {
+        let ref_rc = self
+            .status
+            .interfaces
+            .get(name)
+            .ok_or(BuildError::NoSuchInterfaceType(name.to_string()))?;
+        Ok(FieldType::ReferenceInterface(Arc::downgrade(ref_rc)))
+    }
+
+    /// Get the reference union type
+    pub fn get_union_type(&self, name: &str) -> BuildResult<FieldType> {
+        let rc = self
+            .status
+            .unions
+            .get(name)
+            .ok_or(BuildError::NoSuchUnionType(name.to_string()))?
+            .clone();
+        Ok(FieldType::ReferenceUnion(rc))
+    }
+
+    /// Get the reference custom scalar type
+    pub fn get_scalar_type(&self, name: &str) -> BuildResult<FieldType> {
+        let rc = self
+            .status
+            .scalars
+            .get(name)
+            .ok_or(BuildError::NoSuchScalarType(name.to_string()))?
+            .clone();
+        Ok(FieldType::ReferenceScalar(rc))
+    }
+
+    /// Get the reference custom scalar type as input field
+    pub fn get_scalar_input_type(&self, name: &str) -> BuildResult<InputFieldType> {
+        let rc = self
+            .status
+            .scalars
+            .get(name)
+            .ok_or(BuildError::NoSuchScalarType(name.to_string()))?
+            .clone();
+        Ok(InputFieldType::ReferenceScalar(rc))
     }
 }
@@ -0,0 +1,743 @@
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    sync::{Arc, RwLock},
+};
+
+use gurkle_parser::schema::{
+    Definition, EnumValue, Field as SdlField, InputValue, Type, TypeDefinition, TypeExtension,
+    Value,
+};
+
+use crate::{
+    error::{BuildError, BuildResult},
+    schema::{
+        field::{
+            ArgumentMap, CustomType, Field, FieldType, InputField, InputFieldType, QLEnum,
+            QLEnumValue, QLInput, QLInterface, QLScalar, QLUnion, StaticType,
+        },
+        meta::{MUTATION_TYPE_NAME, QUERY_TYPE_NAME, SUBSCRIPTION_TYPE_NAME},
+        mutation::{Mutation, MutationMap},
+        query::Query,
+        resolve::{
+            ApiResolveFunc, ApiResolver, AsyncApiResolveFunc, AsyncFieldResolveFunc,
+            DefaultFieldResolveFunc, DefaultScalarConvertFunc, DefaultTypeResolveFunc,
+            FieldResolveFunc, FieldResolver, SubscriptionResolveFunc, TypeResolveFunc,
+        },
+        subscription::{Subscription, SubscriptionMap},
+        write_lock, Schema,
+    },
+    value::DataValue,
+};
+
+/// ResolverMap
+///
+/// The resolve functions of the fields defined in SDL, keyed by field coordinate, e.g. `Query.user` or `User.posts`.
+/// The type resolve functions of interface and union types are keyed by type name
+#[derive(Clone, Default)]
+pub struct ResolverMap {
+    apis: HashMap<String, ApiResolver>,
+    subscriptions: HashMap<String, Box<dyn SubscriptionResolveFunc>>,
+    fields: HashMap<String, FieldResolver>,
+    type_resolvers: HashMap<String, Box<dyn TypeResolveFunc>>,
+    /// object fields without resolve function take the value of the parent data
+    resolve_from_source: bool,
+}
+
+impl ResolverMap {
+    fn take_field_resolve(&mut self, coordinate: &str) -> BuildResult<FieldResolver> {
+        match self.fields.remove(coordinate) {
+            Some(resolve) => Ok(resolve),
+            None if self.resolve_from_source => {
+                Ok(FieldResolver::Sync(Box::new(DefaultFieldResolveFunc)))
+            }
+            None => Err(BuildError::MissingResolver(coordinate.to_string())),
+        }
+    }
+
+    fn take_type_resolve(&mut self, type_name: &str) -> Box<dyn TypeResolveFunc> {
+        self.type_resolvers
+            .remove(type_name)
+            .unwrap_or_else(|| Box::new(DefaultTypeResolveFunc))
+    }
+
+    /// coordinates of the resolve functions not taken by any field or type, sorted
+    fn unused(&self) -> Vec<String> {
+        let mut unused = self
+            .apis
+            .keys()
+            .chain(self.subscriptions.keys())
+            .chain(self.fields.keys())
+            .chain(self.type_resolvers.keys())
+            .cloned()
+            .collect::<Vec<_>>();
+        unused.sort();
+        unused
+    }
+}
+
+#[derive(Default)]
+pub struct ResolverMapBuilder {
+    status: ResolverMap,
+}
+
+impl ResolverMapBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn build(self) -> ResolverMap {
+        self.status
+    }
+
+    /// Resolve function of a query or mutation field, e.g. `Query.user`
+    pub fn add_api_resolve(mut self, coordinate: &str, resolve: Box<dyn ApiResolveFunc>) -> Self {
+        self.status
+            .apis
+            .insert(coordinate.to_string(), ApiResolver::Sync(resolve));
+        self
+    }
+
+    pub fn add_async_api_resolve(
+        mut self,
+        coordinate: &str,
+        resolve: Box<dyn AsyncApiResolveFunc>,
+    ) -> Self {
+        self.status
+            .apis
+            .insert(coordinate.to_string(), ApiResolver::Async(resolve));
+        self
+    }
+
+    /// Resolve function of a subscription field, e.g. `Subscription.messages`
+    pub fn add_subscription_resolve(
+        mut self,
+        coordinate: &str,
+        resolve: Box<dyn SubscriptionResolveFunc>,
+    ) -> Self {
+        self.status
+            .subscriptions
+            .insert(coordinate.to_string(), resolve);
+        self
+    }
+
+    /// Resolve function of an object field, e.g. `User.posts`
+    pub fn add_field_resolve(
+        mut self,
+        coordinate: &str,
+        resolve: Box<dyn FieldResolveFunc>,
+    ) -> Self {
+        self.status
+            .fields
+            .insert(coordinate.to_string(), FieldResolver::Sync(resolve));
+        self
+    }
+
+    pub fn add_async_field_resolve(
+        mut self,
+        coordinate: &str,
+        resolve: Box<dyn AsyncFieldResolveFunc>,
+    ) -> Self {
+        self.status
+            .fields
+            .insert(coordinate.to_string(), FieldResolver::Async(resolve));
+        self
+    }
+
+    /// Object fields without resolve function take the value of the parent data,
+    /// instead of failing to load with `BuildError::MissingResolver`
+    pub fn resolve_from_source(mut self) -> Self {
+        self.status.resolve_from_source = true;
+        self
+    }
+
+    /// Function picking the concrete type of an interface or union, `DefaultTypeResolveFunc` is used if not set
+    pub fn set_type_resolve(mut self, type_name: &str, resolve: Box<dyn TypeResolveFunc>) -> Self {
+        self.status
+            .type_resolvers
+            .insert(type_name.to_string(), resolve);
+        self
+    }
+}
+
+/// Add the types of a SDL document to the schema, along with the root fields of its query, mutation
+/// and subscription types.
+///
+/// The types are declared before their fields are converted, so they can reference each other in any order.
+/// Extensions are merged into the types of the document, or extend the objects, interfaces and input objects
+/// of the schema. A scalar of the schema is kept, so that its functions can be set with `add_scalar` first.
+/// Every problem found is returned in `BuildError::InvalidSchema`.
+pub(crate) fn load(schema: &mut Schema, sdl: &str, resolvers: ResolverMap) -> BuildResult<()> {
+    let doc = gurkle_parser::parse_schema(sdl).map_err(|err| {
+        BuildError::InvalidSchema(vec![BuildError::SdlParseError(format!("{:?}", err))])
+    })?;
+    let mut loader = Loader {
+        schema,
+        resolvers,
+        roots: Roots::default(),
+        errors: vec![],
+    };
+    let mut types = vec![];
+    let mut extensions = vec![];
+    for definition in doc.definitions {
+        match definition {
+            Definition::SchemaDefinition(def) => {
+                let roots = &mut loader.roots;
+                roots.query = def.query.unwrap_or(std::mem::take(&mut roots.query));
+                roots.mutation = def.mutation.unwrap_or(std::mem::take(&mut roots.mutation));
+                roots.subscription = def
+                    .subscription
+                    .unwrap_or(std::mem::take(&mut roots.subscription));
+            }
+            Definition::TypeDefinition(t) => types.push(t),
+            Definition::TypeExtension(e) => extensions.push(e),
+            // directives only annotate the definitions, they are not executed
+            Definition::DirectiveDefinition(_) => {}
+        }
+    }
+    // the types of the schema which are extended, their fields are added to the existing ones
+    let mut extending = HashSet::new();
+    for extension in extensions {
+        if let Some(t) = merge_extension(&mut types, extension) {
+            match loader.check_extension(&t) {
+                Ok(()) => {
+                    extending.insert(type_name(&t).to_string());
+                    types.push(t);
+                }
+                Err(err) => loader.errors.push(err),
+            }
+        }
+    }
+
+    // the fields of a type failing to be declared are not converted
+    let mut declared = vec![];
+    for t in types.iter() {
+        let name = type_name(t);
+        if extending.contains(name) || loader.roots.contains(name) {
+            declared.push(t);
+            continue;
+        }
+        match loader.declare_type(t) {
+            Ok(()) => declared.push(t),
+            Err(err) => loader.errors.push(err),
+        }
+    }
+    for t in declared {
+        loader.fill_type(t);
+    }
+
+    let unused = loader.resolvers.unused();
+    loader
+        .errors
+        .extend(unused.into_iter().map(BuildError::UnknownResolver));
+    if loader.errors.is_empty() {
+        Ok(())
+    } else {
+        Err(BuildError::InvalidSchema(loader.errors))
+    }
+}
+
+/// Names of the root operation types, the default ones unless the schema definition names them
+struct Roots {
+    query: String,
+    mutation: String,
+    subscription: String,
+}
+
+impl Default for Roots {
+    fn default() -> Self {
+        Self {
+            query: QUERY_TYPE_NAME.to_string(),
+            mutation: MUTATION_TYPE_NAME.to_string(),
+            subscription: SUBSCRIPTION_TYPE_NAME.to_string(),
+        }
+    }
+}
+
+impl Roots {
+    fn contains(&self, name: &str) -> bool {
+        self.query == name || self.mutation == name || self.subscription == name
+    }
+}
+
+fn type_name(t: &TypeDefinition) -> &str {
+    match t {
+        TypeDefinition::Scalar(t) => &t.name,
+        TypeDefinition::Object(t) => &t.name,
+        TypeDefinition::Interface(t) => &t.name,
+        TypeDefinition::Union(t) => &t.name,
+        TypeDefinition::Enum(t) => &t.name,
+        TypeDefinition::InputObject(t) => &t.name,
+    }
+}
+
+fn no_such_type(t: &TypeDefinition) -> BuildError {
+    let name = type_name(t).to_string();
+    match t {
+        TypeDefinition::Scalar(_) => BuildError::NoSuchScalarType(name),
+        TypeDefinition::Object(_) => BuildError::NoSuchObjectType(name),
+        TypeDefinition::Interface(_) => BuildError::NoSuchInterfaceType(name),
+        TypeDefinition::Union(_) => BuildError::NoSuchUnionType(name),
+        TypeDefinition::Enum(_) => BuildError::NoSuchEnumType(name),
+        TypeDefinition::InputObject(_) => BuildError::NoSuchInputType(name),
+    }
+}
+
+/// Merge the extension into the definition of the same type, the extension is returned as a definition
+/// when the document doesn't define the type
+fn merge_extension(
+    types: &mut [TypeDefinition],
+    extension: TypeExtension,
+) -> Option<TypeDefinition> {
+    let extension = extension_definition(extension);
+    let found = types.iter_mut().find(|t| {
+        type_name(t) == type_name(&extension)
+            && std::mem::discriminant(*t) == std::mem::discriminant(&extension)
+    });
+    match (found, extension) {
+        (Some(TypeDefinition::Object(t)), TypeDefinition::Object(e)) => {
+            t.implements_interfaces.extend(e.implements_interfaces);
+            t.fields.extend(e.fields);
+        }
+        (Some(TypeDefinition::Interface(t)), TypeDefinition::Interface(e)) => {
+            t.fields.extend(e.fields)
+        }
+        (Some(TypeDefinition::Union(t)), TypeDefinition::Union(e)) => t.types.extend(e.types),
+        (Some(TypeDefinition::Enum(t)), TypeDefinition::Enum(e)) => t.values.extend(e.values),
+        (Some(TypeDefinition::InputObject(t)), TypeDefinition::InputObject(e)) => {
+            t.fields.extend(e.fields)
+        }
+        (Some(_), _) => {}
+        (None, extension) => return Some(extension),
+    }
+    None
+}
+
+/// the extension as a definition without description
+fn extension_definition(extension: TypeExtension) -> TypeDefinition {
+    use gurkle_parser::schema as sdl;
+    match extension {
+        TypeExtension::Scalar(e) => TypeDefinition::Scalar(sdl::ScalarType {
+            directives: e.directives,
+            ..sdl::ScalarType::new(e.name)
+        }),
+        TypeExtension::Object(e) => TypeDefinition::Object(sdl::ObjectType {
+            implements_interfaces: e.implements_interfaces,
+            fields: e.fields,
+            ..sdl::ObjectType::new(e.name)
+        }),
+        TypeExtension::Interface(e) => TypeDefinition::Interface(sdl::InterfaceType {
+            fields: e.fields,
+            ..sdl::InterfaceType::new(e.name)
+        }),
+        TypeExtension::Union(e) => TypeDefinition::Union(sdl::UnionType {
+            types: e.types,
+            ..sdl::UnionType::new(e.name)
+        }),
+        TypeExtension::Enum(e) => TypeDefinition::Enum(sdl::EnumType {
+            values: e.values,
+            ..sdl::EnumType::new(e.name)
+        }),
+        TypeExtension::InputObject(e) => TypeDefinition::InputObject(sdl::InputObjectType {
+            fields: e.fields,
+            ..sdl::InputObjectType::new(e.name)
+        }),
+    }
+}
+
+fn is_defined(schema: &Schema, name: &str) -> bool {
+    StaticType::from_name(name).is_some()
+        || schema.objects.contains_key(name)
+        || schema.enums.contains_key(name)
+        || schema.inputs.contains_key(name)
+        || schema.interfaces.contains_key(name)
+        || schema.unions.contains_key(name)
+        || schema.scalars.contains_key(name)
+}
+
+/// the parser keeps the line break ending a block string
+fn description(description: &Option<String>) -> String {
+    description
+        .as_deref()
+        .unwrap_or_default()
+        .trim_end_matches('\n')
+        .to_string()
+}
+
+fn enum_value(value: &EnumValue) -> QLEnumValue {
+    QLEnumValue {
+        value: value.name.clone(),
+        description: description(&value.description),
+    }
+}
+
+/// Loader
+///
+/// The schema being extended with the types of a SDL document, with the problems found so far
+struct Loader<'a> {
+    schema: &'a mut Schema,
+    resolvers: ResolverMap,
+    roots: Roots,
+    errors: Vec<BuildError>,
+}
+
+impl Loader<'_> {
+    /// an extension not merged into the document extends a type of the schema, or a root type
+    fn check_extension(&self, t: &TypeDefinition) -> BuildResult<()> {
+        let schema = &self.schema;
+        let name = type_name(t);
+        let exists = match t {
+            TypeDefinition::Object(_) => {
+                self.roots.contains(name) || schema.objects.contains_key(name)
+            }
+            TypeDefinition::Interface(_) => schema.interfaces.contains_key(name),
+            TypeDefinition::InputObject(_) => schema.inputs.contains_key(name),
+            // their references are shared by the fields already built
+            TypeDefinition::Scalar(_) | TypeDefinition::Union(_) | TypeDefinition::Enum(_) => {
+                if is_defined(schema, name) {
+                    return Err(BuildError::UnsupportedExtension(name.to_string()));
+                }
+                false
+            }
+        };
+        if exists {
+            Ok(())
+        } else {
+            Err(no_such_type(t))
+        }
+    }
+
+    /// Add the type to the schema, objects, interfaces and input objects without their fields
+    fn declare_type(&mut self, t: &TypeDefinition) -> BuildResult<()> {
+        let schema = &mut *self.schema;
+        let name = type_name(t).to_string();
+        if let TypeDefinition::Scalar(scalar) = t {
+            // the built-in scalars and the scalars set with `add_scalar` are kept
+            if StaticType::from_name(&name).is_some() || schema.scalars.contains_key(&name) {
+                return Ok(());
+            }
+            if is_defined(schema, &name) {
+                return Err(BuildError::DuplicateType(name));
+            }
+            let specified_by_url = scalar
+                .directives
+                .iter()
+                .filter(|d| d.name == "specifiedBy")
+                .flat_map(|d| d.arguments.iter())
+                .find_map(|(arg, value)| match (arg.as_str(), value) {
+                    ("url", Value::String(url)) => Some(url.clone()),
+                    _ => None,
+                });
+            let scalar = QLScalar {
+                name: name.clone(),
+                description: description(&scalar.description),
+                specified_by_url,
+                serialize: Box::new(DefaultScalarConvertFunc),
+                parse: Box::new(DefaultScalarConvertFunc),
+            };
+            schema.scalars.insert(name, Arc::new(scalar));
+            return Ok(());
+        }
+        if is_defined(schema, &name) {
+            return Err(BuildError::DuplicateType(name));
+        }
+        match t {
+            TypeDefinition::Object(object) => {
+                let object = CustomType {
+                    name: name.clone(),
+                    fields: BTreeMap::new(),
+                    description: description(&object.description),
+                    interfaces: vec![],
+                };
+                schema.objects.insert(name, Arc::new(RwLock::new(object)));
+            }
+            TypeDefinition::Interface(interface) => {
+                let interface = QLInterface {
+                    name: name.clone(),
+                    fields: BTreeMap::new(),
+                    description: description(&interface.description),
+                    resolve_type: self.resolvers.take_type_resolve(&name),
+                };
+                schema
+                    .interfaces
+                    .insert(name, Arc::new(RwLock::new(interface)));
+            }
+            TypeDefinition::Union(union_type) => {
+                let union_type = QLUnion {
+                    name: name.clone(),
+                    types: union_type.types.clone(),
+                    description: description(&union_type.description),
+                    resolve_type: self.resolvers.take_type_resolve(&name),
+                };
+                schema.unions.insert(name, Arc::new(union_type));
+            }
+            TypeDefinition::Enum(enum_type) => {
+                let enum_type = QLEnum {
+                    name: name.clone(),
+                    description: description(&enum_type.description),
+                    values: enum_type.values.iter().map(enum_value).collect(),
+                };
+                schema.enums.insert(name, Arc::new(enum_type));
+            }
+            TypeDefinition::InputObject(input) => {
+                let input = QLInput {
+                    name: name.clone(),
+                    fields: BTreeMap::new(),
+                    description: description(&input.description),
+                };
+                schema.inputs.insert(name, Arc::new(RwLock::new(input)));
+            }
+            TypeDefinition::Scalar(_) => {}
+        }
+        Ok(())
+    }
+
+    /// Convert the fields of a declared type, and check the types it references
+    fn fill_type(&mut self, t: &TypeDefinition) {
+        let name = type_name(t);
+        match t {
+            TypeDefinition::Object(object) if self.roots.contains(name) => {
+                for field in object.fields.iter() {
+                    self.add_root_field(name, field);
+                }
+            }
+            TypeDefinition::Object(object) => {
+                for interface in object.implements_interfaces.iter() {
+                    if !self.schema.interfaces.contains_key(interface) {
+                        self.errors.push(BuildError::UnresolvedType(
+                            name.to_string(),
+                            interface.clone(),
+                        ));
+                    }
+                }
+                let mut fields = vec![];
+                for field in object.fields.iter() {
+                    let coordinate = format!("{}.{}", name, field.name);
+                    let resolve = self.resolvers.take_field_resolve(&coordinate);
+                    match (self.object_field(&coordinate, field), resolve) {
+                        (Some(mut field), Ok(resolve)) => {
+                            field.resolve = resolve;
+                            fields.push(field);
+                        }
+                        (_, Err(err)) => self.errors.push(err),
+                        (None, Ok(_)) => {}
+                    }
+                }
+                let mut object_type = write_lock(&self.schema.objects[name]);
+                object_type
+                    .interfaces
+                    .extend(object.implements_interfaces.iter().cloned());
+                object_type
+                    .fields
+                    .extend(fields.into_iter().map(|f| (f.name.clone(), Arc::new(f))));
+            }
+            TypeDefinition::Interface(interface) => {
+                // the fields of an interface are executed by the objects implementing it
+                let fields = interface
+                    .fields
+                    .iter()
+                    .filter_map(|field| {
+                        self.object_field(&format!("{}.{}", name, field.name), field)
+                    })
+                    .collect::<Vec<_>>();
+                write_lock(&self.schema.interfaces[name])
+                    .fields
+                    .extend(fields.into_iter().map(|f| (f.name.clone(), f)));
+            }
+            TypeDefinition::Union(union_type) => {
+                for member in union_type.types.iter() {
+                    if !self.schema.objects.contains_key(member) {
+                        self.errors
+                            .push(BuildError::UnresolvedType(name.to_string(), member.clone()));
+                    }
+                }
+            }
+            TypeDefinition::InputObject(input) => {
+                let fields = self.input_values(name, &input.fields, false);
+                write_lock(&self.schema.inputs[name]).fields.extend(fields);
+            }
+            TypeDefinition::Scalar(_) | TypeDefinition::Enum(_) => {}
+        }
+    }
+
+    /// the field with the default resolve function, `None` when a type it references is unknown
+    fn object_field(&mut self, coordinate: &str, field: &SdlField) -> Option<Field> {
+        let arguments = self.input_values(coordinate, &field.arguments, true);
+        let field_type = output_type(self.schema, &field.field_type, coordinate)
+            .map_err(|err| self.errors.push(err))
+            .ok()?;
+        Some(Field {
+            name: field.name.clone(),
+            field_type,
+            arguments,
+            description: description(&field.description),
+            resolve: FieldResolver::Sync(Box::new(DefaultFieldResolveFunc)),
+        })
+    }
+
+    /// a field of the query, mutation or subscription type, it must have a resolve function
+    fn add_root_field(&mut self, type_name: &str, field: &SdlField) {
+        let coordinate = format!("{}.{}", type_name, field.name);
+        let subscription = self.roots.subscription == type_name;
+        // the resolve function is taken first, so that it is not reported as unused
+        let resolve = if subscription {
+            self.resolvers
+                .subscriptions
+                .remove(&coordinate)
+                .map(RootResolve::Subscription)
+        } else {
+            self.resolvers
+                .apis
+                .remove(&coordinate)
+                .map(RootResolve::Api)
+        };
+        let field = self.object_field(&coordinate, field);
+        let (field, resolve) = match (field, resolve) {
+            (Some(field), Some(resolve)) => (field, resolve),
+            (_, None) => return self.errors.push(BuildError::MissingResolver(coordinate)),
+            (None, Some(_)) => return,
+        };
+        let schema = &mut *self.schema;
+        match resolve {
+            RootResolve::Subscription(resolve) => {
+                let subscription = Subscription {
+                    field_type: field.field_type,
+                    arguments: field.arguments,
+                    description: field.description,
+                    resolve,
+                };
+                schema
+                    .subscritions
+                    .get_or_insert_with(SubscriptionMap::new)
+                    .insert(field.name, subscription);
+            }
+            RootResolve::Api(resolve) if self.roots.query == type_name => {
+                let query = Query {
+                    field_type: field.field_type,
+                    arguments: field.arguments,
+                    description: field.description,
+                    resolve,
+                };
+                schema.queries.insert(field.name, query);
+            }
+            RootResolve::Api(resolve) => {
+                let mutation = Mutation {
+                    field_type: field.field_type,
+                    arguments: field.arguments,
+                    description: field.description,
+                    resolve,
+                };
+                schema
+                    .mutations
+                    .get_or_insert_with(MutationMap::new)
+                    .insert(field.name, mutation);
+            }
+        }
+    }
+
+    /// the arguments of a field, or the fields of an input object, values of unknown types are left out
+    fn input_values(
+        &mut self,
+        parent: &str,
+        values: &[InputValue],
+        arguments: bool,
+    ) -> ArgumentMap {
+        let mut map = ArgumentMap::new();
+        for value in values {
+            let coordinate = if arguments {
+                format!("{}({}:)", parent, value.name)
+            } else {
+                format!("{}.{}", parent, value.name)
+            };
+            let field_type = match input_type(self.schema, &value.value_type, &coordinate) {
+                Ok(field_type) => field_type,
+                Err(err) => {
+                    self.errors.push(err);
+                    continue;
+                }
+            };
+            let mut input =
+                InputField::new(&value.name, field_type, &description(&value.description));
+            if let Some(default) = &value.default_value {
+                input = input.set_default(DataValue::from(default.clone()));
+            }
+            map.insert(value.name.clone(), input);
+        }
+        map
+    }
+}
+
+/// the resolve function of a root field
+enum RootResolve {
+    Api(ApiResolver),
+    Subscription(Box<dyn SubscriptionResolveFunc>),
+}
+
+fn output_type(schema: &Schema, t: &Type, coordinate: &str) -> BuildResult<FieldType> {
+    let name = match t {
+        Type::NonNullType(t) => {
+            return Ok(FieldType::NonNullType(Box::new(output_type(
+                schema, t, coordinate,
+            )?)))
+        }
+        Type::ListType(t) => {
+            return Ok(FieldType::List(Box::new(output_type(
+                schema, t, coordinate,
+            )?)))
+        }
+        Type::NamedType(name) => name,
+    };
+    if let Some(t) = StaticType::from_name(name) {
+        return Ok(FieldType::StaticType(t));
+    }
+    if let Some(rc) = schema.scalars.get(name) {
+        return Ok(FieldType::ReferenceScalar(rc.clone()));
+    }
+    if let Some(rc) = schema.enums.get(name) {
+        return Ok(FieldType::ReferenceEnum(rc.clone()));
+    }
+    if let Some(rc) = schema.objects.get(name) {
+        return Ok(FieldType::ReferenceCustom(Arc::downgrade(rc)));
+    }
+    if let Some(rc) = schema.interfaces.get(name) {
+        return Ok(FieldType::ReferenceInterface(Arc::downgrade(rc)));
+    }
+    if let Some(rc) = schema.unions.get(name) {
+        return Ok(FieldType::ReferenceUnion(rc.clone()));
+    }
+    Err(BuildError::UnresolvedType(
+        coordinate.to_string(),
+        name.clone(),
+    ))
+}
+
+fn input_type(schema: &Schema, t: &Type, coordinate: &str) -> BuildResult<InputFieldType> {
+    let name = match t {
+        Type::NonNullType(t) => {
+            return Ok(InputFieldType::NonNullType(Box::new(input_type(
+                schema, t, coordinate,
+            )?)))
+        }
+        Type::ListType(t) => {
+            return Ok(InputFieldType::List(Box::new(input_type(
+                schema, t, coordinate,
+            )?)))
+        }
+        Type::NamedType(name) => name,
+    };
+    if let Some(t) = StaticType::from_name(name) {
+        return Ok(InputFieldType::StaticType(t));
+    }
+    if let Some(rc) = schema.scalars.get(name) {
+        return Ok(InputFieldType::ReferenceScalar(rc.clone()));
+    }
+    if let Some(rc) = schema.enums.get(name) {
+        return Ok(InputFieldType::ReferenceEnum(rc.clone()));
+    }
+    if let Some(rc) = schema.inputs.get(name) {
+        return Ok(InputFieldType::ReferenceInput(Arc::downgrade(rc)));
+    }
+    Err(BuildError::UnresolvedType(
+        coordinate.to_string(),
+        name.clone(),
+    ))
+}
@@ -0,0 +1,76 @@
+use crate::{
+    error::BuildResult,
+    schema::{
+        field::{ArgumentMap, FieldType, InputField, InputFieldType, StaticType},
+        resolve::{DefaultSubscriptionResolveFunc, SubscriptionResolveFunc},
+        subscription::Subscription,
+    },
+    value::DataValue,
+};
+
+pub struct SubscriptionBuilder {
+    status: Subscription,
+}
+
+impl Default for SubscriptionBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SubscriptionBuilder {
+    pub fn new() -> Self {
+        Self {
+            status: uninitialized_subscription(),
+        }
+    }
+
+    pub fn build(self) -> BuildResult<Subscription> {
+        Ok(self.status)
+    }
+
+    pub fn set_type(mut self, field_type: FieldType) -> Self {
+        self.status.field_type = field_type;
+        self
+    }
+
+    pub fn add_argument(mut self, name: &str, argument_type: InputFieldType) -> Self {
+        self.status
+            .arguments
+            .insert(name.to_string(), InputField::simple(name, argument_type));
+        self
+    }
+
+    /// `default` is passed to the resolve function when the argument is not provided
+    pub fn add_argument_with_default(
+        mut self,
+        name: &str,
+        argument_type: InputFieldType,
+        default: DataValue,
+    ) -> Self {
+        self.status.arguments.insert(
+            name.to_string(),
+            InputField::simple_with_default(name, argument_type, default),
+        );
+        self
+    }
+
+    pub fn set_description(mut self, desc: &str) -> Self {
+        self.status.description = desc.to_string();
+        self
+    }
+
+    pub fn set_resolve(mut self, resolve: Box<dyn SubscriptionResolveFunc>) -> Self {
+        self.status.resolve = resolve;
+        self
+    }
+}
+
+fn uninitialized_subscription() -> Subscription {
+    Subscription {
+        field_type: FieldType::StaticType(StaticType::Boolean),
+        arguments: ArgumentMap::default(),
+        description: String::default(),
+        resolve: Box::new(DefaultSubscriptionResolveFunc),
+    }
+}
@@ -0,0 +1,101 @@
+use chrono::{DateTime, Utc};
+
+use crate::{
+    error::BuildResult,
+    schema::field::{CustomType, FieldType, QLEnum, QLUnion, StaticType},
+};
+
+use super::schema::SchemaBuilder;
+
+/// For rust types described by a graphql output type.
+/// Rust types are non null, except `Option<T>` which is the nullable `T`
+pub trait OutputType {
+    /// the field type of this rust type, object types are referenced from `schema`, so add them first
+    fn field_type(schema: &SchemaBuilder) -> BuildResult<FieldType>;
+}
+
+/// For rust structs described by a graphql object type, implemented by `#[derive(GraphQLObject)]`
+/// ```ignore
+/// SchemaBuilder::new("schema").add_object_type::<User>()?
+/// ```
+pub trait ObjectType: OutputType {
+    /// the graphql name of the object type
+    fn type_name() -> &'static str;
+
+    fn object_type(schema: &SchemaBuilder) -> BuildResult<CustomType>;
+}
+
+/// For fieldless rust enums described by a graphql enum type, implemented by `#[derive(GraphQLEnum)]`
+/// ```ignore
+/// SchemaBuilder::new("schema").add_enum(Color::enum_type())
+/// ```
+pub trait EnumType: OutputType {
+    fn enum_type() -> QLEnum;
+}
+
+/// For rust enums with data described by a graphql union type, implemented by `#[derive(GraphQLUnion)]`.
+/// The member object types are named by the variants
+/// ```ignore
+/// SchemaBuilder::new("schema").add_union(SearchResult::union_type())
+/// ```
+pub trait UnionType: OutputType {
+    fn union_type() -> QLUnion;
+}
+
+fn non_null_static(static_type: StaticType) -> BuildResult<FieldType> {
+    Ok(FieldType::NonNullType(Box::new(FieldType::StaticType(
+        static_type,
+    ))))
+}
+
+impl OutputType for String {
+    fn field_type(_schema: &SchemaBuilder) -> BuildResult<FieldType> {
+        non_null_static(StaticType::String)
+    }
+}
+
+impl OutputType for str {
+    fn field_type(_schema: &SchemaBuilder) -> BuildResult<FieldType> {
+        non_null_static(StaticType::String)
+    }
+}
+
+macro_rules! output_type_static {
+    ($static_type:expr, $($t:ty),*) => {
+        $(
+            impl OutputType for $t {
+                fn field_type(_schema: &SchemaBuilder) -> BuildResult<FieldType> {
+                    non_null_static($static_type)
+                }
+            }
+        )*
+    };
+}
+
+output_type_static!(StaticType::Int, i64, i32, i16, i8, u64, u32, u16, u8);
+output_type_static!(StaticType::Float, f64, f32);
+output_type_static!(StaticType::Boolean, bool);
+output_type_static!(StaticType::DateTime, DateTime<Utc>);
+
+impl<T: OutputType> OutputType for Option<T> {
+    fn field_type(schema: &SchemaBuilder) -> BuildResult<FieldType> {
+        match T::field_type(schema)? {
+            FieldType::NonNullType(t) => Ok(*t),
+            t => Ok(t),
+        }
+    }
+}
+
+impl<T: OutputType> OutputType for Vec<T> {
+    fn field_type(schema: &SchemaBuilder) -> BuildResult<FieldType> {
+        Ok(FieldType::NonNullType(Box::new(FieldType::List(Box::new(
+            T::field_type(schema)?,
+        )))))
+    }
+}
+
+impl<T: OutputType + ?Sized> OutputType for Box<T> {
+    fn field_type(schema: &SchemaBuilder) -> BuildResult<FieldType> {
+        T::field_type(schema)
+    }
+}
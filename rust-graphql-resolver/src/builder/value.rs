@@ -1,17 +1,22 @@
-use std::collections::BTreeMap;
-
 use chrono::{DateTime, Utc};
+use indexmap::IndexMap;
 
 use crate::value::DataValue;
 
 pub struct DataValueObjectBuilder {
-    status: BTreeMap<String, DataValue>,
+    status: IndexMap<String, DataValue>,
+}
+
+impl Default for DataValueObjectBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl DataValueObjectBuilder {
     pub fn new() -> Self {
         Self {
-            status: BTreeMap::new(),
+            status: IndexMap::new(),
         }
     }
 
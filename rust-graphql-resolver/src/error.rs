@@ -10,10 +10,22 @@ pub enum Error {
     NotFoundError(String),
     #[error("DataTypeMisMatchError expect: {0}, actul: {1}")]
     DataTypeMisMatchError(String, String),
+    #[error("Cannot convert the value of '{0}': {1}")]
+    ConvertDataValueError(String, String),
     #[error("Missing reference custom type")]
     MissingReferenceCustomTypeError,
     #[error("Mutation schema not defined")]
     MutationSchemaNotDefined,
+    #[error("Subscription schema not defined")]
+    SubscriptionSchemaNotDefined,
+    #[error("Subscription operation must select only one top level field")]
+    SubscriptionSingleRootField,
+    #[error("Subscription operation must be executed as a stream, use `subscribe`")]
+    SubscriptionNeedsStream,
+    #[error("Cannot return null for non-nullable type {0}")]
+    NullForNonNullType(String),
+    #[error("Type {1} is not a possible type of {0}")]
+    NotPossibleType(String, String),
 
     #[error("Must provide operation name if query contains multiple operations")]
     MultipleOperationNeedTarget,
@@ -24,6 +36,48 @@ pub enum Error {
 
     #[error("No such Fragment {0}")]
     NoSuchFragment(String),
+
+    #[error("Cannot query field '{1}' on type {0}")]
+    UnknownField(String, String),
+    #[error("Field '{0}' must not have a selection since type {1} has no subfields")]
+    LeafFieldSelection(String, String),
+    #[error("Field '{0}' of type {1} must have a selection of subfields")]
+    MissingSubselection(String, String),
+    #[error("Fields '{0}' conflict because {1}, use different aliases on the fields")]
+    FieldsConflict(String, String),
+    #[error("Unknown argument '{1}' on field {0}")]
+    UnknownArgument(String, String),
+    #[error("Argument '{0}' got invalid value: {1}")]
+    InvalidArgumentValue(String, String),
+    #[error("Field {0} argument '{1}' of type {2} is required, but it was not provided")]
+    MissingArgument(String, String, String),
+    #[error("Unknown directive '@{0}'")]
+    UnknownDirective(String),
+    #[error("Unknown type {0}")]
+    UnknownType(String),
+    #[error("Fragment cannot condition on non composite type {0}")]
+    FragmentOnNonCompositeType(String),
+    #[error("Fragment {0} is never used")]
+    UnusedFragment(String),
+    #[error("Cannot spread fragment {0} within itself")]
+    FragmentCycle(String),
+    #[error("Variable '${0}' is not defined")]
+    UndefinedVariable(String),
+    #[error("Variable '${0}' is never used")]
+    UnusedVariable(String),
+
+    #[error("Variable '${0}' of required type {1} was not provided")]
+    MissingVariable(String, String),
+    #[error("Variable '${0}' got invalid value: {1}")]
+    InvalidVariableValue(String, String),
+    #[error("Value '{1}' is not a member of enum {0}")]
+    NotEnumMember(String, String),
+    #[error("Invalid value for scalar {0}: {1}")]
+    InvalidScalarValue(String, String),
+    #[error("Unknown field '{1}' on input type {0}")]
+    UnknownInputField(String, String),
+    #[error("Field '{1}' of required type {2} on input type {0} was not provided")]
+    MissingInputField(String, String, String),
 }
 
 pub type Result<T> = std::result::Result<T, Error>;
@@ -36,6 +90,46 @@ pub enum BuildError {
     NoSuchEnumType(String),
     #[error("No such input type {0}, set it first")]
     NoSuchInputType(String),
+    #[error("No such interface type {0}, set it first")]
+    NoSuchInterfaceType(String),
+    #[error("No such union type {0}, set it first")]
+    NoSuchUnionType(String),
+    #[error("No such scalar type {0}, set it first")]
+    NoSuchScalarType(String),
+    #[error("No such field {0}, set it first")]
+    NoSuchField(String),
+    #[error("Parse schema error: {0}")]
+    SdlParseError(String),
+    #[error("Type {0} is defined more than once")]
+    DuplicateType(String),
+    #[error("Unknown type {1} referenced by {0}")]
+    UnresolvedType(String, String),
+    #[error("No resolver for field {0}")]
+    MissingResolver(String),
+    #[error("Resolver {0} does not match any field or type")]
+    UnknownResolver(String),
+    #[error(
+        "Type {0} of the schema cannot be extended, only objects, interfaces and input objects can"
+    )]
+    UnsupportedExtension(String),
+    #[error("The type referenced by {0} is no longer in the schema")]
+    DanglingReference(String),
+    #[error("Type {0} must define one or more fields, values or member types")]
+    EmptyType(String),
+    #[error("{0} is not a valid GraphQL name")]
+    InvalidName(String),
+    #[error("Input type {1} cannot be the type of {0}")]
+    InputTypeAsOutput(String, String),
+    #[error("Invalid schema: {}", join_errors(.0))]
+    InvalidSchema(Vec<BuildError>),
+}
+
+fn join_errors(errors: &[BuildError]) -> String {
+    errors
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join("; ")
 }
 
 pub type BuildResult<T> = std::result::Result<T, BuildError>;
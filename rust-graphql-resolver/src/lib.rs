@@ -1,23 +1,70 @@
 pub mod builder;
 pub mod error;
+pub mod response;
 pub mod schema;
+pub mod validation;
 pub mod value;
 pub use gurkle_parser as ast;
+pub use indexmap;
 pub use macros;
 
-use schema::{resolve::QLContext, Schema};
-use value::DataValue;
+use futures::stream::{self, BoxStream, StreamExt};
+use schema::{
+    resolve::{QLContext, QLVariables},
+    Schema,
+};
 
-use crate::error::{Error, Result};
+use crate::{error::Error, response::Response};
 
+/// Execute a graphql request, the errors are carried in the `Response` instead of failing the request.
+/// It runs synchronously, the future of an async resolver is blocked on, so prefer `execute_async`
+/// for them inside an async runtime
 pub fn execute(
     context: QLContext,
     graphql_request: &str,
     schema: &Schema,
     operation_name: Option<String>,
-) -> Result<DataValue> {
+    variables: QLVariables,
+) -> Response {
     match gurkle_parser::parse_query(graphql_request) {
-        Ok(doc) => schema.execute_document(context, doc, operation_name),
-        Err(err) => Err(Error::ParseError(format!("{:?}", err))),
+        Ok(doc) => schema.execute_document(context, doc, operation_name, variables),
+        Err(err) => Response::from_error(Error::ParseError(format!("{:?}", err))),
+    }
+}
+
+/// Execute a graphql request and await the async resolvers,
+/// independent query fields are resolved concurrently while mutations keep their order
+pub async fn execute_async(
+    context: QLContext,
+    graphql_request: &str,
+    schema: &Schema,
+    operation_name: Option<String>,
+    variables: QLVariables,
+) -> Response {
+    match gurkle_parser::parse_query(graphql_request) {
+        Ok(doc) => {
+            schema
+                .execute_document_async(context, doc, operation_name, variables)
+                .await
+        }
+        Err(err) => Response::from_error(Error::ParseError(format!("{:?}", err))),
+    }
+}
+
+/// Execute a graphql subscription request as a stream of responses, one for each source event.
+/// Queries and mutations result in a stream of their only response
+pub fn subscribe<'a>(
+    context: QLContext,
+    graphql_request: &str,
+    schema: &'a Schema,
+    operation_name: Option<String>,
+    variables: QLVariables,
+) -> BoxStream<'a, Response> {
+    match gurkle_parser::parse_query(graphql_request) {
+        Ok(doc) => schema.subscribe_document(context, doc, operation_name, variables),
+        Err(err) => {
+            let response = Response::from_error(Error::ParseError(format!("{:?}", err)));
+            stream::once(async { response }).boxed()
+        }
     }
 }
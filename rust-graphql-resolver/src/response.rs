@@ -0,0 +1,199 @@
+use std::{collections::BTreeMap, fmt::Display};
+
+use gurkle_parser::Pos;
+use serde::{ser::SerializeMap, Serialize};
+
+use crate::{
+    error::{Error, Result},
+    value::DataValue,
+};
+
+/// PathSegment
+///
+/// One step of the path to a response field, a field's response name (alias or name) or a list index
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PathSegment {
+    Field(String),
+    Index(usize),
+}
+
+impl Display for PathSegment {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            PathSegment::Field(name) => write!(f, "{}", name),
+            PathSegment::Index(index) => write!(f, "{}", index),
+        }
+    }
+}
+
+impl Serialize for PathSegment {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        match self {
+            PathSegment::Field(name) => serializer.serialize_str(name),
+            PathSegment::Index(index) => serializer.serialize_u64(*index as u64),
+        }
+    }
+}
+
+/// the path of a child field or list item
+pub(crate) fn join_path(path: &[PathSegment], segment: PathSegment) -> Vec<PathSegment> {
+    let mut result = path.to_vec();
+    result.push(segment);
+    result
+}
+
+/// Location
+///
+/// Position of the field in the request document, both start from 1
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+pub struct Location {
+    pub line: usize,
+    pub column: usize,
+}
+
+impl From<Pos> for Location {
+    fn from(pos: Pos) -> Self {
+        Self {
+            line: pos.line,
+            column: pos.column,
+        }
+    }
+}
+
+/// QLError
+///
+/// An error in the response, with the path and locations of the field which raises it.
+/// Errors raised before executing (e.g. parsing, variables) have neither.
+#[derive(Debug)]
+pub struct QLError {
+    pub error: Error,
+    pub path: Vec<PathSegment>,
+    pub locations: Vec<Location>,
+}
+
+impl QLError {
+    pub fn new(error: Error) -> Self {
+        Self {
+            error,
+            path: vec![],
+            locations: vec![],
+        }
+    }
+
+    pub fn with_field(error: Error, path: Vec<PathSegment>, location: Location) -> Self {
+        Self {
+            error,
+            path,
+            locations: vec![location],
+        }
+    }
+
+    pub fn with_locations(error: Error, locations: Vec<Location>) -> Self {
+        Self {
+            error,
+            path: vec![],
+            locations,
+        }
+    }
+
+    pub fn message(&self) -> String {
+        self.error.to_string()
+    }
+}
+
+impl Serialize for QLError {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut map = serializer.serialize_map(None)?;
+        map.serialize_entry("message", &self.message())?;
+        if !self.locations.is_empty() {
+            map.serialize_entry("locations", &self.locations)?;
+        }
+        if !self.path.is_empty() {
+            map.serialize_entry("path", &self.path)?;
+        }
+        map.end()
+    }
+}
+
+/// Response
+///
+/// The result of executing a request, serialized as `{"data": ..., "errors": [...], "extensions": {...}}`.
+/// * data: `None` when the request fails before executing, otherwise failed fields are `null`
+/// * errors: omitted in json when empty
+/// * extensions: omitted in json when empty
+#[derive(Debug, Default)]
+pub struct Response {
+    pub data: Option<DataValue>,
+    pub errors: Vec<QLError>,
+    pub extensions: BTreeMap<String, DataValue>,
+}
+
+impl Response {
+    pub fn new(data: DataValue, errors: Vec<QLError>) -> Self {
+        Self {
+            data: Some(data),
+            errors,
+            extensions: BTreeMap::new(),
+        }
+    }
+
+    /// Response of a request failed before executing
+    pub fn from_error(error: Error) -> Self {
+        Self {
+            data: None,
+            errors: vec![QLError::new(error)],
+            extensions: BTreeMap::new(),
+        }
+    }
+
+    /// Response of a request failed before executing, with all the errors (e.g. validation)
+    pub fn from_errors(errors: Vec<QLError>) -> Self {
+        Self {
+            data: None,
+            errors,
+            extensions: BTreeMap::new(),
+        }
+    }
+
+    pub fn add_extension(mut self, name: &str, value: DataValue) -> Self {
+        self.extensions.insert(name.to_string(), value);
+        self
+    }
+
+    pub fn is_ok(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    /// The data if there is no error, otherwise the first error
+    pub fn into_result(self) -> Result<DataValue> {
+        match self.errors.into_iter().next() {
+            Some(err) => Err(err.error),
+            None => Ok(self.data.unwrap_or(DataValue::Null)),
+        }
+    }
+}
+
+impl Serialize for Response {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut map = serializer.serialize_map(None)?;
+        if let Some(data) = &self.data {
+            map.serialize_entry("data", data)?;
+        }
+        if !self.errors.is_empty() {
+            map.serialize_entry("errors", &self.errors)?;
+        }
+        if !self.extensions.is_empty() {
+            map.serialize_entry("extensions", &self.extensions)?;
+        }
+        map.end()
+    }
+}
@@ -1,19 +1,29 @@
 use std::{
-    cell::RefCell,
-    collections::{BTreeMap, HashMap},
-    fmt::Debug,
-    rc::{Rc, Weak},
+    borrow::Cow,
+    collections::BTreeMap,
+    fmt::{Debug, Display},
+    sync::{Arc, RwLock, Weak},
 };
 
-use gurkle_parser::query::{FragmentDefinition, Selection};
+use chrono::{DateTime, Utc};
+use futures::future::{join_all, BoxFuture, FutureExt};
+use indexmap::IndexMap;
+
+use gurkle_parser::{query::Selection, Pos};
 
 use crate::{
     error::{Error, Result},
+    response::{join_path, PathSegment},
     value::DataValue,
 };
 
-use super::resolve::{
-    ArgumentValueMap, DefaultFieldResolveFunc, FieldResolveFunc, QLApiParam, QLContext,
+use super::{
+    read_lock,
+    resolve::{
+        ArgumentValueMap, AsyncFieldResolveFunc, DefaultFieldResolveFunc, FieldResolveFunc,
+        FieldResolver, QLApiParam, QLContext, ScalarConvertFunc, TypeResolveFunc,
+    },
+    response_key, ExecuteEnv, ExecuteResult,
 };
 
 use gurkle_parser::query as ast;
@@ -25,68 +35,253 @@ pub enum FieldType {
     NonNullType(Box<FieldType>),
     List(Box<FieldType>),
     Enum(QLEnum),
-    ReferenceEnum(Rc<QLEnum>),
+    ReferenceEnum(Arc<QLEnum>),
     CustomType(CustomType),
-    ReferenceCustom(Weak<RefCell<CustomType>>),
+    ReferenceCustom(Weak<RwLock<CustomType>>),
+    ReferenceInterface(Weak<RwLock<QLInterface>>),
+    ReferenceUnion(Arc<QLUnion>),
+    ReferenceScalar(Arc<QLScalar>),
 }
 
 impl FieldType {
-    pub(crate) fn execute<'a, 'b>(
+    /// Execute the resolved data by the type, the fields and list items are executed one by one
+    /// in request order, sharing the context
+    pub(crate) fn execute(
         &self,
+        context: &mut QLContext,
+        env: &ExecuteEnv,
+        parameter: &QLApiParam,
+        data: DataValue,
+        path: &[PathSegment],
+        position: Pos,
+    ) -> ExecuteResult<DataValue> {
+        if let Some(result) = self.complete_null(&data) {
+            return result;
+        }
+        match self {
+            FieldType::StaticType(t) => Ok(t.execute(data)?),
+            FieldType::NonNullType(t) => t.execute(context, env, parameter, data, path, position),
+            FieldType::List(list_type) => {
+                let items = list_data(data)?
+                    .into_iter()
+                    .enumerate()
+                    .map(|(index, dat)| {
+                        let item_path = join_path(path, PathSegment::Index(index));
+                        let item =
+                            list_type.execute(context, env, parameter, dat, &item_path, position);
+                        env.complete_value(item, list_type.is_nullable(), item_path, position)
+                    })
+                    .collect::<Vec<_>>();
+                Ok(DataValue::List(
+                    items
+                        .into_iter()
+                        .collect::<ExecuteResult<Vec<DataValue>>>()?,
+                ))
+            }
+            FieldType::Enum(enum_type) => Ok(enum_type.execute(data)?),
+            FieldType::ReferenceEnum(enum_type) => Ok(enum_type.execute(data)?),
+            FieldType::ReferenceScalar(scalar) => Ok(scalar.execute(data)?),
+            _ => self
+                .select_object(context, env, parameter, data)?
+                .execute(context, env, path),
+        }
+    }
+
+    /// Execute the resolved data by the type and await the async resolvers, boxed for the recursion
+    /// through object fields
+    pub(crate) fn execute_async<'a>(
+        &'a self,
         context: &'a mut QLContext,
-        fragments: &'b HashMap<String, FragmentDefinition>,
-        parameter: &'b QLApiParam,
+        env: &'a ExecuteEnv,
+        parameter: &'a QLApiParam,
         data: DataValue,
-    ) -> Result<DataValue> {
+        path: &'a [PathSegment],
+        position: Pos,
+    ) -> BoxFuture<'a, ExecuteResult<DataValue>> {
+        self.execute_data_async(context, env, parameter, data, path, position)
+            .boxed()
+    }
+
+    async fn execute_data_async(
+        &self,
+        context: &mut QLContext,
+        env: &ExecuteEnv<'_>,
+        parameter: &QLApiParam,
+        data: DataValue,
+        path: &[PathSegment],
+        position: Pos,
+    ) -> ExecuteResult<DataValue> {
+        if let Some(result) = self.complete_null(&data) {
+            return result;
+        }
+        match self {
+            FieldType::StaticType(t) => Ok(t.execute(data)?),
+            FieldType::NonNullType(t) => {
+                t.execute_async(context, env, parameter, data, path, position)
+                    .await
+            }
+            FieldType::List(list_type) => {
+                // items are executed concurrently, each with its own copy of the context
+                let items = list_data(data)?
+                    .into_iter()
+                    .enumerate()
+                    .map(|(index, dat)| {
+                        let mut context = context.clone();
+                        async move {
+                            let item_path = join_path(path, PathSegment::Index(index));
+                            let item = list_type
+                                .execute_async(
+                                    &mut context,
+                                    env,
+                                    parameter,
+                                    dat,
+                                    &item_path,
+                                    position,
+                                )
+                                .await;
+                            // a failed item becomes null, or the whole list if the item is non-null
+                            env.complete_value(item, list_type.is_nullable(), item_path, position)
+                        }
+                    });
+                let result = join_all(items)
+                    .await
+                    .into_iter()
+                    .collect::<ExecuteResult<Vec<DataValue>>>()?;
+                Ok(DataValue::List(result))
+            }
+            FieldType::Enum(enum_type) => Ok(enum_type.execute(data)?),
+            FieldType::ReferenceEnum(enum_type) => Ok(enum_type.execute(data)?),
+            FieldType::ReferenceScalar(scalar) => Ok(scalar.execute(data)?),
+            _ => {
+                let selection = self.select_object(context, env, parameter, data)?;
+                selection.execute_async(context, env, path).await
+            }
+        }
+    }
+
+    /// `null` is completed as is, or raises an error if the type is non null
+    fn complete_null(&self, data: &DataValue) -> Option<ExecuteResult<DataValue>> {
+        match (self, data) {
+            (FieldType::NonNullType(_), DataValue::Null) => {
+                Some(Err(Error::NullForNonNullType(self.to_string()).into()))
+            }
+            (_, DataValue::Null) => Some(Ok(DataValue::Null)),
+            _ => None,
+        }
+    }
+
+    /// Select the fields of an object, interface or union typed value by its object type,
+    /// no lock of a referenced type is held by the selection
+    fn select_object<'s>(
+        &self,
+        context: &QLContext,
+        env: &'s ExecuteEnv<'_>,
+        parameter: &'s QLApiParam,
+        data: DataValue,
+    ) -> Result<ObjectSelection<'s>> {
         match self {
-            FieldType::StaticType(t) => t.execute(data),
-            FieldType::NonNullType(t) => match data {
-                DataValue::Null => Err(Error::DataTypeMisMatchError(
-                    "NonNull<...>".to_string(),
-                    "Null".to_string(),
-                )),
-                _ => t.execute(context, fragments, parameter, data),
-            },
-            FieldType::List(list_type) => match data {
-                DataValue::List(data_list) => {
-                    let mut result = vec![];
-                    for dat in data_list {
-                        let item = list_type.execute(context, fragments, parameter, dat)?;
-                        result.push(item)
-                    }
-                    Ok(DataValue::List(result))
-                }
-                _ => Err(Error::DataTypeMisMatchError(
-                    "List<...>".to_string(),
-                    "NonNull".to_string(),
-                )),
-            },
-            FieldType::Enum(_enum_type) => match data {
-                DataValue::String(_) => Ok(data),
-                _ => Err(Error::DataTypeMisMatchError(
-                    "Enum with String value".to_string(),
-                    "NonString".to_string(),
-                )),
-            },
-            FieldType::ReferenceEnum(_enum_type) => match data {
-                DataValue::String(_) => Ok(data),
-                _ => Err(Error::DataTypeMisMatchError(
-                    "Enum with String value".to_string(),
-                    "NonString".to_string(),
-                )),
-            },
             FieldType::CustomType(custom_type) => {
-                custom_type.execute(context, fragments, parameter, data)
+                custom_type.select(env, &parameter.selection_sets, data)
+            }
+            FieldType::ReferenceCustom(custom_type_rc) => {
+                let custom_type_rc = custom_type_rc
+                    .upgrade()
+                    .ok_or(Error::MissingReferenceCustomTypeError)?;
+                let selection =
+                    read_lock(&custom_type_rc).select(env, &parameter.selection_sets, data);
+                selection
+            }
+            FieldType::ReferenceInterface(interface_rc) => {
+                let interface_rc = interface_rc
+                    .upgrade()
+                    .ok_or(Error::MissingReferenceCustomTypeError)?;
+                let interface = read_lock(&interface_rc);
+                select_concrete_type(
+                    context,
+                    env,
+                    parameter,
+                    data,
+                    &interface.name,
+                    interface.resolve_type.as_ref(),
+                )
             }
-            FieldType::ReferenceCustom(custom_type_rc) => custom_type_rc
-                .upgrade()
-                .ok_or(Error::MissingReferenceCustomTypeError)?
-                .borrow()
-                .execute(context, fragments, parameter, data),
+            FieldType::ReferenceUnion(union_type) => select_concrete_type(
+                context,
+                env,
+                parameter,
+                data,
+                &union_type.name,
+                union_type.resolve_type.as_ref(),
+            ),
+            _ => Err(Error::DataTypeMisMatchError(
+                self.to_string(),
+                "Object(CustomType)".to_string(),
+            )),
+        }
+    }
+
+    /// whether the type accepts `null`, that is not a `NonNullType`
+    pub fn is_nullable(&self) -> bool {
+        !matches!(self, FieldType::NonNullType(_))
+    }
+}
+
+impl Display for FieldType {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            FieldType::StaticType(t) => write!(f, "{}", t.name()),
+            FieldType::NonNullType(t) => write!(f, "{}!", t),
+            FieldType::List(t) => write!(f, "[{}]", t),
+            FieldType::Enum(e) => write!(f, "{}", e.name),
+            FieldType::ReferenceEnum(e) => write!(f, "{}", e.name),
+            FieldType::CustomType(c) => write!(f, "{}", c.name),
+            FieldType::ReferenceCustom(c) => match c.upgrade() {
+                Some(rc) => write!(f, "{}", read_lock(&rc).name),
+                None => write!(f, "<missing>"),
+            },
+            FieldType::ReferenceInterface(i) => match i.upgrade() {
+                Some(rc) => write!(f, "{}", read_lock(&rc).name),
+                None => write!(f, "<missing>"),
+            },
+            FieldType::ReferenceUnion(u) => write!(f, "{}", u.name),
+            FieldType::ReferenceScalar(s) => write!(f, "{}", s.name),
         }
     }
 }
 
+/// list data of a list typed field
+fn list_data(data: DataValue) -> Result<Vec<DataValue>> {
+    match data {
+        DataValue::List(data_list) => Ok(data_list),
+        _ => Err(Error::DataTypeMisMatchError(
+            "List<...>".to_string(),
+            data.get_type_name(),
+        )),
+    }
+}
+
+/// select the fields of an interface or union typed value with its concrete object type
+fn select_concrete_type<'s>(
+    context: &QLContext,
+    env: &'s ExecuteEnv<'_>,
+    parameter: &'s QLApiParam,
+    data: DataValue,
+    type_name: &str,
+    resolve_type: &dyn TypeResolveFunc,
+) -> Result<ObjectSelection<'s>> {
+    let concrete_name = resolve_type.call(context, &data)?;
+    if !env.schema.is_possible_type(type_name, &concrete_name) {
+        return Err(Error::NotPossibleType(type_name.to_string(), concrete_name));
+    }
+    let concrete = env
+        .schema
+        .objects
+        .get(&concrete_name)
+        .ok_or_else(|| Error::NotFoundError(format!("Object type {}", concrete_name)))?;
+    let selection = read_lock(concrete).select(env, &parameter.selection_sets, data);
+    selection
+}
+
 /// StaticType
 #[derive(Clone, Debug)]
 pub enum StaticType {
@@ -99,99 +294,215 @@ pub enum StaticType {
 }
 
 impl StaticType {
+    /// coerce a resolved value for this type, compatible values are converted
     pub(crate) fn execute(&self, data: DataValue) -> Result<DataValue> {
         match (self, data) {
             (StaticType::ID, r @ DataValue::String(_)) => Ok(r),
+            (StaticType::ID, r @ DataValue::ID(_)) => Ok(r),
+            (StaticType::ID, DataValue::Int(i)) => Ok(DataValue::String(i.to_string())),
             (StaticType::String, r @ DataValue::String(_)) => Ok(r),
+            (StaticType::String, DataValue::ID(id)) => Ok(DataValue::String(id)),
             (StaticType::Int, r @ DataValue::Int(_)) => Ok(r),
             (StaticType::Float, r @ DataValue::Float(_)) => Ok(r),
+            (StaticType::Float, DataValue::Int(i)) => Ok(DataValue::Float(i as f64)),
             (StaticType::Boolean, r @ DataValue::Boolean(_)) => Ok(r),
             (StaticType::DateTime, r @ DataValue::DateTime(_)) => Ok(r),
+            (StaticType::DateTime, DataValue::String(s)) => DateTime::parse_from_rfc3339(&s)
+                .map(|dt| DataValue::DateTime(dt.with_timezone(&Utc)))
+                .map_err(|_| Error::DataTypeMisMatchError("DateTime".to_string(), s)),
             (_, data) => Err(Error::DataTypeMisMatchError(
                 format!("{:?}", self),
                 format!("{:?}", data),
             )),
         }
     }
+
+    /// coerce a request input value (literal or variable) for this type
+    pub(crate) fn coerce_input(&self, data: DataValue) -> Result<DataValue> {
+        match (self, data) {
+            (StaticType::ID, r @ DataValue::String(_)) => Ok(r),
+            (StaticType::ID, DataValue::ID(id)) => Ok(DataValue::String(id)),
+            (StaticType::ID, DataValue::Int(i)) => Ok(DataValue::String(i.to_string())),
+            (StaticType::String, r @ DataValue::String(_)) => Ok(r),
+            (StaticType::Int, r @ DataValue::Int(_)) => Ok(r),
+            (StaticType::Float, r @ DataValue::Float(_)) => Ok(r),
+            (StaticType::Float, DataValue::Int(i)) => Ok(DataValue::Float(i as f64)),
+            (StaticType::Boolean, r @ DataValue::Boolean(_)) => Ok(r),
+            (StaticType::DateTime, r @ DataValue::DateTime(_)) => Ok(r),
+            (StaticType::DateTime, DataValue::String(s)) => DateTime::parse_from_rfc3339(&s)
+                .map(|dt| DataValue::DateTime(dt.with_timezone(&Utc)))
+                .map_err(|_| Error::DataTypeMisMatchError("DateTime".to_string(), s)),
+            (_, data) => Err(Error::DataTypeMisMatchError(
+                self.name().to_string(),
+                data.get_type_name(),
+            )),
+        }
+    }
+
+    /// graphql type name
+    pub fn name(&self) -> &'static str {
+        match self {
+            StaticType::ID => "ID",
+            StaticType::String => "String",
+            StaticType::Int => "Int",
+            StaticType::Float => "Float",
+            StaticType::Boolean => "Boolean",
+            StaticType::DateTime => "DateTime",
+        }
+    }
+
+    /// find the static type by its graphql type name
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "ID" => Some(StaticType::ID),
+            "String" => Some(StaticType::String),
+            "Int" => Some(StaticType::Int),
+            "Float" => Some(StaticType::Float),
+            "Boolean" => Some(StaticType::Boolean),
+            "DateTime" => Some(StaticType::DateTime),
+            _ => None,
+        }
+    }
 }
 
 /// CustomType
 #[derive(Clone, Debug)]
 pub struct CustomType {
     pub name: String,
-    pub fields: BTreeMap<String, Field>,
+    /// shared with the selections executing them, so no lock of the type is held while executing
+    pub fields: BTreeMap<String, Arc<Field>>,
     pub description: String,
+    /// names of the interfaces this type implements
+    pub interfaces: Vec<String>,
 }
 
 impl CustomType {
-    pub(crate) fn execute<'a, 'b>(
+    /// Select the fields of the object data by response key, in request order, the fields of the type
+    /// are left to execute. Only the selected fields are kept, and a field selected with several aliases
+    /// is executed once for each of them
+    pub(crate) fn select<'s>(
         &self,
-        context: &'a mut QLContext,
-        fragments: &'b HashMap<String, FragmentDefinition>,
-        parameter: &'b QLApiParam,
+        env: &'s ExecuteEnv<'_>,
+        selection_sets: &'s [Selection],
         data: DataValue,
-    ) -> Result<DataValue> {
-        match data {
-            DataValue::Object(mut map) => {
-                self.execute_object(context, fragments, &parameter.selection_sets, &mut map)?;
-                Ok(DataValue::Object(map))
+    ) -> Result<ObjectSelection<'s>> {
+        let source = match data {
+            DataValue::Object(map) => map,
+            _ => {
+                return Err(Error::DataTypeMisMatchError(
+                    "Object(CustomType)".to_string(),
+                    data.get_type_name(),
+                ))
+            }
+        };
+        let mut data_map = IndexMap::new();
+        let mut executing: Vec<(Cow<ast::Field>, Arc<Field>)> = vec![];
+        for field in env.group_fields(selection_sets, &self.name)? {
+            let name = field.name.clone();
+            let key = response_key(&field).to_string();
+            if name == "__typename" {
+                data_map.insert(key, DataValue::String(self.name.clone()));
+                continue;
+            }
+            match self.fields.get(&name) {
+                // the key is kept in place until the field is executed
+                Some(field_define) => {
+                    data_map.insert(key, DataValue::Null);
+                    executing.push((field, field_define.clone()));
+                }
+                // not a field of the type, rejected by validation before execution
+                None => continue,
             }
-            DataValue::Null => Ok(DataValue::Null),
-            _ => Err(Error::DataTypeMisMatchError(
-                "Object(CustomType)".to_string(),
-                data.get_type_name(),
-            )),
         }
+        Ok(ObjectSelection {
+            type_name: self.name.clone(),
+            source: DataValue::Object(source),
+            data_map,
+            executing,
+        })
     }
+}
 
-    pub(crate) fn execute_object<'a, 'b>(
-        &self,
-        context: &'a mut QLContext,
-        fragments: &'b HashMap<String, FragmentDefinition>,
-        selection_sets: &'b Vec<Selection>,
-        data_map: &mut BTreeMap<String, DataValue>,
-    ) -> Result<()> {
-        for set in selection_sets {
-            match set {
-                Selection::Field(field) => {
-                    let name = field.name.clone();
-                    // self data does't have that key, but self fields has
-                    if !data_map.contains_key(&name) && self.fields.contains_key(&name) {
-                        let source = DataValue::Object(data_map.clone());
-                        let field_result = self
-                            .fields
-                            .get(&name)
-                            .unwrap()
-                            .execute(context, fragments, &source, field)?;
-                        data_map.insert(name, field_result);
-                    } else if !data_map.contains_key(&name) && !self.fields.contains_key(&name) {
-                        data_map.insert(name, DataValue::Null);
-                    } else if data_map.contains_key(&name) && !self.fields.contains_key(&name) {
-                        data_map.remove(&name);
-                    } else {
-                        continue;
-                    }
-                }
-                Selection::FragmentSpread(fs) => {
-                    let fragment = fragments
-                        .get(&fs.fragment_name)
-                        .ok_or(Error::NoSuchFragment(fs.fragment_name.clone()))?;
-                    self.execute_object(
-                        context,
-                        fragments,
-                        &fragment.selection_set.items,
-                        data_map,
-                    )?;
-                }
-                Selection::InlineFragment(_) => {
-                    return Err(Error::UnSupportedYetError(
-                        "'InlineFragment' in custom field".to_string(),
-                    ))
-                }
+/// ObjectSelection
+///
+/// The selected fields of an object value, with the field definitions to execute them
+pub(crate) struct ObjectSelection<'s> {
+    type_name: String,
+    /// the object data before any field is resolved
+    source: DataValue,
+    data_map: IndexMap<String, DataValue>,
+    executing: Vec<(Cow<'s, ast::Field>, Arc<Field>)>,
+}
+
+impl ObjectSelection<'_> {
+    /// Execute the fields one by one in request order, sharing the context.
+    /// Their source is the object data before any of them is resolved
+    pub(crate) fn execute(
+        self,
+        context: &mut QLContext,
+        env: &ExecuteEnv<'_>,
+        path: &[PathSegment],
+    ) -> ExecuteResult<DataValue> {
+        let mut data_map = self.data_map;
+        let source = &self.source;
+        let type_name = self.type_name.as_str();
+        let results = self
+            .executing
+            .iter()
+            .map(|(field, field_define)| {
+                let key = response_key(field).to_string();
+                let field_path = join_path(path, PathSegment::Field(key.clone()));
+                let field_result =
+                    field_define.execute(context, env, type_name, source, field, &field_path);
+                env.complete_value(
+                    field_result,
+                    field_define.field_type.is_nullable(),
+                    field_path,
+                    field.position,
+                )
+                .map(|value| (key, value))
+            })
+            .collect::<Vec<_>>();
+        for result in results {
+            let (key, value) = result?;
+            data_map.insert(key, value);
+        }
+        Ok(DataValue::Object(data_map))
+    }
+
+    /// Execute the fields concurrently, each with its own copy of the context.
+    /// Their source is the object data before any of them is resolved
+    pub(crate) async fn execute_async(
+        self,
+        context: &mut QLContext,
+        env: &ExecuteEnv<'_>,
+        path: &[PathSegment],
+    ) -> ExecuteResult<DataValue> {
+        let mut data_map = self.data_map;
+        let source = &self.source;
+        let type_name = self.type_name.as_str();
+        let results = self.executing.iter().map(|(field, field_define)| {
+            let mut context = context.clone();
+            async move {
+                let key = response_key(field).to_string();
+                let field_path = join_path(path, PathSegment::Field(key.clone()));
+                let field_result = field_define
+                    .execute_async(&mut context, env, type_name, source, field, &field_path)
+                    .await;
+                env.complete_value(
+                    field_result,
+                    field_define.field_type.is_nullable(),
+                    field_path,
+                    field.position,
+                )
+                .map(|value| (key, value))
             }
+        });
+        for result in join_all(results).await {
+            let (key, value) = result?;
+            data_map.insert(key, value);
         }
-        //Ok(DataValue::Object(data_map))
-        Ok(())
+        Ok(DataValue::Object(data_map))
     }
 }
 
@@ -200,28 +511,96 @@ impl CustomType {
 pub struct Field {
     pub name: String,
     pub field_type: FieldType,
+    pub arguments: ArgumentMap,
     pub description: String,
-    pub resolve: Box<dyn FieldResolveFunc>,
+    pub resolve: FieldResolver,
 }
 
 impl Field {
-    pub(crate) fn execute<'a, 'b>(
+    pub(crate) fn execute(
         &self,
-        context: &'a mut QLContext,
-        fragments: &'b HashMap<String, FragmentDefinition>,
-        source: &'b DataValue,
-        field: &'b ast::Field,
-    ) -> Result<DataValue> {
-        let parameter = QLApiParam {
-            arguments: ArgumentValueMap::from(field.arguments.to_owned()),
-            selection_sets: field.selection_set.items.clone(),
+        context: &mut QLContext,
+        env: &ExecuteEnv<'_>,
+        type_name: &str,
+        source: &DataValue,
+        field: &ast::Field,
+        path: &[PathSegment],
+    ) -> ExecuteResult<DataValue> {
+        let parameter = self.parameter(env, type_name, field)?;
+        let resolve_result = match self.source_value(source, field) {
+            Some(value) => value,
+            None => self
+                .resolve
+                .call(context, source, &parameter)?
+                .to_data_value(),
+        };
+        self.field_type.execute(
+            context,
+            env,
+            &parameter,
+            resolve_result,
+            path,
+            field.position,
+        )
+    }
+
+    pub(crate) async fn execute_async(
+        &self,
+        context: &mut QLContext,
+        env: &ExecuteEnv<'_>,
+        type_name: &str,
+        source: &DataValue,
+        field: &ast::Field,
+        path: &[PathSegment],
+    ) -> ExecuteResult<DataValue> {
+        let parameter = self.parameter(env, type_name, field)?;
+        let resolve_result = match self.source_value(source, field) {
+            Some(value) => value,
+            None => self
+                .resolve
+                .call_async(context, source, &parameter)
+                .await?
+                .to_data_value(),
         };
-        let resolve_result = self
-            .resolve
-            .call(context, source, &parameter)?
-            .to_data_value();
         self.field_type
-            .execute(context, fragments, &parameter, resolve_result)
+            .execute_async(
+                context,
+                env,
+                &parameter,
+                resolve_result,
+                path,
+                field.position,
+            )
+            .await
+    }
+
+    /// the coerced arguments and the selections of the request field
+    fn parameter(
+        &self,
+        env: &ExecuteEnv<'_>,
+        type_name: &str,
+        field: &ast::Field,
+    ) -> Result<QLApiParam> {
+        Ok(QLApiParam {
+            arguments: ArgumentValueMap::coerce(
+                field.arguments.to_owned(),
+                &env.variables,
+                &self.arguments,
+                &format!("{}.{}", type_name, field.name),
+            )?,
+            selection_sets: field.selection_set.items.clone(),
+        })
+    }
+
+    /// A field without resolve function takes the value in the source data as is,
+    /// a missing key is null and the field type decides if that is an error
+    fn source_value(&self, source: &DataValue, field: &ast::Field) -> Option<DataValue> {
+        match source {
+            DataValue::Object(map) if self.resolve.is_default() => {
+                Some(map.get(&field.name).cloned().unwrap_or(DataValue::Null))
+            }
+            _ => None,
+        }
     }
 
     pub fn new(
@@ -233,8 +612,24 @@ impl Field {
         Self {
             name: name.to_string(),
             field_type,
+            arguments: ArgumentMap::new(),
             description: description.to_string(),
-            resolve,
+            resolve: FieldResolver::Sync(resolve),
+        }
+    }
+
+    pub fn new_async(
+        name: &str,
+        field_type: FieldType,
+        description: &str,
+        resolve: Box<dyn AsyncFieldResolveFunc>,
+    ) -> Self {
+        Self {
+            name: name.to_string(),
+            field_type,
+            arguments: ArgumentMap::new(),
+            description: description.to_string(),
+            resolve: FieldResolver::Async(resolve),
         }
     }
 
@@ -254,13 +649,43 @@ impl Field {
         Self::new(name, field_type, "", resolve)
     }
 
+    pub fn simple_with_async_resolve(
+        name: &str,
+        field_type: FieldType,
+        resolve: Box<dyn AsyncFieldResolveFunc>,
+    ) -> Self {
+        Self::new_async(name, field_type, "", resolve)
+    }
+
+    /// Declare an argument of this field, passed coerced to the resolve function
+    pub fn add_argument(mut self, name: &str, argument: InputFieldType) -> Self {
+        self.arguments
+            .insert(name.to_string(), InputField::simple(name, argument));
+        self
+    }
+
+    /// Declare an argument of this field, `default` is passed when it is not provided
+    pub fn add_argument_with_default(
+        mut self,
+        name: &str,
+        argument: InputFieldType,
+        default: DataValue,
+    ) -> Self {
+        self.arguments.insert(
+            name.to_string(),
+            InputField::simple_with_default(name, argument, default),
+        );
+        self
+    }
+
     /// create a basic id field without resolve
     pub fn basic_id() -> Self {
         Self {
             name: String::default(),
             field_type: FieldType::StaticType(StaticType::ID),
+            arguments: ArgumentMap::new(),
             description: String::default(),
-            resolve: Box::new(DefaultFieldResolveFunc),
+            resolve: FieldResolver::Sync(Box::new(DefaultFieldResolveFunc)),
         }
     }
 
@@ -269,8 +694,9 @@ impl Field {
         Self {
             name: String::default(),
             field_type: FieldType::StaticType(StaticType::Int),
+            arguments: ArgumentMap::new(),
             description: String::default(),
-            resolve: Box::new(DefaultFieldResolveFunc),
+            resolve: FieldResolver::Sync(Box::new(DefaultFieldResolveFunc)),
         }
     }
 
@@ -279,8 +705,9 @@ impl Field {
         Self {
             name: String::default(),
             field_type: FieldType::StaticType(StaticType::Float),
+            arguments: ArgumentMap::new(),
             description: String::default(),
-            resolve: Box::new(DefaultFieldResolveFunc),
+            resolve: FieldResolver::Sync(Box::new(DefaultFieldResolveFunc)),
         }
     }
 
@@ -289,8 +716,9 @@ impl Field {
         Self {
             name: String::default(),
             field_type: FieldType::StaticType(StaticType::String),
+            arguments: ArgumentMap::new(),
             description: String::default(),
-            resolve: Box::new(DefaultFieldResolveFunc),
+            resolve: FieldResolver::Sync(Box::new(DefaultFieldResolveFunc)),
         }
     }
 
@@ -299,8 +727,9 @@ impl Field {
         Self {
             name: String::default(),
             field_type: FieldType::StaticType(StaticType::Boolean),
+            arguments: ArgumentMap::new(),
             description: String::default(),
-            resolve: Box::new(DefaultFieldResolveFunc),
+            resolve: FieldResolver::Sync(Box::new(DefaultFieldResolveFunc)),
         }
     }
 
@@ -309,8 +738,9 @@ impl Field {
         Self {
             name: String::default(),
             field_type: FieldType::StaticType(StaticType::DateTime),
+            arguments: ArgumentMap::new(),
             description: String::default(),
-            resolve: Box::new(DefaultFieldResolveFunc),
+            resolve: FieldResolver::Sync(Box::new(DefaultFieldResolveFunc)),
         }
     }
 }
@@ -319,8 +749,92 @@ impl Debug for Field {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         write!(
             f,
-            "Field{{name: {}, field_type: {:?}, description: {}, resolve: <FieldResolveFunc>}}",
-            self.name, self.field_type, self.description
+            "Field{{name: {}, field_type: {:?}, arguments: {:?}, description: {}, resolve: <FieldResolveFunc>}}",
+            self.name, self.field_type, self.arguments, self.description
+        )
+    }
+}
+
+/// QLInterface
+///
+/// Graphql interface definition, storaged in `Schema`'s `interfaces` and referenced by `FieldType::ReferenceInterface`.
+/// The object types implementing it are the `CustomType`s listing its name in `interfaces`,
+/// and `resolve_type` picks one of them for each resolved value.
+#[derive(Clone)]
+pub struct QLInterface {
+    pub name: String,
+    pub fields: BTreeMap<String, Field>,
+    pub description: String,
+    pub resolve_type: Box<dyn TypeResolveFunc>,
+}
+
+impl Debug for QLInterface {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "QLInterface{{name: {}, fields: {:?}, description: {}, resolve_type: <TypeResolveFunc>}}",
+            self.name, self.fields, self.description
+        )
+    }
+}
+
+/// QLUnion
+///
+/// Graphql union definition, storaged in `Schema`'s `unions` and referenced by `FieldType::ReferenceUnion`.
+/// `resolve_type` picks one of the member object types for each resolved value.
+#[derive(Clone)]
+pub struct QLUnion {
+    pub name: String,
+    /// names of the member object types
+    pub types: Vec<String>,
+    pub description: String,
+    pub resolve_type: Box<dyn TypeResolveFunc>,
+}
+
+impl Debug for QLUnion {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "QLUnion{{name: {}, types: {:?}, description: {}, resolve_type: <TypeResolveFunc>}}",
+            self.name, self.types, self.description
+        )
+    }
+}
+
+/// QLScalar
+///
+/// Graphql custom scalar definition, storaged in `Schema`'s `scalars` and referenced by `FieldType::ReferenceScalar`
+/// and `InputFieldType::ReferenceScalar`.
+/// * `serialize` converts the resolved values for the response
+/// * `parse` checks and converts the request input values (literals and variables)
+#[derive(Clone)]
+pub struct QLScalar {
+    pub name: String,
+    pub description: String,
+    /// url of the scalar specification, `specifiedByURL` in introspection
+    pub specified_by_url: Option<String>,
+    pub serialize: Box<dyn ScalarConvertFunc>,
+    pub parse: Box<dyn ScalarConvertFunc>,
+}
+
+impl QLScalar {
+    /// serialize a resolved value
+    pub(crate) fn execute(&self, data: DataValue) -> Result<DataValue> {
+        self.serialize.call(data)
+    }
+
+    /// parse a request input value
+    pub(crate) fn coerce_input(&self, data: DataValue) -> Result<DataValue> {
+        self.parse.call(data)
+    }
+}
+
+impl Debug for QLScalar {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "QLScalar{{name: {}, description: {}, specified_by_url: {:?}, serialize: <ScalarConvertFunc>, parse: <ScalarConvertFunc>}}",
+            self.name, self.description, self.specified_by_url
         )
     }
 }
@@ -337,6 +851,39 @@ pub struct QLEnum {
     pub values: Vec<QLEnumValue>,
 }
 
+impl QLEnum {
+    /// check a resolved value, it must be one of the enum values
+    pub(crate) fn execute(&self, data: DataValue) -> Result<DataValue> {
+        match data {
+            DataValue::String(value) if !self.values.iter().any(|v| v.value == value) => {
+                Err(Error::NotEnumMember(self.name.clone(), value))
+            }
+            DataValue::String(_) => Ok(data),
+            _ => Err(Error::DataTypeMisMatchError(
+                "Enum with String value".to_string(),
+                "NonString".to_string(),
+            )),
+        }
+    }
+
+    /// coerce a request input value, it must be one of the enum values
+    pub(crate) fn coerce_input(&self, data: DataValue) -> Result<DataValue> {
+        match data {
+            DataValue::String(s) => {
+                if self.values.iter().any(|v| v.value == s) {
+                    Ok(DataValue::String(s))
+                } else {
+                    Err(Error::NotEnumMember(self.name.clone(), s))
+                }
+            }
+            _ => Err(Error::DataTypeMisMatchError(
+                self.name.clone(),
+                data.get_type_name(),
+            )),
+        }
+    }
+}
+
 /// QLEnumValue
 ///
 /// Graphql enumuation value definition
@@ -350,7 +897,7 @@ pub struct QLEnumValue {
 ///
 /// Graphql enumuation definition. This can be storaged in `QueryMap`'s `inputs`.
 /// * If your input is used for once (for definition), you can set it (`QLInput`) in your argument directly.
-/// * If your input is uesd for more than once, you can store the input in `QueryMap`, and use its reference (`Rc<RefCell<QLInput>>`)
+/// * If your input is uesd for more than once, you can store the input in `QueryMap`, and use its reference (`Arc<RwLock<QLInput>>`)
 #[derive(Clone, Debug)]
 pub struct QLInput {
     pub name: String,
@@ -358,12 +905,56 @@ pub struct QLInput {
     pub description: String,
 }
 
+impl QLInput {
+    /// coerce a request input value, it must be an object matching the declared fields
+    pub(crate) fn coerce_input(&self, data: DataValue) -> Result<DataValue> {
+        let mut map = match data {
+            DataValue::Object(map) => map,
+            _ => {
+                return Err(Error::DataTypeMisMatchError(
+                    self.name.clone(),
+                    data.get_type_name(),
+                ))
+            }
+        };
+        if let Some(unknown) = map.keys().find(|k| !self.fields.contains_key(*k)) {
+            return Err(Error::UnknownInputField(self.name.clone(), unknown.clone()));
+        }
+        let mut result = IndexMap::new();
+        for (name, field) in self.fields.iter() {
+            let value = map.shift_remove(name);
+            if value.is_none() && field.is_required() {
+                return Err(Error::MissingInputField(
+                    self.name.clone(),
+                    name.clone(),
+                    field.field_type.to_string(),
+                ));
+            }
+            if let Some(value) = field.coerce(value)? {
+                result.insert(name.clone(), value);
+            }
+        }
+        Ok(DataValue::Object(result))
+    }
+
+    fn check_literal(&self, fields: &BTreeMap<String, ast::Value>) -> Result<()> {
+        fields
+            .iter()
+            .try_for_each(|(name, value)| match self.fields.get(name) {
+                Some(field) => field.field_type.check_literal(value),
+                None => Ok(()),
+            })
+    }
+}
+
 /// InputField
 #[derive(Clone, Debug)]
 pub struct InputField {
     pub name: String,
     pub field_type: InputFieldType,
     pub description: String,
+    /// used when the value is not provided, coerced as a provided one
+    pub default_value: Option<DataValue>,
 }
 
 impl InputField {
@@ -372,6 +963,7 @@ impl InputField {
             name: name.to_string(),
             field_type,
             description: description.to_string(),
+            default_value: None,
         }
     }
 
@@ -383,12 +975,34 @@ impl InputField {
         Self::new(name, field_type, desc)
     }
 
+    pub fn simple_with_default(name: &str, field_type: InputFieldType, default: DataValue) -> Self {
+        Self::new(name, field_type, "").set_default(default)
+    }
+
+    pub fn set_default(mut self, default: DataValue) -> Self {
+        self.default_value = Some(default);
+        self
+    }
+
+    /// a value must be provided, the type is non null and there is no default value
+    pub(crate) fn is_required(&self) -> bool {
+        matches!(self.field_type, InputFieldType::NonNullType(_)) && self.default_value.is_none()
+    }
+
+    /// coerce the provided value, or the default value if it is not provided
+    pub(crate) fn coerce(&self, data: Option<DataValue>) -> Result<Option<DataValue>> {
+        data.or_else(|| self.default_value.clone())
+            .map(|value| self.field_type.coerce(value))
+            .transpose()
+    }
+
     /// create a basic id field for input object
     pub fn basic_id() -> Self {
         Self {
             name: String::default(),
             field_type: InputFieldType::StaticType(StaticType::ID),
             description: String::default(),
+            default_value: None,
         }
     }
 
@@ -398,6 +1012,7 @@ impl InputField {
             name: String::default(),
             field_type: InputFieldType::StaticType(StaticType::Int),
             description: String::default(),
+            default_value: None,
         }
     }
 
@@ -407,6 +1022,7 @@ impl InputField {
             name: String::default(),
             field_type: InputFieldType::StaticType(StaticType::Float),
             description: String::default(),
+            default_value: None,
         }
     }
 
@@ -416,6 +1032,7 @@ impl InputField {
             name: String::default(),
             field_type: InputFieldType::StaticType(StaticType::String),
             description: String::default(),
+            default_value: None,
         }
     }
 
@@ -425,6 +1042,7 @@ impl InputField {
             name: String::default(),
             field_type: InputFieldType::StaticType(StaticType::Boolean),
             description: String::default(),
+            default_value: None,
         }
     }
 
@@ -434,6 +1052,7 @@ impl InputField {
             name: String::default(),
             field_type: InputFieldType::StaticType(StaticType::DateTime),
             description: String::default(),
+            default_value: None,
         }
     }
 }
@@ -445,12 +1064,70 @@ pub enum InputFieldType {
     NonNullType(Box<InputFieldType>),
     List(Box<InputFieldType>),
     Enum(QLEnum),
-    ReferenceEnum(Rc<QLEnum>),
+    ReferenceEnum(Arc<QLEnum>),
     QLInput(QLInput),
-    ReferenceInput(Weak<RefCell<QLInput>>),
+    ReferenceInput(Weak<RwLock<QLInput>>),
+    ReferenceScalar(Arc<QLScalar>),
 }
 
 impl InputFieldType {
+    /// coerce a request input value (literal or variable) against this type
+    pub(crate) fn coerce(&self, data: DataValue) -> Result<DataValue> {
+        match (self, data) {
+            (InputFieldType::NonNullType(_), DataValue::Null) => Err(Error::DataTypeMisMatchError(
+                self.to_string(),
+                "Null".to_string(),
+            )),
+            (InputFieldType::NonNullType(t), data) => t.coerce(data),
+            (_, DataValue::Null) => Ok(DataValue::Null),
+            (InputFieldType::List(t), DataValue::List(list)) => list
+                .into_iter()
+                .map(|item| t.coerce(item))
+                .collect::<Result<Vec<DataValue>>>()
+                .map(DataValue::List),
+            // a single value is accepted as a list of one item
+            (InputFieldType::List(t), data) => Ok(DataValue::List(vec![t.coerce(data)?])),
+            (InputFieldType::StaticType(t), data) => t.coerce_input(data),
+            (InputFieldType::Enum(e), data) => e.coerce_input(data),
+            (InputFieldType::ReferenceEnum(e), data) => e.coerce_input(data),
+            (InputFieldType::ReferenceScalar(s), data) => s.coerce_input(data),
+            (InputFieldType::QLInput(i), data) => i.coerce_input(data),
+            (InputFieldType::ReferenceInput(i), data) => {
+                let input = i.upgrade().ok_or(Error::MissingReferenceCustomTypeError)?;
+                let result = read_lock(&input).coerce_input(data);
+                result
+            }
+        }
+    }
+
+    /// check a request literal is an enum value when this type is an enum, and only then.
+    /// The difference is lost once the literal is a `DataValue`, other mismatches are left to `coerce`
+    pub(crate) fn check_literal(&self, value: &ast::Value) -> Result<()> {
+        match (self, value) {
+            (_, ast::Value::Variable(_)) | (_, ast::Value::Null) => Ok(()),
+            (InputFieldType::NonNullType(t), value) => t.check_literal(value),
+            (InputFieldType::List(t), ast::Value::List(items)) => {
+                items.iter().try_for_each(|item| t.check_literal(item))
+            }
+            (InputFieldType::List(t), value) => t.check_literal(value),
+            (InputFieldType::Enum(_), ast::Value::Enum(_))
+            | (InputFieldType::ReferenceEnum(_), ast::Value::Enum(_)) => Ok(()),
+            (InputFieldType::Enum(_), value) | (InputFieldType::ReferenceEnum(_), value) => Err(
+                Error::DataTypeMisMatchError(self.to_string(), literal_type_name(value)),
+            ),
+            (InputFieldType::StaticType(_), ast::Value::Enum(_)) => Err(
+                Error::DataTypeMisMatchError(self.to_string(), literal_type_name(value)),
+            ),
+            (InputFieldType::QLInput(i), ast::Value::Object(fields)) => i.check_literal(fields),
+            (InputFieldType::ReferenceInput(i), ast::Value::Object(fields)) => {
+                let input = i.upgrade().ok_or(Error::MissingReferenceCustomTypeError)?;
+                let result = read_lock(&input).check_literal(fields);
+                result
+            }
+            _ => Ok(()),
+        }
+    }
+
     /// create a basic id field for input object
     pub fn basic_id() -> Self {
         Self::StaticType(StaticType::ID)
@@ -482,5 +1159,30 @@ impl InputFieldType {
     }
 }
 
+impl Display for InputFieldType {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            InputFieldType::StaticType(t) => write!(f, "{}", t.name()),
+            InputFieldType::NonNullType(t) => write!(f, "{}!", t),
+            InputFieldType::List(t) => write!(f, "[{}]", t),
+            InputFieldType::Enum(e) => write!(f, "{}", e.name),
+            InputFieldType::ReferenceEnum(e) => write!(f, "{}", e.name),
+            InputFieldType::QLInput(i) => write!(f, "{}", i.name),
+            InputFieldType::ReferenceInput(i) => match i.upgrade() {
+                Some(rc) => write!(f, "{}", read_lock(&rc).name),
+                None => write!(f, "<missing>"),
+            },
+            InputFieldType::ReferenceScalar(s) => write!(f, "{}", s.name),
+        }
+    }
+}
+
+fn literal_type_name(value: &ast::Value) -> String {
+    match value {
+        ast::Value::Enum(value) => format!("Enum({})", value),
+        value => DataValue::from(value.clone()).get_type_name(),
+    }
+}
+
 /// ArgumentMap
-pub type ArgumentMap = BTreeMap<String, InputFieldType>;
+pub type ArgumentMap = BTreeMap<String, InputField>;
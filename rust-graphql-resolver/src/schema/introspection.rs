@@ -0,0 +1,278 @@
+use gurkle_parser::query::{self as ast, Selection};
+use indexmap::IndexMap;
+
+use crate::{
+    error::{Error, Result},
+    value::DataValue,
+};
+
+use super::{
+    field::QLEnumValue,
+    meta::{DirectiveMeta, FieldMeta, InputValueMeta, SchemaMeta, TypeKind, TypeMeta, TypeRef},
+    resolve::ArgumentValueMap,
+    response_key, ExecuteEnv,
+};
+
+/// Introspector
+///
+/// Resolves the `__schema` and `__type` meta fields against the collected `SchemaMeta`
+pub(crate) struct Introspector<'a> {
+    meta: &'a SchemaMeta,
+    env: &'a ExecuteEnv<'a>,
+}
+
+fn string_or_null(s: &str) -> DataValue {
+    if s.is_empty() {
+        DataValue::Null
+    } else {
+        DataValue::String(s.to_string())
+    }
+}
+
+impl<'a> Introspector<'a> {
+    pub(crate) fn new(meta: &'a SchemaMeta, env: &'a ExecuteEnv<'a>) -> Self {
+        Self { meta, env }
+    }
+
+    /// resolve the `__schema` meta field
+    pub(crate) fn execute_schema(&self, field: &ast::Field) -> Result<DataValue> {
+        self.execute_object(
+            "__Schema",
+            &field.selection_set.items,
+            &mut |f| match f.name.as_str() {
+                "description" => Ok(DataValue::Null),
+                "types" => {
+                    let types = self
+                        .meta
+                        .types
+                        .values()
+                        .map(|t| self.execute_type_meta(t, &f.selection_set.items))
+                        .collect::<Result<Vec<_>>>()?;
+                    Ok(DataValue::List(types))
+                }
+                "queryType" => self.execute_named_type(&self.meta.query_type, f),
+                "mutationType" => match &self.meta.mutation_type {
+                    Some(name) => self.execute_named_type(name, f),
+                    None => Ok(DataValue::Null),
+                },
+                "subscriptionType" => match &self.meta.subscription_type {
+                    Some(name) => self.execute_named_type(name, f),
+                    None => Ok(DataValue::Null),
+                },
+                "directives" => self.execute_list(self.meta.directives.iter(), |directive| {
+                    self.execute_directive(directive, &f.selection_set.items)
+                }),
+                _ => Err(Error::NotFoundError(format!(
+                    "Field {} of __Schema",
+                    f.name
+                ))),
+            },
+        )
+    }
+
+    /// resolve the `__type(name: String!)` meta field, unknown type name results `null`
+    pub(crate) fn execute_type(&self, field: &ast::Field) -> Result<DataValue> {
+        let arguments = ArgumentValueMap::new(field.arguments.clone(), &self.env.variables);
+        match arguments.get(&"name".to_string()) {
+            Some(DataValue::String(name)) => self.execute_named_type(name, field),
+            Some(other) => Err(Error::DataTypeMisMatchError(
+                "String".to_string(),
+                other.get_type_name(),
+            )),
+            None => Err(Error::NotFoundError(
+                "Argument 'name' of __type".to_string(),
+            )),
+        }
+    }
+
+    fn execute_named_type(&self, name: &str, field: &ast::Field) -> Result<DataValue> {
+        match self.meta.get_type(name) {
+            Some(t) => self.execute_type_meta(t, &field.selection_set.items),
+            None => Ok(DataValue::Null),
+        }
+    }
+
+    fn execute_type_ref(&self, type_ref: &TypeRef, selections: &[Selection]) -> Result<DataValue> {
+        let (kind, of_type) = match type_ref {
+            TypeRef::Named(name) => {
+                return match self.meta.get_type(name) {
+                    Some(t) => self.execute_type_meta(t, selections),
+                    None => Ok(DataValue::Null),
+                }
+            }
+            TypeRef::List(t) => (TypeKind::List, t),
+            TypeRef::NonNull(t) => (TypeKind::NonNull, t),
+        };
+        self.execute_object("__Type", selections, &mut |f| match f.name.as_str() {
+            "kind" => Ok(DataValue::String(kind.as_str().to_string())),
+            "ofType" => self.execute_type_ref(of_type, &f.selection_set.items),
+            "name" | "description" | "specifiedByURL" | "fields" | "interfaces"
+            | "possibleTypes" | "enumValues" | "inputFields" => Ok(DataValue::Null),
+            _ => Err(Error::NotFoundError(format!("Field {} of __Type", f.name))),
+        })
+    }
+
+    fn execute_type_meta(
+        &self,
+        type_meta: &TypeMeta,
+        selections: &[Selection],
+    ) -> Result<DataValue> {
+        let kind = type_meta.kind;
+        self.execute_object("__Type", selections, &mut |f| {
+            let items = &f.selection_set.items;
+            match f.name.as_str() {
+                "kind" => Ok(DataValue::String(kind.as_str().to_string())),
+                "name" => Ok(DataValue::String(type_meta.name.clone())),
+                "description" => Ok(string_or_null(&type_meta.description)),
+                "specifiedByURL" => Ok(match &type_meta.specified_by_url {
+                    Some(url) => DataValue::String(url.clone()),
+                    None => DataValue::Null,
+                }),
+                "fields" => match kind {
+                    TypeKind::Object | TypeKind::Interface => self
+                        .execute_list(type_meta.fields.iter(), |field| {
+                            self.execute_field_meta(field, items)
+                        }),
+                    _ => Ok(DataValue::Null),
+                },
+                "interfaces" => match kind {
+                    TypeKind::Object | TypeKind::Interface => self
+                        .execute_list(type_meta.interfaces.iter(), |name| {
+                            self.execute_type_ref(&TypeRef::named(name), items)
+                        }),
+                    _ => Ok(DataValue::Null),
+                },
+                "possibleTypes" => match kind {
+                    TypeKind::Interface | TypeKind::Union => self
+                        .execute_list(type_meta.possible_types.iter(), |name| {
+                            self.execute_type_ref(&TypeRef::named(name), items)
+                        }),
+                    _ => Ok(DataValue::Null),
+                },
+                "enumValues" => match kind {
+                    TypeKind::Enum => self.execute_list(type_meta.enum_values.iter(), |value| {
+                        self.execute_enum_value(value, items)
+                    }),
+                    _ => Ok(DataValue::Null),
+                },
+                "inputFields" => match kind {
+                    TypeKind::InputObject => self
+                        .execute_list(type_meta.input_fields.iter(), |value| {
+                            self.execute_input_value(value, items)
+                        }),
+                    _ => Ok(DataValue::Null),
+                },
+                "ofType" => Ok(DataValue::Null),
+                _ => Err(Error::NotFoundError(format!("Field {} of __Type", f.name))),
+            }
+        })
+    }
+
+    fn execute_field_meta(
+        &self,
+        field_meta: &FieldMeta,
+        selections: &[Selection],
+    ) -> Result<DataValue> {
+        self.execute_object("__Field", selections, &mut |f| match f.name.as_str() {
+            "name" => Ok(DataValue::String(field_meta.name.clone())),
+            "description" => Ok(string_or_null(&field_meta.description)),
+            "args" => self.execute_list(field_meta.args.iter(), |arg| {
+                self.execute_input_value(arg, &f.selection_set.items)
+            }),
+            "type" => self.execute_type_ref(&field_meta.type_ref, &f.selection_set.items),
+            "isDeprecated" => Ok(DataValue::Boolean(false)),
+            "deprecationReason" => Ok(DataValue::Null),
+            _ => Err(Error::NotFoundError(format!("Field {} of __Field", f.name))),
+        })
+    }
+
+    fn execute_input_value(
+        &self,
+        input_value: &InputValueMeta,
+        selections: &[Selection],
+    ) -> Result<DataValue> {
+        self.execute_object("__InputValue", selections, &mut |f| match f.name.as_str() {
+            "name" => Ok(DataValue::String(input_value.name.clone())),
+            "description" => Ok(string_or_null(&input_value.description)),
+            "type" => self.execute_type_ref(&input_value.type_ref, &f.selection_set.items),
+            "defaultValue" => Ok(match &input_value.default_value {
+                Some(value) => DataValue::String(value.clone()),
+                None => DataValue::Null,
+            }),
+            _ => Err(Error::NotFoundError(format!(
+                "Field {} of __InputValue",
+                f.name
+            ))),
+        })
+    }
+
+    fn execute_directive(
+        &self,
+        directive: &DirectiveMeta,
+        selections: &[Selection],
+    ) -> Result<DataValue> {
+        self.execute_object("__Directive", selections, &mut |f| match f.name.as_str() {
+            "name" => Ok(DataValue::String(directive.name.clone())),
+            "description" => Ok(string_or_null(&directive.description)),
+            "locations" => Ok(DataValue::List(
+                directive
+                    .locations
+                    .iter()
+                    .map(|l| DataValue::String(l.clone()))
+                    .collect(),
+            )),
+            "args" => self.execute_list(directive.args.iter(), |arg| {
+                self.execute_input_value(arg, &f.selection_set.items)
+            }),
+            "isRepeatable" => Ok(DataValue::Boolean(false)),
+            _ => Err(Error::NotFoundError(format!(
+                "Field {} of __Directive",
+                f.name
+            ))),
+        })
+    }
+
+    fn execute_enum_value(
+        &self,
+        value: &QLEnumValue,
+        selections: &[Selection],
+    ) -> Result<DataValue> {
+        self.execute_object("__EnumValue", selections, &mut |f| match f.name.as_str() {
+            "name" => Ok(DataValue::String(value.value.clone())),
+            "description" => Ok(string_or_null(&value.description)),
+            "isDeprecated" => Ok(DataValue::Boolean(false)),
+            "deprecationReason" => Ok(DataValue::Null),
+            _ => Err(Error::NotFoundError(format!(
+                "Field {} of __EnumValue",
+                f.name
+            ))),
+        })
+    }
+
+    fn execute_list<T>(
+        &self,
+        items: impl Iterator<Item = T>,
+        f: impl Fn(T) -> Result<DataValue>,
+    ) -> Result<DataValue> {
+        Ok(DataValue::List(items.map(f).collect::<Result<Vec<_>>>()?))
+    }
+
+    /// walk the selections of a meta object, `resolve` gives the value of each selected field
+    fn execute_object(
+        &self,
+        type_name: &str,
+        selections: &[Selection],
+        resolve: &mut dyn FnMut(&ast::Field) -> Result<DataValue>,
+    ) -> Result<DataValue> {
+        let mut result = IndexMap::new();
+        for field in self.env.group_fields(selections, type_name)? {
+            let value = if field.name == "__typename" {
+                DataValue::String(type_name.to_string())
+            } else {
+                resolve(&field)?
+            };
+            result.insert(response_key(&field).to_string(), value);
+        }
+        Ok(DataValue::Object(result))
+    }
+}
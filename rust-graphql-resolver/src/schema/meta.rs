@@ -0,0 +1,744 @@
+use std::{borrow::Borrow, collections::BTreeMap, fmt::Display};
+
+use indexmap::IndexMap;
+
+use crate::value::DataValue;
+
+use super::{
+    field::{
+        CustomType, Field, FieldType, InputField, InputFieldType, QLEnum, QLEnumValue, QLInput,
+        QLInterface, QLScalar, QLUnion, StaticType,
+    },
+    read_lock, Schema,
+};
+
+/// TypeKind
+///
+/// The kind of a graphql type, as `__TypeKind` in introspection
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TypeKind {
+    Scalar,
+    Object,
+    Interface,
+    Union,
+    Enum,
+    InputObject,
+    List,
+    NonNull,
+}
+
+impl TypeKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TypeKind::Scalar => "SCALAR",
+            TypeKind::Object => "OBJECT",
+            TypeKind::Interface => "INTERFACE",
+            TypeKind::Union => "UNION",
+            TypeKind::Enum => "ENUM",
+            TypeKind::InputObject => "INPUT_OBJECT",
+            TypeKind::List => "LIST",
+            TypeKind::NonNull => "NON_NULL",
+        }
+    }
+}
+
+/// TypeRef
+///
+/// Reference of a named type, wrapped by list and non-null
+#[derive(Clone, Debug, PartialEq)]
+pub enum TypeRef {
+    Named(String),
+    List(Box<TypeRef>),
+    NonNull(Box<TypeRef>),
+}
+
+impl TypeRef {
+    pub fn named(name: &str) -> Self {
+        Self::Named(name.to_string())
+    }
+
+    pub fn list(self) -> Self {
+        Self::List(Box::new(self))
+    }
+
+    pub fn non_null(self) -> Self {
+        Self::NonNull(Box::new(self))
+    }
+
+    /// the name of the innermost named type
+    pub fn named_type(&self) -> &str {
+        match self {
+            TypeRef::Named(name) => name,
+            TypeRef::List(t) | TypeRef::NonNull(t) => t.named_type(),
+        }
+    }
+}
+
+impl Display for TypeRef {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            TypeRef::Named(name) => write!(f, "{}", name),
+            TypeRef::List(t) => write!(f, "[{}]", t),
+            TypeRef::NonNull(t) => write!(f, "{}!", t),
+        }
+    }
+}
+
+impl From<&FieldType> for TypeRef {
+    fn from(field_type: &FieldType) -> Self {
+        match field_type {
+            FieldType::StaticType(t) => TypeRef::named(t.name()),
+            FieldType::NonNullType(t) => TypeRef::from(t.as_ref()).non_null(),
+            FieldType::List(t) => TypeRef::from(t.as_ref()).list(),
+            FieldType::Enum(e) => TypeRef::named(&e.name),
+            FieldType::ReferenceEnum(e) => TypeRef::named(&e.name),
+            FieldType::CustomType(c) => TypeRef::named(&c.name),
+            FieldType::ReferenceCustom(c) => match c.upgrade() {
+                Some(rc) => TypeRef::named(&read_lock(&rc).name),
+                None => TypeRef::named("<missing>"),
+            },
+            FieldType::ReferenceInterface(i) => match i.upgrade() {
+                Some(rc) => TypeRef::named(&read_lock(&rc).name),
+                None => TypeRef::named("<missing>"),
+            },
+            FieldType::ReferenceUnion(u) => TypeRef::named(&u.name),
+            FieldType::ReferenceScalar(s) => TypeRef::named(&s.name),
+        }
+    }
+}
+
+impl From<&InputFieldType> for TypeRef {
+    fn from(input_type: &InputFieldType) -> Self {
+        match input_type {
+            InputFieldType::StaticType(t) => TypeRef::named(t.name()),
+            InputFieldType::NonNullType(t) => TypeRef::from(t.as_ref()).non_null(),
+            InputFieldType::List(t) => TypeRef::from(t.as_ref()).list(),
+            InputFieldType::Enum(e) => TypeRef::named(&e.name),
+            InputFieldType::ReferenceEnum(e) => TypeRef::named(&e.name),
+            InputFieldType::QLInput(i) => TypeRef::named(&i.name),
+            InputFieldType::ReferenceInput(i) => match i.upgrade() {
+                Some(rc) => TypeRef::named(&read_lock(&rc).name),
+                None => TypeRef::named("<missing>"),
+            },
+            InputFieldType::ReferenceScalar(s) => TypeRef::named(&s.name),
+        }
+    }
+}
+
+/// InputValueMeta
+///
+/// Definition of an argument or an input object field
+#[derive(Clone, Debug)]
+pub struct InputValueMeta {
+    pub name: String,
+    pub description: String,
+    pub type_ref: TypeRef,
+    /// default value in graphql literal
+    pub default_value: Option<String>,
+}
+
+impl InputValueMeta {
+    pub(crate) fn new(name: &str, type_ref: TypeRef) -> Self {
+        Self {
+            name: name.to_string(),
+            description: String::default(),
+            type_ref,
+            default_value: None,
+        }
+    }
+}
+
+/// FieldMeta
+///
+/// Definition of an output field
+#[derive(Clone, Debug)]
+pub struct FieldMeta {
+    pub name: String,
+    pub description: String,
+    pub args: Vec<InputValueMeta>,
+    pub type_ref: TypeRef,
+}
+
+impl FieldMeta {
+    pub(crate) fn new(name: &str, type_ref: TypeRef) -> Self {
+        Self {
+            name: name.to_string(),
+            description: String::default(),
+            args: vec![],
+            type_ref,
+        }
+    }
+
+    fn with_arg(mut self, name: &str, type_ref: TypeRef, default_value: &str) -> Self {
+        let mut arg = InputValueMeta::new(name, type_ref);
+        arg.default_value = Some(default_value.to_string());
+        self.args.push(arg);
+        self
+    }
+}
+
+/// DirectiveMeta
+///
+/// Definition of a directive supported by the executor
+#[derive(Clone, Debug)]
+pub struct DirectiveMeta {
+    pub name: String,
+    pub description: String,
+    pub locations: Vec<String>,
+    pub args: Vec<InputValueMeta>,
+}
+
+/// TypeMeta
+///
+/// Definition of a named type, fields which don't fit the kind are left empty
+#[derive(Clone, Debug)]
+pub struct TypeMeta {
+    pub kind: TypeKind,
+    pub name: String,
+    pub description: String,
+    /// url of the specification of a custom scalar
+    pub specified_by_url: Option<String>,
+    pub fields: Vec<FieldMeta>,
+    pub input_fields: Vec<InputValueMeta>,
+    pub enum_values: Vec<QLEnumValue>,
+    pub interfaces: Vec<String>,
+    pub possible_types: Vec<String>,
+}
+
+impl TypeMeta {
+    fn new(kind: TypeKind, name: &str, description: &str) -> Self {
+        Self {
+            kind,
+            name: name.to_string(),
+            description: description.to_string(),
+            specified_by_url: None,
+            fields: vec![],
+            input_fields: vec![],
+            enum_values: vec![],
+            interfaces: vec![],
+            possible_types: vec![],
+        }
+    }
+
+    pub fn get_field(&self, name: &str) -> Option<&FieldMeta> {
+        self.fields.iter().find(|f| f.name == name)
+    }
+}
+
+/// SchemaMeta
+///
+/// All named types of a `Schema` collected by name, including the inline defined types,
+/// the root operation types, the built-in scalars and the introspection types
+#[derive(Clone, Debug)]
+pub struct SchemaMeta {
+    pub types: BTreeMap<String, TypeMeta>,
+    pub query_type: String,
+    pub mutation_type: Option<String>,
+    pub subscription_type: Option<String>,
+    pub directives: Vec<DirectiveMeta>,
+}
+
+pub const QUERY_TYPE_NAME: &str = "Query";
+pub const MUTATION_TYPE_NAME: &str = "Mutation";
+pub const SUBSCRIPTION_TYPE_NAME: &str = "Subscription";
+
+impl SchemaMeta {
+    pub fn new(schema: &Schema) -> Self {
+        let mut meta = Self {
+            types: BTreeMap::new(),
+            query_type: QUERY_TYPE_NAME.to_string(),
+            mutation_type: None,
+            subscription_type: None,
+            directives: specified_directives(),
+        };
+
+        for t in [
+            StaticType::ID,
+            StaticType::String,
+            StaticType::Int,
+            StaticType::Float,
+            StaticType::Boolean,
+            StaticType::DateTime,
+        ] {
+            meta.insert(TypeMeta::new(TypeKind::Scalar, t.name(), ""));
+        }
+        for scalar in schema.scalars.values() {
+            meta.collect_scalar(scalar);
+        }
+
+        let mut query_root = TypeMeta::new(TypeKind::Object, QUERY_TYPE_NAME, "");
+        let mut queries = schema.queries.iter().collect::<Vec<_>>();
+        queries.sort_by_key(|(name, _)| name.to_owned());
+        for (name, query) in queries {
+            query_root.fields.push(meta.collect_root_field(
+                name,
+                &query.description,
+                &query.arguments,
+                &query.field_type,
+            ));
+        }
+        meta.insert(query_root);
+
+        if let Some(mutations) = &schema.mutations {
+            let mut mutation_root = TypeMeta::new(TypeKind::Object, MUTATION_TYPE_NAME, "");
+            let mut mutations = mutations.iter().collect::<Vec<_>>();
+            mutations.sort_by_key(|(name, _)| name.to_owned());
+            for (name, mutation) in mutations {
+                mutation_root.fields.push(meta.collect_root_field(
+                    name,
+                    &mutation.description,
+                    &mutation.arguments,
+                    &mutation.field_type,
+                ));
+            }
+            meta.insert(mutation_root);
+            meta.mutation_type = Some(MUTATION_TYPE_NAME.to_string());
+        }
+
+        if let Some(subscriptions) = &schema.subscritions {
+            let mut subscription_root = TypeMeta::new(TypeKind::Object, SUBSCRIPTION_TYPE_NAME, "");
+            let mut subscriptions = subscriptions.iter().collect::<Vec<_>>();
+            subscriptions.sort_by_key(|(name, _)| name.to_owned());
+            for (name, subscription) in subscriptions {
+                subscription_root.fields.push(meta.collect_root_field(
+                    name,
+                    &subscription.description,
+                    &subscription.arguments,
+                    &subscription.field_type,
+                ));
+            }
+            meta.insert(subscription_root);
+            meta.subscription_type = Some(SUBSCRIPTION_TYPE_NAME.to_string());
+        }
+
+        for object in schema.objects.values() {
+            meta.collect_object(&read_lock(object));
+        }
+        for interface in schema.interfaces.values() {
+            meta.collect_interface(&read_lock(interface));
+        }
+        for union_type in schema.unions.values() {
+            meta.collect_union(union_type);
+        }
+        for t in meta.types.values_mut() {
+            if let TypeKind::Interface | TypeKind::Union = t.kind {
+                t.possible_types = schema.possible_types(&t.name);
+            }
+        }
+        for enum_type in schema.enums.values() {
+            meta.collect_enum(enum_type);
+        }
+        for input in schema.inputs.values() {
+            meta.collect_input(&read_lock(input));
+        }
+
+        for t in introspection_types() {
+            meta.insert(t);
+        }
+        meta
+    }
+
+    pub fn get_type(&self, name: &str) -> Option<&TypeMeta> {
+        self.types.get(name)
+    }
+
+    pub fn get_directive(&self, name: &str) -> Option<&DirectiveMeta> {
+        self.directives.iter().find(|d| d.name == name)
+    }
+
+    fn insert(&mut self, type_meta: TypeMeta) {
+        self.types.insert(type_meta.name.clone(), type_meta);
+    }
+
+    fn collect_root_field(
+        &mut self,
+        name: &str,
+        description: &str,
+        arguments: &BTreeMap<String, InputField>,
+        field_type: &FieldType,
+    ) -> FieldMeta {
+        self.collect_field_type(field_type);
+        let mut field = FieldMeta::new(name, TypeRef::from(field_type));
+        field.description = description.to_string();
+        field.args = self.collect_input_values(arguments);
+        field
+    }
+
+    /// the arguments of a field or the fields of an input object
+    fn collect_input_values(
+        &mut self,
+        input_values: &BTreeMap<String, InputField>,
+    ) -> Vec<InputValueMeta> {
+        let mut result = vec![];
+        for (name, input_value) in input_values.iter() {
+            self.collect_input_type(&input_value.field_type);
+            let mut value = InputValueMeta::new(name, TypeRef::from(&input_value.field_type));
+            value.description = input_value.description.clone();
+            value.default_value = input_value
+                .default_value
+                .as_ref()
+                .map(|default| print_value(default, &input_value.field_type));
+            result.push(value);
+        }
+        result
+    }
+
+    fn collect_field_type(&mut self, field_type: &FieldType) {
+        match field_type {
+            FieldType::StaticType(_) => {}
+            FieldType::NonNullType(t) | FieldType::List(t) => self.collect_field_type(t),
+            FieldType::Enum(e) => self.collect_enum(e),
+            FieldType::ReferenceEnum(e) => self.collect_enum(e),
+            FieldType::CustomType(c) => self.collect_object(c),
+            FieldType::ReferenceCustom(c) => {
+                if let Some(rc) = c.upgrade() {
+                    self.collect_object(&read_lock(&rc))
+                }
+            }
+            FieldType::ReferenceInterface(i) => {
+                if let Some(rc) = i.upgrade() {
+                    self.collect_interface(&read_lock(&rc))
+                }
+            }
+            FieldType::ReferenceUnion(u) => self.collect_union(u),
+            FieldType::ReferenceScalar(s) => self.collect_scalar(s),
+        }
+    }
+
+    fn collect_input_type(&mut self, input_type: &InputFieldType) {
+        match input_type {
+            InputFieldType::StaticType(_) => {}
+            InputFieldType::NonNullType(t) | InputFieldType::List(t) => self.collect_input_type(t),
+            InputFieldType::Enum(e) => self.collect_enum(e),
+            InputFieldType::ReferenceEnum(e) => self.collect_enum(e),
+            InputFieldType::QLInput(i) => self.collect_input(i),
+            InputFieldType::ReferenceInput(i) => {
+                if let Some(rc) = i.upgrade() {
+                    self.collect_input(&read_lock(&rc))
+                }
+            }
+            InputFieldType::ReferenceScalar(s) => self.collect_scalar(s),
+        }
+    }
+
+    fn collect_object(&mut self, object: &CustomType) {
+        if self.types.contains_key(&object.name) {
+            return;
+        }
+        // insert before walking the fields, so that recursive references stop here
+        self.insert(TypeMeta::new(
+            TypeKind::Object,
+            &object.name,
+            &object.description,
+        ));
+        let fields = self.collect_fields(&object.fields);
+        if let Some(t) = self.types.get_mut(&object.name) {
+            t.fields = fields;
+            t.interfaces = object.interfaces.clone();
+        }
+    }
+
+    fn collect_interface(&mut self, interface: &QLInterface) {
+        if self.types.contains_key(&interface.name) {
+            return;
+        }
+        self.insert(TypeMeta::new(
+            TypeKind::Interface,
+            &interface.name,
+            &interface.description,
+        ));
+        let fields = self.collect_fields(&interface.fields);
+        if let Some(t) = self.types.get_mut(&interface.name) {
+            t.fields = fields;
+        }
+    }
+
+    fn collect_union(&mut self, union_type: &QLUnion) {
+        if self.types.contains_key(&union_type.name) {
+            return;
+        }
+        self.insert(TypeMeta::new(
+            TypeKind::Union,
+            &union_type.name,
+            &union_type.description,
+        ));
+    }
+
+    fn collect_fields<F: Borrow<Field>>(&mut self, fields: &BTreeMap<String, F>) -> Vec<FieldMeta> {
+        let mut result = vec![];
+        for (name, field) in fields.iter() {
+            let field = field.borrow();
+            self.collect_field_type(&field.field_type);
+            let mut field_meta = FieldMeta::new(name, TypeRef::from(&field.field_type));
+            field_meta.description = field.description.clone();
+            field_meta.args = self.collect_input_values(&field.arguments);
+            result.push(field_meta);
+        }
+        result
+    }
+
+    fn collect_scalar(&mut self, scalar: &QLScalar) {
+        if self.types.contains_key(&scalar.name) {
+            return;
+        }
+        let mut t = TypeMeta::new(TypeKind::Scalar, &scalar.name, &scalar.description);
+        t.specified_by_url = scalar.specified_by_url.clone();
+        self.insert(t);
+    }
+
+    fn collect_enum(&mut self, enum_type: &QLEnum) {
+        if self.types.contains_key(&enum_type.name) {
+            return;
+        }
+        let mut t = TypeMeta::new(TypeKind::Enum, &enum_type.name, &enum_type.description);
+        t.enum_values = enum_type.values.clone();
+        self.insert(t);
+    }
+
+    fn collect_input(&mut self, input: &QLInput) {
+        if self.types.contains_key(&input.name) {
+            return;
+        }
+        self.insert(TypeMeta::new(
+            TypeKind::InputObject,
+            &input.name,
+            &input.description,
+        ));
+        let input_fields = self.collect_input_values(&input.fields);
+        if let Some(t) = self.types.get_mut(&input.name) {
+            t.input_fields = input_fields;
+        }
+    }
+}
+
+/// Print an input value as a graphql literal, strings of enum types are printed as enum values
+pub(crate) fn print_value(value: &DataValue, input_type: &InputFieldType) -> String {
+    match (input_type, value) {
+        (_, DataValue::Null) => "null".to_string(),
+        (InputFieldType::NonNullType(t), value) => print_value(value, t),
+        (InputFieldType::List(t), DataValue::List(list)) => format!(
+            "[{}]",
+            list.iter()
+                .map(|item| print_value(item, t))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+        (InputFieldType::List(t), value) => print_value(value, t),
+        (InputFieldType::Enum(_), DataValue::String(s))
+        | (InputFieldType::ReferenceEnum(_), DataValue::String(s)) => s.clone(),
+        (InputFieldType::QLInput(i), DataValue::Object(map)) => print_object(map, i),
+        (InputFieldType::ReferenceInput(i), DataValue::Object(map)) => match i.upgrade() {
+            Some(input) => {
+                let input = read_lock(&input);
+                print_object(map, &input)
+            }
+            None => print_plain_value(value),
+        },
+        (_, value) => print_plain_value(value),
+    }
+}
+
+fn print_object(map: &IndexMap<String, DataValue>, input: &QLInput) -> String {
+    let fields = map
+        .iter()
+        .map(|(name, value)| match input.fields.get(name) {
+            Some(field) => format!("{}: {}", name, print_value(value, &field.field_type)),
+            None => format!("{}: {}", name, print_plain_value(value)),
+        })
+        .collect::<Vec<_>>();
+    format!("{{{}}}", fields.join(", "))
+}
+
+/// print a value without its type, scalars are printed as their json
+fn print_plain_value(value: &DataValue) -> String {
+    match value {
+        DataValue::List(list) => format!(
+            "[{}]",
+            list.iter()
+                .map(print_plain_value)
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+        DataValue::Object(map) => format!(
+            "{{{}}}",
+            map.iter()
+                .map(|(name, value)| format!("{}: {}", name, print_plain_value(value)))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+        value => serde_json::to_string(value).unwrap_or_default(),
+    }
+}
+
+fn enum_meta(name: &str, values: &[&str]) -> TypeMeta {
+    let mut t = TypeMeta::new(TypeKind::Enum, name, "");
+    t.enum_values = values
+        .iter()
+        .map(|v| QLEnumValue {
+            value: v.to_string(),
+            description: String::default(),
+        })
+        .collect();
+    t
+}
+
+fn object_meta(name: &str, description: &str, fields: Vec<FieldMeta>) -> TypeMeta {
+    let mut t = TypeMeta::new(TypeKind::Object, name, description);
+    t.fields = fields;
+    t
+}
+
+/// `@include` and `@skip`, both executed when collecting the selections
+fn specified_directives() -> Vec<DirectiveMeta> {
+    let directive = |name: &str, description: &str, if_description: &str| {
+        let mut if_arg = InputValueMeta::new("if", TypeRef::named("Boolean").non_null());
+        if_arg.description = if_description.to_string();
+        DirectiveMeta {
+            name: name.to_string(),
+            description: description.to_string(),
+            locations: ["FIELD", "FRAGMENT_SPREAD", "INLINE_FRAGMENT"]
+                .iter()
+                .map(|l| l.to_string())
+                .collect(),
+            args: vec![if_arg],
+        }
+    };
+    vec![
+        directive(
+            "include",
+            "Directs the executor to include this field or fragment only when the `if` argument is true.",
+            "Included when true.",
+        ),
+        directive(
+            "skip",
+            "Directs the executor to skip this field or fragment when the `if` argument is true.",
+            "Skipped when true.",
+        ),
+    ]
+}
+
+/// the types of the introspection system itself
+fn introspection_types() -> Vec<TypeMeta> {
+    let string = || TypeRef::named("String");
+    let boolean = || TypeRef::named("Boolean");
+    let type_ref = || TypeRef::named("__Type");
+    let non_null_list = |name: &str| TypeRef::named(name).non_null().list();
+    vec![
+        object_meta(
+            "__Schema",
+            "A GraphQL Schema defines the capabilities of a GraphQL server.",
+            vec![
+                FieldMeta::new("description", string()),
+                FieldMeta::new("types", non_null_list("__Type").non_null()),
+                FieldMeta::new("queryType", type_ref().non_null()),
+                FieldMeta::new("mutationType", type_ref()),
+                FieldMeta::new("subscriptionType", type_ref()),
+                FieldMeta::new("directives", non_null_list("__Directive").non_null()),
+            ],
+        ),
+        object_meta(
+            "__Type",
+            "The fundamental unit of any GraphQL Schema is the type.",
+            vec![
+                FieldMeta::new("kind", TypeRef::named("__TypeKind").non_null()),
+                FieldMeta::new("name", string()),
+                FieldMeta::new("description", string()),
+                FieldMeta::new("specifiedByURL", string()),
+                FieldMeta::new("fields", non_null_list("__Field")).with_arg(
+                    "includeDeprecated",
+                    boolean(),
+                    "false",
+                ),
+                FieldMeta::new("interfaces", non_null_list("__Type")),
+                FieldMeta::new("possibleTypes", non_null_list("__Type")),
+                FieldMeta::new("enumValues", non_null_list("__EnumValue")).with_arg(
+                    "includeDeprecated",
+                    boolean(),
+                    "false",
+                ),
+                FieldMeta::new("inputFields", non_null_list("__InputValue")),
+                FieldMeta::new("ofType", type_ref()),
+            ],
+        ),
+        object_meta(
+            "__Field",
+            "Object and Interface types are described by a list of Fields.",
+            vec![
+                FieldMeta::new("name", string().non_null()),
+                FieldMeta::new("description", string()),
+                FieldMeta::new("args", non_null_list("__InputValue").non_null()),
+                FieldMeta::new("type", type_ref().non_null()),
+                FieldMeta::new("isDeprecated", boolean().non_null()),
+                FieldMeta::new("deprecationReason", string()),
+            ],
+        ),
+        object_meta(
+            "__InputValue",
+            "Arguments provided to Fields or Directives and the input fields of an InputObject.",
+            vec![
+                FieldMeta::new("name", string().non_null()),
+                FieldMeta::new("description", string()),
+                FieldMeta::new("type", type_ref().non_null()),
+                FieldMeta::new("defaultValue", string()),
+            ],
+        ),
+        object_meta(
+            "__EnumValue",
+            "One possible value for a given Enum.",
+            vec![
+                FieldMeta::new("name", string().non_null()),
+                FieldMeta::new("description", string()),
+                FieldMeta::new("isDeprecated", boolean().non_null()),
+                FieldMeta::new("deprecationReason", string()),
+            ],
+        ),
+        object_meta(
+            "__Directive",
+            "A Directive provides a way to describe alternate runtime execution.",
+            vec![
+                FieldMeta::new("name", string().non_null()),
+                FieldMeta::new("description", string()),
+                FieldMeta::new("locations", non_null_list("__DirectiveLocation").non_null()),
+                FieldMeta::new("args", non_null_list("__InputValue").non_null()),
+                FieldMeta::new("isRepeatable", boolean().non_null()),
+            ],
+        ),
+        enum_meta(
+            "__TypeKind",
+            &[
+                "SCALAR",
+                "OBJECT",
+                "INTERFACE",
+                "UNION",
+                "ENUM",
+                "INPUT_OBJECT",
+                "LIST",
+                "NON_NULL",
+            ],
+        ),
+        enum_meta(
+            "__DirectiveLocation",
+            &[
+                "QUERY",
+                "MUTATION",
+                "SUBSCRIPTION",
+                "FIELD",
+                "FRAGMENT_DEFINITION",
+                "FRAGMENT_SPREAD",
+                "INLINE_FRAGMENT",
+                "VARIABLE_DEFINITION",
+                "SCHEMA",
+                "SCALAR",
+                "OBJECT",
+                "FIELD_DEFINITION",
+                "ARGUMENT_DEFINITION",
+                "INTERFACE",
+                "UNION",
+                "ENUM",
+                "ENUM_VALUE",
+                "INPUT_OBJECT",
+                "INPUT_FIELD_DEFINITION",
+            ],
+        ),
+    ]
+}
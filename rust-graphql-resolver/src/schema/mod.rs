@@ -1,54 +1,86 @@
 use std::{
-    cell::RefCell,
-    collections::{BTreeMap, HashMap},
-    rc::Rc,
+    borrow::Cow,
+    collections::HashMap,
+    sync::{Arc, Mutex, OnceLock, PoisonError, RwLock, RwLockReadGuard, RwLockWriteGuard},
 };
 
-use gurkle_parser::query::{
-    Definition, Document, FragmentDefinition, Mutation as AstMutation, OperationDefinition,
-    Query as AstQuery, Selection, SelectionSet, Subscription as AstSubscription,
+use futures::{
+    future::join_all,
+    stream::{self, BoxStream, StreamExt},
 };
+use gurkle_parser::{
+    query::{
+        Definition, Directive, Document, Field as AstField, FragmentDefinition,
+        Mutation as AstMutation, OperationDefinition, Query as AstQuery, Selection, SelectionSet,
+        Subscription as AstSubscription, Type, TypeCondition, VariableDefinition,
+    },
+    Pos,
+};
+use indexmap::IndexMap;
 
 use crate::{
     error::{Error, Result},
+    response::{Location, PathSegment, QLError, Response},
+    validation::validate,
     value::DataValue,
 };
 
 use self::{
-    field::{CustomType, QLEnum, QLInput},
-    mutation::MutationMap,
-    query::QueryMap,
-    resolve::QLContext,
+    field::{
+        CustomType, InputFieldType, QLEnum, QLInput, QLInterface, QLScalar, QLUnion, StaticType,
+    },
+    introspection::Introspector,
+    meta::{SchemaMeta, MUTATION_TYPE_NAME, QUERY_TYPE_NAME, SUBSCRIPTION_TYPE_NAME},
+    mutation::{Mutation, MutationMap},
+    query::{Query, QueryMap},
+    resolve::{QLContext, QLVariables},
+    subscription::SubscriptionMap,
 };
 
 pub mod field;
+pub(crate) mod introspection;
+pub mod meta;
 pub mod mutation;
 pub mod query;
 pub mod resolve;
-
-/// NotSupported
-#[derive(Clone, Debug)]
-pub struct NotSupported;
+pub(crate) mod sdl;
+pub mod subscription;
 
 /// Schema: The main logic struct
 /// * query: definition for query apis, and resolve by request
-/// * mutation: todo
-/// * subscrition: todo
+/// * mutation: definition for mutation apis, executed one by one
+/// * subscrition: definition for subscription apis, executed as a stream of responses
+///
+/// It is `Send + Sync`, so one built schema can be shared with `Arc` by every worker thread.
+/// It is built by `SchemaBuilder` and read only afterwards, the type system collected by `meta` stays valid
 #[derive(Clone, Debug)]
 pub struct Schema {
     /// for tracting instance's id
-    pub id: String,
-    pub queries: QueryMap,
-    pub mutations: Option<MutationMap>,
-    /// TODO: Subscrition
-    pub subscritions: Option<NotSupported>,
+    pub(crate) id: String,
+    pub(crate) queries: QueryMap,
+    pub(crate) mutations: Option<MutationMap>,
+    pub(crate) subscritions: Option<SubscriptionMap>,
 
     /// storage of reference object types
-    pub objects: HashMap<String, Rc<RefCell<CustomType>>>,
+    pub(crate) objects: HashMap<String, Arc<RwLock<CustomType>>>,
     /// storage of reference enum types
-    pub enums: HashMap<String, Rc<QLEnum>>,
+    pub(crate) enums: HashMap<String, Arc<QLEnum>>,
     /// storage of reference input object types
-    pub inputs: HashMap<String, Rc<RefCell<QLInput>>>,
+    pub(crate) inputs: HashMap<String, Arc<RwLock<QLInput>>>,
+    /// storage of reference interface types
+    pub(crate) interfaces: HashMap<String, Arc<RwLock<QLInterface>>>,
+    /// storage of reference union types
+    pub(crate) unions: HashMap<String, Arc<QLUnion>>,
+    /// storage of reference custom scalar types
+    pub(crate) scalars: HashMap<String, Arc<QLScalar>>,
+    /// the collected type system, kept once the schema is built
+    pub(crate) meta: OnceLock<SchemaMeta>,
+}
+
+/// The query or mutation operation selected to execute
+enum SelectedOperation {
+    Query(SelectionSet),
+    Mutation(SelectionSet),
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -57,12 +89,13 @@ pub(crate) enum OperationKey {
     RealNamed(String),
 }
 
-impl ToString for OperationKey {
-    fn to_string(&self) -> String {
-        format!("{:?}", self)
+impl std::fmt::Display for OperationKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{:?}", self)
     }
 }
 
+#[derive(Default)]
 pub(crate) struct OperationGroup {
     selection_set: Option<SelectionSet>,
     queries: HashMap<OperationKey, AstQuery>,
@@ -81,10 +114,42 @@ impl OperationGroup {
         selection_set_count + self.queries.len() + self.mutations.len() + self.subscriptions.len()
     }
 
+    /// the key of the only operation, if there is exactly one
+    fn only_key(&self) -> Option<OperationKey> {
+        if self.count() != 1 {
+            return None;
+        }
+        self.queries
+            .keys()
+            .chain(self.mutations.keys())
+            .chain(self.subscriptions.keys())
+            .next()
+            .cloned()
+    }
+
     fn contains_anonymous(&self) -> bool {
         self.selection_set.is_some()
             && (self.queries.contains_key(&OperationKey::Anonymous)
-                || self.mutations.contains_key(&OperationKey::Anonymous))
+                || self.mutations.contains_key(&OperationKey::Anonymous)
+                || self.subscriptions.contains_key(&OperationKey::Anonymous))
+    }
+
+    /// the key of the operation to execute, the only operation is picked when operation name is not provided
+    fn operation_key(&self, operation_name: Option<String>) -> Result<OperationKey> {
+        let key = match (opt_to_operation_key(operation_name), self.only_key()) {
+            (OperationKey::Anonymous, Some(only)) => only,
+            (key, _) => key,
+        };
+
+        if self.count() > 1 {
+            if let OperationKey::Anonymous = key {
+                return Err(Error::MultipleOperationNeedTarget);
+            }
+            if self.contains_anonymous() {
+                return Err(Error::MustBeDefinedAnonymousOperation);
+            }
+        }
+        Ok(key)
     }
 }
 
@@ -95,19 +160,335 @@ fn opt_to_operation_key(opt: Option<String>) -> OperationKey {
     }
 }
 
-impl Default for OperationGroup {
-    fn default() -> Self {
+/// ExecuteError
+///
+/// Failure of executing a field or list item
+/// * Raised: a new error, not recorded in response yet
+/// * NullPropagation: a non-null value became null, the error is recorded already
+#[derive(Debug)]
+pub(crate) enum ExecuteError {
+    Raised(Error),
+    NullPropagation,
+}
+
+impl From<Error> for ExecuteError {
+    fn from(err: Error) -> Self {
+        ExecuteError::Raised(err)
+    }
+}
+
+pub(crate) type ExecuteResult<T> = std::result::Result<T, ExecuteError>;
+
+/// Read a type of the schema, types are only written while building the schema
+pub(crate) fn read_lock<T>(lock: &RwLock<T>) -> RwLockReadGuard<'_, T> {
+    lock.read().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Write a type of the schema while building it
+pub(crate) fn write_lock<T>(lock: &RwLock<T>) -> RwLockWriteGuard<'_, T> {
+    lock.write().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// ExecuteEnv
+///
+/// Request-wide data shared by every layer of one operation's execution
+pub(crate) struct ExecuteEnv<'a> {
+    pub(crate) schema: &'a Schema,
+    pub(crate) fragments: HashMap<String, FragmentDefinition>,
+    /// coerced variables of the executing operation
+    pub(crate) variables: QLVariables,
+    /// field errors collected during execution
+    errors: Mutex<Vec<QLError>>,
+}
+
+impl<'a> ExecuteEnv<'a> {
+    pub(crate) fn new(
+        schema: &'a Schema,
+        fragments: HashMap<String, FragmentDefinition>,
+        variables: QLVariables,
+    ) -> Self {
         Self {
-            selection_set: None,
-            queries: HashMap::default(),
-            mutations: HashMap::default(),
-            subscriptions: HashMap::default(),
-            fragments: HashMap::default(),
+            schema,
+            fragments,
+            variables,
+            errors: Mutex::new(vec![]),
         }
     }
+
+    /// record the error of a field or list item
+    pub(crate) fn add_error(&self, error: Error, path: Vec<PathSegment>, position: Pos) {
+        self.errors
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .push(QLError::with_field(error, path, Location::from(position)));
+    }
+
+    /// Complete the executed value of a field or list item at `path`.
+    /// A raised error is recorded, then the value becomes `null` if it's nullable,
+    /// otherwise the null propagates to the parent
+    pub(crate) fn complete_value(
+        &self,
+        result: ExecuteResult<DataValue>,
+        nullable: bool,
+        path: Vec<PathSegment>,
+        position: Pos,
+    ) -> ExecuteResult<DataValue> {
+        match result {
+            Ok(value) => Ok(value),
+            Err(err) => {
+                if let ExecuteError::Raised(error) = err {
+                    self.add_error(error, path, position);
+                }
+                if nullable {
+                    Ok(DataValue::Null)
+                } else {
+                    Err(ExecuteError::NullPropagation)
+                }
+            }
+        }
+    }
+
+    /// Build the response of the executed data, the data is `null` when a non-null root field fails
+    pub(crate) fn into_response(self, data: ExecuteResult<DataValue>) -> Result<Response> {
+        let data = match data {
+            Ok(data) => data,
+            Err(ExecuteError::NullPropagation) => DataValue::Null,
+            Err(ExecuteError::Raised(err)) => return Err(err),
+        };
+        let errors = self
+            .errors
+            .into_inner()
+            .unwrap_or_else(PoisonError::into_inner);
+        Ok(Response::new(data, errors))
+    }
+
+    pub(crate) fn get_fragment(&self, name: &str) -> Result<&FragmentDefinition> {
+        self.fragments
+            .get(name)
+            .ok_or_else(|| Error::NoSuchFragment(name.to_string()))
+    }
+
+    /// Whether the selections of a fragment apply to the object type named `type_name`,
+    /// fragments without type condition always apply
+    pub(crate) fn type_condition_applies(
+        &self,
+        condition: Option<&TypeCondition>,
+        type_name: &str,
+    ) -> bool {
+        match condition {
+            Some(TypeCondition::On(name)) => {
+                name == type_name || self.schema.is_possible_type(name, type_name)
+            }
+            None => true,
+        }
+    }
+
+    /// Flatten the selections into the fields applying to the object type named `type_name`,
+    /// in request order, the selections of fragments are spread in place
+    fn collect_fields<'s>(
+        &'s self,
+        selections: &'s [Selection],
+        type_name: &str,
+        fields: &mut Vec<&'s AstField>,
+    ) -> Result<()> {
+        for selection in selections {
+            match selection {
+                Selection::Field(field) => {
+                    if self.should_include(&field.directives)? {
+                        fields.push(field);
+                    }
+                }
+                Selection::FragmentSpread(fs) => {
+                    if !self.should_include(&fs.directives)? {
+                        continue;
+                    }
+                    let fragment = self.get_fragment(&fs.fragment_name)?;
+                    if self.type_condition_applies(Some(&fragment.type_condition), type_name) {
+                        self.collect_fields(&fragment.selection_set.items, type_name, fields)?;
+                    }
+                }
+                Selection::InlineFragment(inline) => {
+                    if self.should_include(&inline.directives)?
+                        && self.type_condition_applies(inline.type_condition.as_ref(), type_name)
+                    {
+                        self.collect_fields(&inline.selection_set.items, type_name, fields)?;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// `@skip(if:)` and `@include(if:)` decide whether a selection is collected, other directives are ignored
+    fn should_include(&self, directives: &[Directive]) -> Result<bool> {
+        for directive in directives {
+            let skip_if = match directive.name.as_str() {
+                "skip" => true,
+                "include" => false,
+                _ => continue,
+            };
+            let condition = directive
+                .arguments
+                .iter()
+                .find(|(name, _)| name == "if")
+                .map(|(_, value)| DataValue::from_parser_value(value.clone(), &self.variables));
+            match condition {
+                Some(DataValue::Boolean(b)) if b == skip_if => return Ok(false),
+                Some(DataValue::Boolean(_)) => {}
+                Some(other) => {
+                    return Err(Error::InvalidArgumentValue(
+                        "if".to_string(),
+                        Error::DataTypeMisMatchError("Boolean!".to_string(), other.get_type_name())
+                            .to_string(),
+                    ))
+                }
+                None => {
+                    return Err(Error::MissingArgument(
+                        format!("@{}", directive.name),
+                        "if".to_string(),
+                        "Boolean!".to_string(),
+                    ))
+                }
+            }
+        }
+        Ok(true)
+    }
+
+    /// Collect the fields like `collect_fields`, grouped by response key in request order.
+    /// A field selected more than once is merged into the first one, with the subfields of all of them
+    pub(crate) fn group_fields<'s>(
+        &'s self,
+        selections: &'s [Selection],
+        type_name: &str,
+    ) -> Result<Vec<Cow<'s, AstField>>> {
+        let mut fields = vec![];
+        self.collect_fields(selections, type_name, &mut fields)?;
+        let mut grouped: Vec<Cow<AstField>> = vec![];
+        for field in fields {
+            match grouped
+                .iter_mut()
+                .find(|grouped_field| response_key(grouped_field) == response_key(field))
+            {
+                Some(first) => first
+                    .to_mut()
+                    .selection_set
+                    .items
+                    .extend(field.selection_set.items.iter().cloned()),
+                None => grouped.push(Cow::Borrowed(field)),
+            }
+        }
+        Ok(grouped)
+    }
+}
+
+/// the key of a field in the response, its alias or its name
+pub(crate) fn response_key(field: &AstField) -> &str {
+    field.alias.as_deref().unwrap_or(&field.name)
+}
+
+fn find_inline_input_type(input_type: &InputFieldType, name: &str) -> Option<InputFieldType> {
+    match input_type {
+        InputFieldType::NonNullType(t) | InputFieldType::List(t) => find_inline_input_type(t, name),
+        InputFieldType::Enum(e) if e.name == name => Some(input_type.clone()),
+        InputFieldType::QLInput(i) if i.name == name => Some(input_type.clone()),
+        InputFieldType::QLInput(i) => i
+            .fields
+            .values()
+            .find_map(|f| find_inline_input_type(&f.field_type, name)),
+        _ => None,
+    }
 }
 
 impl Schema {
+    /// for tracting instance's id
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    pub fn queries(&self) -> &QueryMap {
+        &self.queries
+    }
+
+    pub fn mutations(&self) -> Option<&MutationMap> {
+        self.mutations.as_ref()
+    }
+
+    pub fn subscriptions(&self) -> Option<&SubscriptionMap> {
+        self.subscritions.as_ref()
+    }
+
+    /// The object type named `name`
+    pub fn object(&self, name: &str) -> Option<RwLockReadGuard<'_, CustomType>> {
+        self.objects.get(name).map(|object| read_lock(object))
+    }
+
+    /// The enum type named `name`
+    pub fn enum_type(&self, name: &str) -> Option<&QLEnum> {
+        self.enums.get(name).map(|enum_type| enum_type.as_ref())
+    }
+
+    /// The input object type named `name`
+    pub fn input(&self, name: &str) -> Option<RwLockReadGuard<'_, QLInput>> {
+        self.inputs.get(name).map(|input| read_lock(input))
+    }
+
+    /// The interface type named `name`
+    pub fn interface(&self, name: &str) -> Option<RwLockReadGuard<'_, QLInterface>> {
+        self.interfaces
+            .get(name)
+            .map(|interface| read_lock(interface))
+    }
+
+    /// The union type named `name`
+    pub fn union(&self, name: &str) -> Option<&QLUnion> {
+        self.unions.get(name).map(|union_type| union_type.as_ref())
+    }
+
+    /// The custom scalar type named `name`
+    pub fn scalar(&self, name: &str) -> Option<&QLScalar> {
+        self.scalars.get(name).map(|scalar| scalar.as_ref())
+    }
+
+    /// The type system of this schema, as exposed by introspection,
+    /// collected once since the types are only written while building the schema
+    pub fn meta(&self) -> &SchemaMeta {
+        self.meta.get_or_init(|| SchemaMeta::new(self))
+    }
+
+    /// Print the schema as graphql SDL, the types are printed in a stable order
+    pub fn to_sdl(&self) -> String {
+        sdl::print_schema(self.meta())
+    }
+
+    /// Whether the object type named `object_name` implements the interface,
+    /// or is a member of the union named `abstract_name`
+    pub fn is_possible_type(&self, abstract_name: &str, object_name: &str) -> bool {
+        if let Some(union_type) = self.unions.get(abstract_name) {
+            return union_type.types.iter().any(|t| t == object_name);
+        }
+        if self.interfaces.contains_key(abstract_name) {
+            return self.objects.get(object_name).is_some_and(|object| {
+                read_lock(object)
+                    .interfaces
+                    .iter()
+                    .any(|i| i == abstract_name)
+            });
+        }
+        false
+    }
+
+    /// Names of the object types an interface or union may resolve to, sorted by name
+    pub fn possible_types(&self, abstract_name: &str) -> Vec<String> {
+        let mut names = self
+            .objects
+            .keys()
+            .filter(|name| self.is_possible_type(abstract_name, name))
+            .cloned()
+            .collect::<Vec<_>>();
+        names.sort();
+        names
+    }
+
     pub(crate) fn grouping_document(&self, doc: Document) -> Result<OperationGroup> {
         let mut group = OperationGroup::default();
         for def in doc.definitions {
@@ -146,134 +527,472 @@ impl Schema {
         Ok(group)
     }
 
+    /// Execute the document synchronously, the resolvers run one by one sharing the context
     pub(crate) fn execute_document(
         &self,
-        context: QLContext,
+        mut context: QLContext,
         doc: Document,
         operation_name: Option<String>,
-    ) -> Result<DataValue> {
-        let group = self.grouping_document(doc)?;
+        variables: QLVariables,
+    ) -> Response {
+        let errors = validate(self, &doc);
+        if !errors.is_empty() {
+            return Response::from_errors(errors);
+        }
+        let result = self
+            .grouping_document(doc)
+            .and_then(|group| self.select_operation(group, operation_name, variables))
+            .and_then(|(operation, env)| {
+                let data = match operation {
+                    SelectedOperation::Query(selection_set) => {
+                        self.execute_selection_set(&mut context, &selection_set.items, &env)
+                    }
+                    SelectedOperation::Mutation(selection_set) => {
+                        self.execute_mutation(&mut context, &selection_set.items, &env)
+                    }
+                };
+                env.into_response(data)
+            });
+        result.unwrap_or_else(Response::from_error)
+    }
 
-        let key = opt_to_operation_key(operation_name);
+    pub(crate) async fn execute_document_async(
+        &self,
+        context: QLContext,
+        doc: Document,
+        operation_name: Option<String>,
+        variables: QLVariables,
+    ) -> Response {
+        let errors = validate(self, &doc);
+        if !errors.is_empty() {
+            return Response::from_errors(errors);
+        }
+        match self.grouping_document(doc) {
+            Ok(group) => {
+                self.execute_grouped_operation_async(context, group, operation_name, variables)
+                    .await
+            }
+            Err(err) => Response::from_error(err),
+        }
+    }
 
-        if group.count() > 1 {
-            if let OperationKey::Anonymous = key {
-                return Err(Error::MultipleOperationNeedTarget);
+    /// Select the operation and execute it, errors before executing fail the whole request
+    async fn execute_grouped_operation_async(
+        &self,
+        mut context: QLContext,
+        group: OperationGroup,
+        operation_name: Option<String>,
+        variables: QLVariables,
+    ) -> Response {
+        let (operation, env) = match self.select_operation(group, operation_name, variables) {
+            Ok(selected) => selected,
+            Err(err) => return Response::from_error(err),
+        };
+        let data = match operation {
+            SelectedOperation::Query(selection_set) => {
+                self.execute_selection_set_async(&context, &selection_set.items, &env)
+                    .await
             }
-            if group.contains_anonymous() {
-                return Err(Error::MustBeDefinedAnonymousOperation);
+            SelectedOperation::Mutation(selection_set) => {
+                self.execute_mutation_async(&mut context, &selection_set.items, &env)
+                    .await
             }
-        }
+        };
+        env.into_response(data).unwrap_or_else(Response::from_error)
+    }
+
+    /// Select the operation to execute and coerce its variables, a subscription needs a stream
+    fn select_operation(
+        &self,
+        mut group: OperationGroup,
+        operation_name: Option<String>,
+        variables: QLVariables,
+    ) -> Result<(SelectedOperation, ExecuteEnv<'_>)> {
+        let key = group.operation_key(operation_name)?;
+
+        let fragments = group.fragments;
 
         if let Some(selection_set) = group.selection_set {
-            return self.execute_selection_set(context, selection_set, &group.fragments);
+            let env = ExecuteEnv::new(self, fragments, QLVariables::default());
+            return Ok((SelectedOperation::Query(selection_set), env));
         }
 
-        if let Some(query) = group.queries.get(&key) {
-            return self.execute_query(context, query.to_owned(), &group.fragments);
+        if let Some(query) = group.queries.remove(&key) {
+            let variables = self.coerce_variables(&query.variable_definitions, variables)?;
+            let env = ExecuteEnv::new(self, fragments, variables);
+            return Ok((SelectedOperation::Query(query.selection_set), env));
         }
 
-        if let Some(mutation) = group.mutations.get(&key) {
-            return self.execute_mutation(context, mutation.to_owned(), &group.fragments);
+        if let Some(mutation) = group.mutations.remove(&key) {
+            let variables = self.coerce_variables(&mutation.variable_definitions, variables)?;
+            let env = ExecuteEnv::new(self, fragments, variables);
+            return Ok((SelectedOperation::Mutation(mutation.selection_set), env));
         }
 
-        if let Some(_sub) = group.subscriptions.get(&key) {
-            return Err(Error::UnSupportedYetError(
-                "'Subscription' in schema request".to_string(),
-            ));
+        if group.subscriptions.contains_key(&key) {
+            return Err(Error::SubscriptionNeedsStream);
         }
 
-        Err(Error::NotFoundError(format!(
-            "Operation named '{}'",
-            key.to_string()
-        )))
+        Err(Error::NotFoundError(format!("Operation named '{}'", key)))
     }
 
-    pub(crate) fn execute_selection_set(
-        &self,
+    /// Execute a subscription operation as a stream of responses, one for each source event.
+    /// Other operations result in a stream of their only response,
+    /// and errors before executing result in a stream of the only error response
+    pub(crate) fn subscribe_document<'a>(
+        &'a self,
+        context: QLContext,
+        doc: Document,
+        operation_name: Option<String>,
+        variables: QLVariables,
+    ) -> BoxStream<'a, Response> {
+        let errors = validate(self, &doc);
+        if !errors.is_empty() {
+            return stream::once(async { Response::from_errors(errors) }).boxed();
+        }
+        let mut group = match self.grouping_document(doc) {
+            Ok(group) => group,
+            Err(err) => return stream::once(async { Response::from_error(err) }).boxed(),
+        };
+        let subscription = group
+            .operation_key(operation_name.clone())
+            .map(|key| group.subscriptions.remove(&key));
+        match subscription {
+            Ok(Some(subscription)) => {
+                match self.subscribe_operation(context, subscription, group.fragments, variables) {
+                    Ok(responses) => responses,
+                    Err(err) => stream::once(async { Response::from_error(err) }).boxed(),
+                }
+            }
+            Ok(None) => stream::once(self.execute_grouped_operation_async(
+                context,
+                group,
+                operation_name,
+                variables,
+            ))
+            .boxed(),
+            Err(err) => stream::once(async { Response::from_error(err) }).boxed(),
+        }
+    }
+
+    /// Create the source event stream of the only root field,
+    /// then execute the selection sets against every event with a new copy of the context
+    fn subscribe_operation<'a>(
+        &'a self,
         mut context: QLContext,
-        sets: SelectionSet,
-        fragments: &HashMap<String, FragmentDefinition>,
-    ) -> Result<DataValue> {
-        let mut result = BTreeMap::<String, DataValue>::new();
-        for set in sets.items {
-            match set {
-                Selection::Field(field) => {
-                    let name = field.name.clone();
-                    let insert_key = if let Some(alias) = field.alias.clone() {
-                        alias
-                    } else {
-                        name.clone()
-                    };
-                    let query_result = self
-                        .queries
-                        .get(&name)
-                        .ok_or(Error::NotFoundError(format!("Query api {}", &name)))?
-                        .execute(&mut context, fragments, field)?;
-                    result.insert(insert_key, query_result);
+        subscription: AstSubscription,
+        fragments: HashMap<String, FragmentDefinition>,
+        variables: QLVariables,
+    ) -> Result<BoxStream<'a, Response>> {
+        let variables = self.coerce_variables(&subscription.variable_definitions, variables)?;
+        let env = ExecuteEnv::new(self, fragments, variables);
+        let field = {
+            let fields =
+                env.group_fields(&subscription.selection_set.items, SUBSCRIPTION_TYPE_NAME)?;
+            match fields.as_slice() {
+                [field] => field.clone().into_owned(),
+                _ => return Err(Error::SubscriptionSingleRootField),
+            }
+        };
+        let subscription_api = self
+            .subscritions
+            .as_ref()
+            .ok_or(Error::SubscriptionSchemaNotDefined)?
+            .get(&field.name)
+            .ok_or_else(|| Error::NotFoundError(format!("Subscription api {}", field.name)))?;
+        let events = subscription_api.subscribe(&mut context, &env, &field)?;
+
+        let insert_key = response_key(&field).to_string();
+        let nullable = subscription_api.field_type.is_nullable();
+        let responses = events.then(move |event| {
+            // the boxed value is converted before awaiting, for the stream to be `Send`
+            let event = event.map(|value| value.to_data_value());
+            let env = ExecuteEnv::new(self, env.fragments.clone(), env.variables.clone());
+            let mut context = context.clone();
+            let field = field.clone();
+            let insert_key = insert_key.clone();
+            async move {
+                let path = vec![PathSegment::Field(insert_key.clone())];
+                let event_result = match event {
+                    Ok(event) => {
+                        subscription_api
+                            .execute_event(&mut context, &env, &field, event, &path)
+                            .await
+                    }
+                    Err(err) => Err(err.into()),
+                };
+                let data = env
+                    .complete_value(event_result, nullable, path, field.position)
+                    .map(|value| DataValue::Object(IndexMap::from([(insert_key, value)])));
+                env.into_response(data).unwrap_or_else(Response::from_error)
+            }
+        });
+        Ok(responses.boxed())
+    }
+
+    /// Check the request variables against the operation's variable definitions,
+    /// and apply the default values of the variables not provided
+    pub(crate) fn coerce_variables(
+        &self,
+        definitions: &[VariableDefinition],
+        mut variables: QLVariables,
+    ) -> Result<QLVariables> {
+        let mut result = QLVariables::new();
+        for def in definitions {
+            let input_type = self.get_input_type_by_ast(&def.var_type)?;
+            let value = match (variables.remove(&def.name), &def.default_value) {
+                (Some(value), _) => value,
+                (None, Some(default_value)) => DataValue::from(default_value.clone()),
+                (None, None) => {
+                    if let Type::NonNullType(_) = def.var_type {
+                        return Err(Error::MissingVariable(
+                            def.name.clone(),
+                            def.var_type.to_string(),
+                        ));
+                    }
+                    continue;
                 }
-                Selection::FragmentSpread(_) => {
-                    return Err(Error::UnSupportedYetError(
-                        "'FragmentSpread' in query(selection sets)".to_string(),
-                    ))
+            };
+            // the value is kept as provided, it is coerced with the argument using it,
+            // so that the custom scalars parse it only once
+            input_type
+                .coerce(value.clone())
+                .map_err(|err| Error::InvalidVariableValue(def.name.clone(), err.to_string()))?;
+            result.insert(def.name.clone(), value);
+        }
+        Ok(result)
+    }
+
+    /// Find the input type of a variable type declared in request
+    pub(crate) fn get_input_type_by_ast(&self, ast_type: &Type) -> Result<InputFieldType> {
+        match ast_type {
+            Type::NonNullType(t) => Ok(InputFieldType::NonNullType(Box::new(
+                self.get_input_type_by_ast(t)?,
+            ))),
+            Type::ListType(t) => Ok(InputFieldType::List(Box::new(
+                self.get_input_type_by_ast(t)?,
+            ))),
+            Type::NamedType(name) => {
+                if let Some(t) = StaticType::from_name(name) {
+                    return Ok(InputFieldType::StaticType(t));
                 }
-                Selection::InlineFragment(_) => {
-                    return Err(Error::UnSupportedYetError(
-                        "'InlineFragment' in query(selection sets)".to_string(),
-                    ))
+                if let Some(rc) = self.enums.get(name) {
+                    return Ok(InputFieldType::ReferenceEnum(rc.clone()));
                 }
+                if let Some(rc) = self.scalars.get(name) {
+                    return Ok(InputFieldType::ReferenceScalar(rc.clone()));
+                }
+                if let Some(rc) = self.inputs.get(name) {
+                    return Ok(InputFieldType::ReferenceInput(Arc::downgrade(rc)));
+                }
+                // types which are defined inline the arguments
+                let query_args = self.queries.values().flat_map(|q| q.arguments.values());
+                let mutation_args = self
+                    .mutations
+                    .iter()
+                    .flat_map(|m| m.values())
+                    .flat_map(|m| m.arguments.values());
+                let subscription_args = self
+                    .subscritions
+                    .iter()
+                    .flat_map(|s| s.values())
+                    .flat_map(|s| s.arguments.values());
+                query_args
+                    .chain(mutation_args)
+                    .chain(subscription_args)
+                    .find_map(|arg| find_inline_input_type(&arg.field_type, name))
+                    .or_else(|| {
+                        self.objects.values().find_map(|object| {
+                            let object = read_lock(object);
+                            let found = object
+                                .fields
+                                .values()
+                                .flat_map(|f| f.arguments.values())
+                                .find_map(|arg| find_inline_input_type(&arg.field_type, name));
+                            found
+                        })
+                    })
+                    .ok_or_else(|| Error::NotFoundError(format!("Type {}", name)))
             }
         }
+    }
+
+    /// Root query fields are executed one by one in request order, sharing the context
+    pub(crate) fn execute_selection_set(
+        &self,
+        context: &mut QLContext,
+        selections: &[Selection],
+        env: &ExecuteEnv<'_>,
+    ) -> ExecuteResult<DataValue> {
+        let fields = env.group_fields(selections, QUERY_TYPE_NAME)?;
+        let results = fields
+            .into_iter()
+            .map(|field| {
+                let insert_key = response_key(&field).to_string();
+                let path = vec![PathSegment::Field(insert_key.clone())];
+                let (query_result, nullable) = match self.execute_query_meta_field(&field, env) {
+                    Some(meta_result) => meta_result,
+                    None => match self.get_query(&field.name) {
+                        Ok(query) => (
+                            query.execute(context, env, &field, &path),
+                            query.field_type.is_nullable(),
+                        ),
+                        Err(err) => (Err(err.into()), true),
+                    },
+                };
+                env.complete_value(query_result, nullable, path, field.position)
+                    .map(|value| (insert_key, value))
+            })
+            .collect::<Vec<_>>();
+        let mut result = IndexMap::<String, DataValue>::new();
+        for query_result in results {
+            let (insert_key, value) = query_result?;
+            result.insert(insert_key, value);
+        }
         Ok(DataValue::Object(result))
     }
 
-    pub(crate) fn execute_query(
+    /// Root query fields are executed concurrently, each with its own copy of the context
+    pub(crate) async fn execute_selection_set_async(
         &self,
-        context: QLContext,
-        query: AstQuery,
-        fragments: &HashMap<String, FragmentDefinition>,
-    ) -> Result<DataValue> {
-        self.execute_selection_set(context, query.selection_set, fragments)
+        context: &QLContext,
+        selections: &[Selection],
+        env: &ExecuteEnv<'_>,
+    ) -> ExecuteResult<DataValue> {
+        let fields = env.group_fields(selections, QUERY_TYPE_NAME)?;
+        let results = fields.into_iter().map(|field| {
+            let mut context = context.clone();
+            async move {
+                let insert_key = response_key(&field).to_string();
+                let path = vec![PathSegment::Field(insert_key.clone())];
+                let (query_result, nullable) = match self.execute_query_meta_field(&field, env) {
+                    Some(meta_result) => meta_result,
+                    None => match self.get_query(&field.name) {
+                        Ok(query) => (
+                            query.execute_async(&mut context, env, &field, &path).await,
+                            query.field_type.is_nullable(),
+                        ),
+                        Err(err) => (Err(err.into()), true),
+                    },
+                };
+                env.complete_value(query_result, nullable, path, field.position)
+                    .map(|value| (insert_key, value))
+            }
+        });
+        let mut result = IndexMap::<String, DataValue>::new();
+        for query_result in join_all(results).await {
+            let (insert_key, value) = query_result?;
+            result.insert(insert_key, value);
+        }
+        Ok(DataValue::Object(result))
+    }
+
+    /// execute a meta field of the query root, along with whether its type is nullable,
+    /// other fields are executed by their query api
+    fn execute_query_meta_field(
+        &self,
+        field: &AstField,
+        env: &ExecuteEnv<'_>,
+    ) -> Option<(ExecuteResult<DataValue>, bool)> {
+        match field.name.as_str() {
+            "__typename" => Some((Ok(DataValue::String(QUERY_TYPE_NAME.to_string())), false)),
+            "__schema" => Some((
+                Introspector::new(self.meta(), env)
+                    .execute_schema(field)
+                    .map_err(ExecuteError::from),
+                false,
+            )),
+            "__type" => Some((
+                Introspector::new(self.meta(), env)
+                    .execute_type(field)
+                    .map_err(ExecuteError::from),
+                true,
+            )),
+            _ => None,
+        }
     }
 
+    fn get_query(&self, name: &str) -> Result<&Query> {
+        self.queries
+            .get(name)
+            .ok_or_else(|| Error::NotFoundError(format!("Query api {}", name)))
+    }
+
+    fn get_mutation(&self, name: &str) -> Result<&Mutation> {
+        self.mutations
+            .as_ref()
+            .ok_or(Error::MutationSchemaNotDefined)
+            .and_then(|mutations| {
+                mutations
+                    .get(name)
+                    .ok_or_else(|| Error::NotFoundError(format!("Mutation api {}", name)))
+            })
+    }
+
+    /// Root mutation fields are executed one by one in request order, sharing the context
     pub(crate) fn execute_mutation(
         &self,
-        mut context: QLContext,
-        mutation: AstMutation,
-        fragments: &HashMap<String, FragmentDefinition>,
-    ) -> Result<DataValue> {
-        let mut result = BTreeMap::<String, DataValue>::new();
-        for set in mutation.selection_set.items {
-            match set {
-                Selection::Field(field) => {
-                    let name = field.name.clone();
-                    let insert_key = if let Some(alias) = field.alias.clone() {
-                        alias
-                    } else {
-                        name.clone()
-                    };
-                    let mutation_result = self
-                        .mutations
-                        .as_ref()
-                        .ok_or(Error::MutationSchemaNotDefined)?
-                        .get(&name)
-                        .ok_or(Error::NotFoundError(format!("Mutation api {}", &name)))?
-                        .execute(&mut context, fragments, field)?;
-                    result.insert(insert_key, mutation_result);
-                }
-                Selection::FragmentSpread(_) => {
-                    return Err(Error::UnSupportedYetError(
-                        "'FragmentSpread' in mutation(selection sets)".to_string(),
-                    ))
-                }
-                Selection::InlineFragment(_) => {
-                    return Err(Error::UnSupportedYetError(
-                        "'InlineFragment' in mutation(selection sets)".to_string(),
-                    ))
-                }
+        context: &mut QLContext,
+        selections: &[Selection],
+        env: &ExecuteEnv<'_>,
+    ) -> ExecuteResult<DataValue> {
+        let fields = env.group_fields(selections, MUTATION_TYPE_NAME)?;
+        let mut result = IndexMap::<String, DataValue>::new();
+        for field in fields {
+            let insert_key = response_key(&field).to_string();
+            if field.name == "__typename" {
+                result.insert(
+                    insert_key,
+                    DataValue::String(MUTATION_TYPE_NAME.to_string()),
+                );
+                continue;
+            }
+            let path = vec![PathSegment::Field(insert_key.clone())];
+            let (mutation_result, nullable) = match self.get_mutation(&field.name) {
+                Ok(mutation) => (
+                    mutation.execute(context, env, &field, &path),
+                    mutation.field_type.is_nullable(),
+                ),
+                Err(err) => (Err(err.into()), true),
+            };
+            let mutation_result =
+                env.complete_value(mutation_result, nullable, path, field.position)?;
+            result.insert(insert_key, mutation_result);
+        }
+        Ok(DataValue::Object(result))
+    }
+
+    /// Root mutation fields are awaited one by one in request order, sharing the context
+    pub(crate) async fn execute_mutation_async(
+        &self,
+        context: &mut QLContext,
+        selections: &[Selection],
+        env: &ExecuteEnv<'_>,
+    ) -> ExecuteResult<DataValue> {
+        let fields = env.group_fields(selections, MUTATION_TYPE_NAME)?;
+        let mut result = IndexMap::<String, DataValue>::new();
+        for field in fields {
+            let insert_key = response_key(&field).to_string();
+            if field.name == "__typename" {
+                result.insert(
+                    insert_key,
+                    DataValue::String(MUTATION_TYPE_NAME.to_string()),
+                );
+                continue;
             }
+            let path = vec![PathSegment::Field(insert_key.clone())];
+            let (mutation_result, nullable) = match self.get_mutation(&field.name) {
+                Ok(mutation) => (
+                    mutation.execute_async(context, env, &field, &path).await,
+                    mutation.field_type.is_nullable(),
+                ),
+                Err(err) => (Err(err.into()), true),
+            };
+            let mutation_result =
+                env.complete_value(mutation_result, nullable, path, field.position)?;
+            result.insert(insert_key, mutation_result);
         }
-        return Ok(DataValue::Object(result));
+        Ok(DataValue::Object(result))
     }
 }
 
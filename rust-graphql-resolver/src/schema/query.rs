@@ -1,13 +1,15 @@
 use std::{collections::HashMap, fmt::Debug};
 
-use crate::{error::Result, value::DataValue};
+use crate::{error::Result, response::PathSegment, value::DataValue};
 
 use super::{
     field::{ArgumentMap, FieldType},
-    resolve::{ApiResolveFunc, ArgumentValueMap, QLApiParam, QLContext},
+    meta::QUERY_TYPE_NAME,
+    resolve::{ApiResolver, ArgumentValueMap, QLApiParam, QLContext},
+    ExecuteEnv, ExecuteResult,
 };
 
-use gurkle_parser::query::{self as ast, FragmentDefinition};
+use gurkle_parser::query as ast;
 
 /// QueryMap
 pub type QueryMap = HashMap<String, Query>;
@@ -18,23 +20,65 @@ pub struct Query {
     pub field_type: FieldType,
     pub arguments: ArgumentMap,
     pub description: String,
-    pub resolve: Box<dyn ApiResolveFunc>,
+    pub resolve: ApiResolver,
 }
 
 impl Query {
-    pub(crate) fn execute<'a, 'b>(
+    pub(crate) fn execute(
         &self,
-        context: &'a mut QLContext,
-        fragments: &'b HashMap<String, FragmentDefinition>,
-        field: ast::Field,
-    ) -> Result<DataValue> {
-        let parameter = QLApiParam {
-            arguments: ArgumentValueMap::from(field.arguments),
-            selection_sets: field.selection_set.items,
-        };
+        context: &mut QLContext,
+        env: &ExecuteEnv<'_>,
+        field: &ast::Field,
+        path: &[PathSegment],
+    ) -> ExecuteResult<DataValue> {
+        let parameter = self.parameter(env, field)?;
         let resolve_result = self.resolve.call(context, &parameter)?.to_data_value();
+        self.field_type.execute(
+            context,
+            env,
+            &parameter,
+            resolve_result,
+            path,
+            field.position,
+        )
+    }
+
+    pub(crate) async fn execute_async(
+        &self,
+        context: &mut QLContext,
+        env: &ExecuteEnv<'_>,
+        field: &ast::Field,
+        path: &[PathSegment],
+    ) -> ExecuteResult<DataValue> {
+        let parameter = self.parameter(env, field)?;
+        let resolve_result = self
+            .resolve
+            .call_async(context, &parameter)
+            .await?
+            .to_data_value();
         self.field_type
-            .execute(context, fragments, &parameter, resolve_result)
+            .execute_async(
+                context,
+                env,
+                &parameter,
+                resolve_result,
+                path,
+                field.position,
+            )
+            .await
+    }
+
+    /// the coerced arguments and the selections of the request field
+    fn parameter(&self, env: &ExecuteEnv<'_>, field: &ast::Field) -> Result<QLApiParam> {
+        Ok(QLApiParam {
+            arguments: ArgumentValueMap::coerce(
+                field.arguments.to_owned(),
+                &env.variables,
+                &self.arguments,
+                &format!("{}.{}", QUERY_TYPE_NAME, field.name),
+            )?,
+            selection_sets: field.selection_set.items.clone(),
+        })
     }
 }
 
@@ -1,13 +1,20 @@
-use std::collections::HashMap;
+use std::{collections::HashMap, future::Future};
 
 use dyn_clone::{clone_trait_object, DynClone};
+use futures::{
+    executor::block_on,
+    future::{BoxFuture, FutureExt},
+    stream::{BoxStream, Stream, StreamExt},
+};
 use gurkle_parser::query::{Selection, Value as ParserValue};
 
 use crate::{
     error::{Error, Result},
-    value::{DataValue, ToDataValue},
+    value::{at_path, DataValue, FromDataValue, ToDataValue},
 };
 
+use super::field::ArgumentMap;
+
 pub type BoxedValue = Box<dyn ToDataValue>;
 
 /// ApiResolveFunc
@@ -15,24 +22,16 @@ pub type BoxedValue = Box<dyn ToDataValue>;
 /// This is a function (closure) to resolve graphql api restule data
 /// * context: storage and transfer key-value through invoking nested
 /// * parameter: arguments and selection_sets from graphql request
-pub trait ApiResolveFunc: DynClone {
-    fn call<'a, 'b>(
-        &self,
-        context: &'a mut QLContext,
-        parameter: &'b QLApiParam,
-    ) -> Result<BoxedValue>;
+pub trait ApiResolveFunc: DynClone + Send + Sync {
+    fn call(&self, context: &mut QLContext, parameter: &QLApiParam) -> Result<BoxedValue>;
 }
 clone_trait_object!(ApiResolveFunc);
 
 impl<F> ApiResolveFunc for F
 where
-    F: Fn(&'_ mut QLContext, &'_ QLApiParam) -> Result<BoxedValue> + Clone,
+    F: Fn(&'_ mut QLContext, &'_ QLApiParam) -> Result<BoxedValue> + Clone + Send + Sync,
 {
-    fn call<'a, 'b>(
-        &self,
-        context: &'a mut QLContext,
-        parameter: &'b QLApiParam,
-    ) -> Result<BoxedValue> {
+    fn call(&self, context: &mut QLContext, parameter: &QLApiParam) -> Result<BoxedValue> {
         self(context, parameter)
     }
 }
@@ -42,18 +41,206 @@ where
 pub struct DefaultApiResolveFunc;
 
 impl ApiResolveFunc for DefaultApiResolveFunc {
-    fn call<'a, 'b>(
+    fn call(&self, _context: &mut QLContext, _parameter: &QLApiParam) -> Result<BoxedValue> {
+        Err(Error::DefaultResolveError)
+    }
+}
+
+/// AsyncApiResolveFunc
+///
+/// The async counterpart of `ApiResolveFunc`, the returned future is awaited by `execute_async`
+/// and blocked on by `execute`, it is `Send` so that execution can move between threads
+/// * context: a copy of the request key-values, writes to it are not seen by other resolvers
+/// * parameter: arguments and selection_sets from graphql request
+///
+/// Closures get a copy of the context and parameter, so the future doesn't borrow them
+pub trait AsyncApiResolveFunc: DynClone + Send + Sync {
+    fn call(
         &self,
-        _context: &'a mut QLContext,
-        _parameter: &'b QLApiParam,
+        context: &mut QLContext,
+        parameter: &QLApiParam,
+    ) -> BoxFuture<'static, Result<BoxedValue>>;
+}
+clone_trait_object!(AsyncApiResolveFunc);
+
+impl<F, Fut> AsyncApiResolveFunc for F
+where
+    F: Fn(QLContext, QLApiParam) -> Fut + Clone + Send + Sync,
+    Fut: Future<Output = Result<BoxedValue>> + Send + 'static,
+{
+    fn call(
+        &self,
+        context: &mut QLContext,
+        parameter: &QLApiParam,
+    ) -> BoxFuture<'static, Result<BoxedValue>> {
+        self(context.clone(), parameter.clone()).boxed()
+    }
+}
+
+/// ApiResolver
+///
+/// The resolve function of a query or mutation api, plain or async
+#[derive(Clone)]
+pub enum ApiResolver {
+    Sync(Box<dyn ApiResolveFunc>),
+    Async(Box<dyn AsyncApiResolveFunc>),
+}
+
+impl ApiResolver {
+    /// Call the resolve function, the future of an async one is blocked on
+    pub(crate) fn call(
+        &self,
+        context: &mut QLContext,
+        parameter: &QLApiParam,
+    ) -> Result<BoxedValue> {
+        match self {
+            ApiResolver::Sync(resolve) => resolve.call(context, parameter),
+            ApiResolver::Async(resolve) => block_on(resolve.call(context, parameter)),
+        }
+    }
+
+    pub(crate) async fn call_async(
+        &self,
+        context: &mut QLContext,
+        parameter: &QLApiParam,
     ) -> Result<BoxedValue> {
+        match self {
+            ApiResolver::Sync(resolve) => resolve.call(context, parameter),
+            ApiResolver::Async(resolve) => resolve.call(context, parameter).await,
+        }
+    }
+}
+
+/// SubscriptionResolveFunc
+///
+/// This is a function (closure) to create the stream of source events of a subscription api,
+/// every event is executed against the selection sets to produce a response
+/// * context: a copy of the request key-values, each event is executed with a new copy of it
+/// * parameter: arguments and selection_sets from graphql request
+///
+/// Closures get a copy of the context and parameter, so the stream doesn't borrow them
+pub trait SubscriptionResolveFunc: DynClone + Send + Sync {
+    fn call(
+        &self,
+        context: &mut QLContext,
+        parameter: &QLApiParam,
+    ) -> Result<BoxStream<'static, Result<BoxedValue>>>;
+}
+clone_trait_object!(SubscriptionResolveFunc);
+
+impl<F, S> SubscriptionResolveFunc for F
+where
+    F: Fn(QLContext, QLApiParam) -> Result<S> + Clone + Send + Sync,
+    S: Stream<Item = Result<BoxedValue>> + Send + 'static,
+{
+    fn call(
+        &self,
+        context: &mut QLContext,
+        parameter: &QLApiParam,
+    ) -> Result<BoxStream<'static, Result<BoxedValue>>> {
+        Ok(self(context.clone(), parameter.clone())?.boxed())
+    }
+}
+
+/// DefaultSubscriptionResolveFunc return Err(...)
+#[derive(Debug, Clone)]
+pub struct DefaultSubscriptionResolveFunc;
+
+impl SubscriptionResolveFunc for DefaultSubscriptionResolveFunc {
+    fn call(
+        &self,
+        _context: &mut QLContext,
+        _parameter: &QLApiParam,
+    ) -> Result<BoxStream<'static, Result<BoxedValue>>> {
         Err(Error::DefaultResolveError)
     }
 }
 
+/// TypeResolveFunc
+///
+/// This is a function (closure) for interface and union types to pick the concrete object type of a result
+/// * context: storage and transfer key-value through invoking nested
+/// * data: the data value resolved for the interface or union typed field
+///
+/// It returns the name of the concrete object type (`CustomType`)
+pub trait TypeResolveFunc: DynClone + Send + Sync {
+    fn call(&self, context: &QLContext, data: &DataValue) -> Result<String>;
+}
+clone_trait_object!(TypeResolveFunc);
+
+impl<F> TypeResolveFunc for F
+where
+    F: Fn(&'_ QLContext, &'_ DataValue) -> Result<String> + Clone + Send + Sync,
+{
+    fn call(&self, context: &QLContext, data: &DataValue) -> Result<String> {
+        self(context, data)
+    }
+}
+
+/// DefaultTypeResolveFunc picks the type named by the `__typename` key of the data
+#[derive(Debug, Clone)]
+pub struct DefaultTypeResolveFunc;
+
+impl TypeResolveFunc for DefaultTypeResolveFunc {
+    fn call(&self, _context: &QLContext, data: &DataValue) -> Result<String> {
+        match data {
+            DataValue::Object(map) => match map.get("__typename") {
+                Some(DataValue::String(name)) => Ok(name.clone()),
+                _ => Err(Error::NotFoundError("'__typename' in data".to_string())),
+            },
+            _ => Err(Error::DataTypeMisMatchError(
+                "Object(...)".to_string(),
+                data.get_type_name(),
+            )),
+        }
+    }
+}
+
+/// ScalarConvertFunc
+///
+/// This is a function (closure) for custom scalar types to convert a value
+/// * serialize: the resolved value into the value of response
+/// * parse: the request input value (literal or variable) into the value given to resolvers,
+///   an invalid input value results an error
+pub trait ScalarConvertFunc: DynClone + Send + Sync {
+    fn call(&self, value: DataValue) -> Result<DataValue>;
+}
+clone_trait_object!(ScalarConvertFunc);
+
+impl<F> ScalarConvertFunc for F
+where
+    F: Fn(DataValue) -> Result<DataValue> + Clone + Send + Sync,
+{
+    fn call(&self, value: DataValue) -> Result<DataValue> {
+        self(value)
+    }
+}
+
+/// DefaultScalarConvertFunc keeps the value as it is
+#[derive(Debug, Clone)]
+pub struct DefaultScalarConvertFunc;
+
+impl ScalarConvertFunc for DefaultScalarConvertFunc {
+    fn call(&self, value: DataValue) -> Result<DataValue> {
+        Ok(value)
+    }
+}
+
 /// QLContext
+///
+/// Request key-values given to the resolvers.
+/// `execute` runs the resolvers one by one in request order sharing one context,
+/// so a value written by a resolver is seen by every resolver after it.
+/// `execute_async` runs query fields, object fields and list items concurrently, each with its own copy,
+/// so a write is only seen by the resolvers nested in the same field; mutation fields share one context.
+/// Async resolvers always get a copy
 pub type QLContext = HashMap<String, DataValue>;
 
+/// QLVariables
+///
+/// Values of the `$variables` sent along with a graphql request, keyed by variable name (without `$`)
+pub type QLVariables = HashMap<String, DataValue>;
+
 /// QLApiParam
 #[derive(Clone, Debug)]
 pub struct QLApiParam {
@@ -66,18 +253,77 @@ pub struct QLApiParam {
 pub struct ArgumentValueMap(HashMap<String, DataValue>);
 
 impl ArgumentValueMap {
+    /// Build from request arguments, `$variable` references are substituted from `variables`
+    pub fn new(list: Vec<(String, ParserValue)>, variables: &QLVariables) -> Self {
+        let map = list
+            .into_iter()
+            .map(|(k, v)| (k, DataValue::from_parser_value(v, variables)))
+            .collect::<HashMap<String, DataValue>>();
+        Self(map)
+    }
+
+    /// Build from request arguments, checked and coerced against the declared `arguments`
+    /// of the field at `coordinate` (e.g. `Query.user`), before the resolver runs
+    pub fn coerce(
+        list: Vec<(String, ParserValue)>,
+        variables: &QLVariables,
+        arguments: &ArgumentMap,
+        coordinate: &str,
+    ) -> Result<Self> {
+        // an argument of a variable which is not provided, is not provided either
+        let mut provided = list
+            .into_iter()
+            .filter(
+                |(_, v)| !matches!(v, ParserValue::Variable(name) if !variables.contains_key(name)),
+            )
+            .collect::<HashMap<String, ParserValue>>();
+        if let Some(unknown) = provided.keys().find(|k| !arguments.contains_key(*k)) {
+            return Err(Error::UnknownArgument(
+                coordinate.to_string(),
+                unknown.clone(),
+            ));
+        }
+        let mut map = HashMap::new();
+        for (name, argument) in arguments.iter() {
+            let value = provided
+                .remove(name)
+                .map(|value| {
+                    argument.field_type.check_literal(&value)?;
+                    Ok(DataValue::from_parser_value(value, variables))
+                })
+                .transpose()
+                .map_err(|err: Error| Error::InvalidArgumentValue(name.clone(), err.to_string()))?;
+            if value.is_none() && argument.is_required() {
+                return Err(Error::MissingArgument(
+                    coordinate.to_string(),
+                    name.clone(),
+                    argument.field_type.to_string(),
+                ));
+            }
+            let value = argument
+                .coerce(value)
+                .map_err(|err| Error::InvalidArgumentValue(name.clone(), err.to_string()))?;
+            if let Some(value) = value {
+                map.insert(name.clone(), value);
+            }
+        }
+        Ok(Self(map))
+    }
+
     pub fn get(&self, key: &String) -> Option<&DataValue> {
         self.0.get(key)
     }
+
+    /// Convert the argument `name` into `T`, a missing argument is taken as `DataValue::Null`
+    pub fn parse<T: FromDataValue>(&self, name: &str) -> Result<T> {
+        T::from_data_value(self.0.get(name).unwrap_or(&DataValue::Null))
+            .map_err(|err| at_path(name, err))
+    }
 }
 
 impl From<Vec<(String, ParserValue)>> for ArgumentValueMap {
     fn from(list: Vec<(String, ParserValue)>) -> Self {
-        let map = list
-            .into_iter()
-            .map(|(k, v)| (k, DataValue::from(v)))
-            .collect::<HashMap<String, DataValue>>();
-        Self(map)
+        Self::new(list, &QLVariables::default())
     }
 }
 
@@ -87,23 +333,31 @@ impl From<Vec<(String, ParserValue)>> for ArgumentValueMap {
 /// * context: storage and transfer key-value through invoking nested
 /// * source: parent data value result, you can get the data from last layer, but only one layer
 /// * parameter: arguments and selection_sets from graphql request
-pub trait FieldResolveFunc: DynClone {
-    fn call<'a, 'b>(
+pub trait FieldResolveFunc: DynClone + Send + Sync {
+    fn call<'b>(
         &self,
-        context: &'a mut QLContext,
+        context: &mut QLContext,
         source: &'b DataValue,
         parameter: &'b QLApiParam,
     ) -> Result<BoxedValue>;
+
+    /// Only `DefaultFieldResolveFunc` is the default, its fields take their value from the source data
+    fn is_default(&self) -> bool {
+        false
+    }
 }
 clone_trait_object!(FieldResolveFunc);
 
 impl<F> FieldResolveFunc for F
 where
-    F: Fn(&'_ mut QLContext, &'_ DataValue, &'_ QLApiParam) -> Result<BoxedValue> + Clone,
+    F: Fn(&'_ mut QLContext, &'_ DataValue, &'_ QLApiParam) -> Result<BoxedValue>
+        + Clone
+        + Send
+        + Sync,
 {
-    fn call<'a, 'b>(
+    fn call<'b>(
         &self,
-        context: &'a mut QLContext,
+        context: &mut QLContext,
         source: &'b DataValue,
         parameter: &'b QLApiParam,
     ) -> Result<BoxedValue> {
@@ -116,12 +370,91 @@ where
 pub struct DefaultFieldResolveFunc;
 
 impl FieldResolveFunc for DefaultFieldResolveFunc {
-    fn call<'a, 'b>(
+    fn call<'b>(
         &self,
-        _context: &'a mut QLContext,
+        _context: &mut QLContext,
         _source: &'b DataValue,
         _parameter: &'b QLApiParam,
     ) -> Result<BoxedValue> {
         Err(Error::DefaultResolveError)
     }
+
+    fn is_default(&self) -> bool {
+        true
+    }
+}
+
+/// AsyncFieldResolveFunc
+///
+/// The async counterpart of `FieldResolveFunc`, the returned future is awaited by `execute_async`
+/// and blocked on by `execute`, it is `Send` so that execution can move between threads
+/// * context: a copy of the request key-values, writes to it are not seen by other resolvers
+/// * source: parent data value result, you can get the data from last layer, but only one layer
+/// * parameter: arguments and selection_sets from graphql request
+///
+/// Closures get a copy of the context, source and parameter, so the future doesn't borrow them
+pub trait AsyncFieldResolveFunc: DynClone + Send + Sync {
+    fn call(
+        &self,
+        context: &mut QLContext,
+        source: &DataValue,
+        parameter: &QLApiParam,
+    ) -> BoxFuture<'static, Result<BoxedValue>>;
+}
+clone_trait_object!(AsyncFieldResolveFunc);
+
+impl<F, Fut> AsyncFieldResolveFunc for F
+where
+    F: Fn(QLContext, DataValue, QLApiParam) -> Fut + Clone + Send + Sync,
+    Fut: Future<Output = Result<BoxedValue>> + Send + 'static,
+{
+    fn call(
+        &self,
+        context: &mut QLContext,
+        source: &DataValue,
+        parameter: &QLApiParam,
+    ) -> BoxFuture<'static, Result<BoxedValue>> {
+        self(context.clone(), source.clone(), parameter.clone()).boxed()
+    }
+}
+
+/// FieldResolver
+///
+/// The resolve function of an object field, plain or async
+#[derive(Clone)]
+pub enum FieldResolver {
+    Sync(Box<dyn FieldResolveFunc>),
+    Async(Box<dyn AsyncFieldResolveFunc>),
+}
+
+impl FieldResolver {
+    /// Call the resolve function, the future of an async one is blocked on
+    pub(crate) fn call(
+        &self,
+        context: &mut QLContext,
+        source: &DataValue,
+        parameter: &QLApiParam,
+    ) -> Result<BoxedValue> {
+        match self {
+            FieldResolver::Sync(resolve) => resolve.call(context, source, parameter),
+            FieldResolver::Async(resolve) => block_on(resolve.call(context, source, parameter)),
+        }
+    }
+
+    pub(crate) async fn call_async(
+        &self,
+        context: &mut QLContext,
+        source: &DataValue,
+        parameter: &QLApiParam,
+    ) -> Result<BoxedValue> {
+        match self {
+            FieldResolver::Sync(resolve) => resolve.call(context, source, parameter),
+            FieldResolver::Async(resolve) => resolve.call(context, source, parameter).await,
+        }
+    }
+
+    /// fields without resolve function take their value from the source data
+    pub(crate) fn is_default(&self) -> bool {
+        matches!(self, FieldResolver::Sync(resolve) if resolve.is_default())
+    }
 }
@@ -0,0 +1,166 @@
+use std::fmt::Write;
+
+use super::meta::{
+    FieldMeta, InputValueMeta, SchemaMeta, TypeKind, TypeMeta, MUTATION_TYPE_NAME, QUERY_TYPE_NAME,
+    SUBSCRIPTION_TYPE_NAME,
+};
+
+/// the scalars every graphql service has, which are not printed
+const SPECIFIED_SCALARS: [&str; 5] = ["ID", "String", "Int", "Float", "Boolean"];
+
+/// Print the types of the schema as graphql SDL, the root operation types first,
+/// then the other types in name order
+pub(crate) fn print_schema(meta: &SchemaMeta) -> String {
+    let roots = [QUERY_TYPE_NAME, MUTATION_TYPE_NAME, SUBSCRIPTION_TYPE_NAME];
+    let root_types = roots.iter().filter_map(|name| meta.get_type(name));
+    let other_types = meta
+        .types
+        .values()
+        .filter(|t| !roots.contains(&t.name.as_str()))
+        .filter(|t| !t.name.starts_with("__"))
+        .filter(|t| t.kind != TypeKind::Scalar || is_printed_scalar(meta, &t.name));
+    root_types
+        .chain(other_types)
+        .map(print_type)
+        .collect::<Vec<_>>()
+        .join("\n\n")
+        + "\n"
+}
+
+/// custom scalars, and the built-in `DateTime` when it is used
+fn is_printed_scalar(meta: &SchemaMeta, name: &str) -> bool {
+    if SPECIFIED_SCALARS.contains(&name) {
+        return false;
+    }
+    if name != "DateTime" {
+        return true;
+    }
+    meta.types
+        .values()
+        .filter(|t| !t.name.starts_with("__"))
+        .any(|t| {
+            let field_refs = t.fields.iter().flat_map(|f| {
+                std::iter::once(&f.type_ref).chain(f.args.iter().map(|arg| &arg.type_ref))
+            });
+            let input_refs = t.input_fields.iter().map(|f| &f.type_ref);
+            field_refs
+                .chain(input_refs)
+                .any(|type_ref| type_ref.named_type() == name)
+        })
+}
+
+fn print_type(type_meta: &TypeMeta) -> String {
+    let mut sdl = print_description(&type_meta.description, "");
+    match type_meta.kind {
+        TypeKind::Scalar => {
+            write!(sdl, "scalar {}", type_meta.name).unwrap();
+            if let Some(url) = &type_meta.specified_by_url {
+                write!(sdl, " @specifiedBy(url: {})", print_string(url)).unwrap();
+            }
+        }
+        TypeKind::Object | TypeKind::Interface => {
+            let keyword = match type_meta.kind {
+                TypeKind::Object => "type",
+                _ => "interface",
+            };
+            write!(sdl, "{} {}", keyword, type_meta.name).unwrap();
+            if !type_meta.interfaces.is_empty() {
+                write!(sdl, " implements {}", type_meta.interfaces.join(" & ")).unwrap();
+            }
+            let fields = type_meta.fields.iter().map(print_field).collect::<Vec<_>>();
+            sdl += &print_block(&fields);
+        }
+        TypeKind::Union => {
+            write!(sdl, "union {}", type_meta.name).unwrap();
+            if !type_meta.possible_types.is_empty() {
+                write!(sdl, " = {}", type_meta.possible_types.join(" | ")).unwrap();
+            }
+        }
+        TypeKind::Enum => {
+            write!(sdl, "enum {}", type_meta.name).unwrap();
+            let values = type_meta
+                .enum_values
+                .iter()
+                .map(|v| print_description(&v.description, "  ") + "  " + &v.value)
+                .collect::<Vec<_>>();
+            sdl += &print_block(&values);
+        }
+        TypeKind::InputObject => {
+            write!(sdl, "input {}", type_meta.name).unwrap();
+            let fields = type_meta
+                .input_fields
+                .iter()
+                .map(|f| print_description(&f.description, "  ") + "  " + &print_input_value(f))
+                .collect::<Vec<_>>();
+            sdl += &print_block(&fields);
+        }
+        TypeKind::List | TypeKind::NonNull => {}
+    }
+    sdl
+}
+
+/// the fields or values of a type in braces, a type without any is printed without braces
+fn print_block(items: &[String]) -> String {
+    if items.is_empty() {
+        String::default()
+    } else {
+        format!(" {{\n{}\n}}", items.join("\n"))
+    }
+}
+
+fn print_field(field: &FieldMeta) -> String {
+    let mut sdl = print_description(&field.description, "  ");
+    write!(sdl, "  {}", field.name).unwrap();
+    if field.args.iter().any(|arg| !arg.description.is_empty()) {
+        // arguments with descriptions are printed one per line
+        let args = field
+            .args
+            .iter()
+            .map(|arg| {
+                print_description(&arg.description, "    ") + "    " + &print_input_value(arg)
+            })
+            .collect::<Vec<_>>();
+        write!(sdl, "(\n{}\n  )", args.join("\n")).unwrap();
+    } else if !field.args.is_empty() {
+        let args = field.args.iter().map(print_input_value).collect::<Vec<_>>();
+        write!(sdl, "({})", args.join(", ")).unwrap();
+    }
+    write!(sdl, ": {}", field.type_ref).unwrap();
+    sdl
+}
+
+fn print_input_value(input_value: &InputValueMeta) -> String {
+    match &input_value.default_value {
+        Some(default) => format!(
+            "{}: {} = {}",
+            input_value.name, input_value.type_ref, default
+        ),
+        None => format!("{}: {}", input_value.name, input_value.type_ref),
+    }
+}
+
+/// a description as a block string on the lines before the definition, nothing for an empty one
+fn print_description(description: &str, indent: &str) -> String {
+    if description.is_empty() {
+        return String::default();
+    }
+    let escaped = description.replace("\"\"\"", "\\\"\"\"");
+    if !escaped.contains('\n') && !escaped.ends_with('"') {
+        return format!("{}\"\"\"{}\"\"\"\n", indent, escaped);
+    }
+    let lines = escaped
+        .lines()
+        .map(|line| {
+            if line.is_empty() {
+                String::default()
+            } else {
+                format!("{}{}", indent, line)
+            }
+        })
+        .collect::<Vec<_>>();
+    format!("{}\"\"\"\n{}\n{}\"\"\"\n", indent, lines.join("\n"), indent)
+}
+
+fn print_string(s: &str) -> String {
+    serde_json::to_string(s).unwrap_or_default()
+}
@@ -0,0 +1,80 @@
+use std::{collections::HashMap, fmt::Debug};
+
+use futures::stream::BoxStream;
+use gurkle_parser::query as ast;
+
+use crate::{error::Result, response::PathSegment, value::DataValue};
+
+use super::{
+    field::{ArgumentMap, FieldType},
+    meta::SUBSCRIPTION_TYPE_NAME,
+    resolve::{ArgumentValueMap, BoxedValue, QLApiParam, QLContext, SubscriptionResolveFunc},
+    ExecuteEnv, ExecuteResult,
+};
+
+/// SubscriptionMap
+pub type SubscriptionMap = HashMap<String, Subscription>;
+
+/// Subscription
+#[derive(Clone)]
+pub struct Subscription {
+    pub field_type: FieldType,
+    pub arguments: ArgumentMap,
+    pub description: String,
+    pub resolve: Box<dyn SubscriptionResolveFunc>,
+}
+
+impl Subscription {
+    fn parameter(&self, env: &ExecuteEnv<'_>, field: &ast::Field) -> Result<QLApiParam> {
+        Ok(QLApiParam {
+            arguments: ArgumentValueMap::coerce(
+                field.arguments.to_owned(),
+                &env.variables,
+                &self.arguments,
+                &format!("{}.{}", SUBSCRIPTION_TYPE_NAME, field.name),
+            )?,
+            selection_sets: field.selection_set.items.clone(),
+        })
+    }
+
+    /// Create the stream of source events for the request field
+    pub(crate) fn subscribe(
+        &self,
+        context: &mut QLContext,
+        env: &ExecuteEnv<'_>,
+        field: &ast::Field,
+    ) -> Result<BoxStream<'static, Result<BoxedValue>>> {
+        self.resolve.call(context, &self.parameter(env, field)?)
+    }
+
+    /// Execute the selection sets of the request field against one source event
+    pub(crate) async fn execute_event(
+        &self,
+        context: &mut QLContext,
+        env: &ExecuteEnv<'_>,
+        field: &ast::Field,
+        event: DataValue,
+        path: &[PathSegment],
+    ) -> ExecuteResult<DataValue> {
+        self.field_type
+            .execute_async(
+                context,
+                env,
+                &self.parameter(env, field)?,
+                event,
+                path,
+                field.position,
+            )
+            .await
+    }
+}
+
+impl Debug for Subscription {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "Subscription{{field_type: {:?}, description: {}, resolve: <SubscriptionResolveFunc>}}",
+            self.field_type, self.description
+        )
+    }
+}
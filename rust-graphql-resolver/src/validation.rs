@@ -0,0 +1,623 @@
+use std::collections::{HashMap, HashSet};
+
+use gurkle_parser::{
+    query::{
+        Definition, Directive, Document, Field as AstField, FragmentDefinition, FragmentSpread,
+        OperationDefinition, Selection, SelectionSet, Type, TypeCondition, Value as ParserValue,
+        VariableDefinition,
+    },
+    Pos,
+};
+
+use crate::{
+    error::Error,
+    response::{Location, QLError},
+    schema::{
+        meta::{
+            FieldMeta, InputValueMeta, SchemaMeta, TypeKind, TypeRef, MUTATION_TYPE_NAME,
+            QUERY_TYPE_NAME, SUBSCRIPTION_TYPE_NAME,
+        },
+        response_key, Schema,
+    },
+    value::DataValue,
+};
+
+/// OperationRef
+///
+/// The parts of an operation definition checked by validation
+struct OperationRef<'d> {
+    name: Option<&'d str>,
+    position: Pos,
+    root_type: &'static str,
+    variable_definitions: &'d [VariableDefinition],
+    selection_set: &'d SelectionSet,
+}
+
+impl<'d> From<&'d OperationDefinition> for OperationRef<'d> {
+    fn from(op: &'d OperationDefinition) -> Self {
+        match op {
+            OperationDefinition::SelectionSet(selection_set) => Self {
+                name: None,
+                position: selection_set.span.0,
+                root_type: QUERY_TYPE_NAME,
+                variable_definitions: &[],
+                selection_set,
+            },
+            OperationDefinition::Query(query) => Self {
+                name: query.name.as_deref(),
+                position: query.position,
+                root_type: QUERY_TYPE_NAME,
+                variable_definitions: &query.variable_definitions,
+                selection_set: &query.selection_set,
+            },
+            OperationDefinition::Mutation(mutation) => Self {
+                name: mutation.name.as_deref(),
+                position: mutation.position,
+                root_type: MUTATION_TYPE_NAME,
+                variable_definitions: &mutation.variable_definitions,
+                selection_set: &mutation.selection_set,
+            },
+            OperationDefinition::Subscription(sub) => Self {
+                name: sub.name.as_deref(),
+                position: sub.position,
+                root_type: SUBSCRIPTION_TYPE_NAME,
+                variable_definitions: &sub.variable_definitions,
+                selection_set: &sub.selection_set,
+            },
+        }
+    }
+}
+
+/// Validate a request document against the schema, before any resolver runs.
+/// All violations are returned with their locations, the document is valid when there is none
+pub fn validate(schema: &Schema, doc: &Document) -> Vec<QLError> {
+    let mut operations = vec![];
+    let mut fragments = HashMap::new();
+    for def in doc.definitions.iter() {
+        match def {
+            Definition::Operation(op) => operations.push(OperationRef::from(op)),
+            Definition::Fragment(frag) => {
+                fragments.insert(frag.name.as_str(), frag);
+            }
+        }
+    }
+    let mut validator = Validator {
+        schema,
+        meta: schema.meta(),
+        fragments,
+        errors: vec![],
+    };
+    validator.validate_operation_names(&operations);
+    for operation in operations.iter() {
+        validator.validate_operation(operation);
+    }
+    validator.validate_fragments(&operations);
+    validator.errors
+}
+
+struct Validator<'a, 'd> {
+    schema: &'a Schema,
+    meta: &'a SchemaMeta,
+    fragments: HashMap<&'d str, &'d FragmentDefinition>,
+    errors: Vec<QLError>,
+}
+
+impl<'a, 'd> Validator<'a, 'd> {
+    fn add_error(&mut self, error: Error, positions: &[Pos]) {
+        let locations = positions.iter().map(|pos| Location::from(*pos)).collect();
+        self.errors.push(QLError::with_locations(error, locations));
+    }
+
+    /// operation names are unique, and an anonymous operation must be the only one
+    fn validate_operation_names(&mut self, operations: &[OperationRef<'d>]) {
+        let mut names = HashSet::new();
+        for operation in operations {
+            match operation.name {
+                Some(name) if !names.insert(name) => {
+                    self.add_error(
+                        Error::OnlyOneOperationCanNamed(name.to_string()),
+                        &[operation.position],
+                    );
+                }
+                Some(_) => {}
+                None if operations.len() > 1 => {
+                    self.add_error(
+                        Error::MustBeDefinedAnonymousOperation,
+                        &[operation.position],
+                    );
+                }
+                None => {}
+            }
+        }
+    }
+
+    fn validate_operation(&mut self, operation: &OperationRef<'d>) {
+        if self.meta.get_type(operation.root_type).is_none() {
+            let error = match operation.root_type {
+                MUTATION_TYPE_NAME => Error::MutationSchemaNotDefined,
+                _ => Error::SubscriptionSchemaNotDefined,
+            };
+            self.add_error(error, &[operation.position]);
+            return;
+        }
+        self.validate_selections(&operation.selection_set.items, operation.root_type);
+        self.validate_field_merging(&operation.selection_set.items, operation.root_type);
+
+        // variables are used and defined
+        let mut used = vec![];
+        let mut visited = HashSet::new();
+        self.collect_variable_usages(&operation.selection_set.items, &mut visited, &mut used);
+        let defined = operation
+            .variable_definitions
+            .iter()
+            .map(|def| def.name.as_str())
+            .collect::<HashSet<_>>();
+        for (name, position) in used.iter() {
+            if !defined.contains(name.as_str()) {
+                self.add_error(Error::UndefinedVariable(name.clone()), &[*position]);
+            }
+        }
+        for def in operation.variable_definitions {
+            if !used.iter().any(|(name, _)| *name == def.name) {
+                self.add_error(Error::UnusedVariable(def.name.clone()), &[def.position]);
+            }
+        }
+    }
+
+    /// fragments are defined on existing composite types, used by operations and not cyclic
+    fn validate_fragments(&mut self, operations: &[OperationRef<'d>]) {
+        let mut fragments = self.fragments.values().copied().collect::<Vec<_>>();
+        fragments.sort_by_key(|frag| frag.position);
+
+        for frag in fragments.iter() {
+            let TypeCondition::On(type_name) = &frag.type_condition;
+            if self.validate_type_condition(type_name, frag.position) {
+                self.validate_selections(&frag.selection_set.items, type_name);
+            }
+        }
+
+        let mut used = HashSet::new();
+        for operation in operations {
+            self.collect_used_fragments(&operation.selection_set.items, &mut used);
+        }
+        for frag in fragments.iter() {
+            if !used.contains(frag.name.as_str()) {
+                self.add_error(Error::UnusedFragment(frag.name.clone()), &[frag.position]);
+            }
+        }
+
+        let mut visited = HashSet::new();
+        for frag in fragments {
+            self.detect_fragment_cycle(frag, &mut visited, &mut vec![]);
+        }
+    }
+
+    /// the type of a fragment must exist and be an object, interface or union type
+    fn validate_type_condition(&mut self, type_name: &str, position: Pos) -> bool {
+        match self.meta.get_type(type_name).map(|t| t.kind) {
+            Some(TypeKind::Object) | Some(TypeKind::Interface) | Some(TypeKind::Union) => true,
+            Some(_) => {
+                self.add_error(
+                    Error::FragmentOnNonCompositeType(type_name.to_string()),
+                    &[position],
+                );
+                false
+            }
+            None => {
+                self.add_error(Error::UnknownType(type_name.to_string()), &[position]);
+                false
+            }
+        }
+    }
+
+    fn validate_selections(&mut self, selections: &'d [Selection], type_name: &str) {
+        for selection in selections {
+            match selection {
+                Selection::Field(field) => {
+                    self.validate_directives(&field.directives);
+                    self.validate_field(field, type_name);
+                }
+                Selection::FragmentSpread(fs) => {
+                    self.validate_directives(&fs.directives);
+                    if !self.fragments.contains_key(fs.fragment_name.as_str()) {
+                        self.add_error(
+                            Error::NoSuchFragment(fs.fragment_name.clone()),
+                            &[fs.position],
+                        );
+                    }
+                }
+                Selection::InlineFragment(inline) => {
+                    self.validate_directives(&inline.directives);
+                    match &inline.type_condition {
+                        Some(TypeCondition::On(condition)) => {
+                            if self.validate_type_condition(condition, inline.position) {
+                                self.validate_selections(&inline.selection_set.items, condition);
+                            }
+                        }
+                        None => self.validate_selections(&inline.selection_set.items, type_name),
+                    }
+                }
+            }
+        }
+    }
+
+    /// the directives are among the ones of `SchemaMeta::directives`, with known and correctly typed arguments
+    fn validate_directives(&mut self, directives: &[Directive]) {
+        for directive in directives {
+            let directive_meta = match self.meta.get_directive(&directive.name) {
+                Some(directive_meta) => directive_meta.clone(),
+                None => {
+                    self.add_error(
+                        Error::UnknownDirective(directive.name.clone()),
+                        &[directive.position],
+                    );
+                    continue;
+                }
+            };
+            let coordinate = format!("@{}", directive.name);
+            for (name, value) in directive.arguments.iter() {
+                let arg = match directive_meta.args.iter().find(|arg| arg.name == *name) {
+                    Some(arg) => arg,
+                    None => {
+                        self.add_error(
+                            Error::UnknownArgument(coordinate.clone(), name.clone()),
+                            &[directive.position],
+                        );
+                        continue;
+                    }
+                };
+                // values with variables are checked after the variables are coerced
+                if contains_variable(value) {
+                    continue;
+                }
+                // the arguments of the directives are scalars
+                let value_type = DataValue::from(value.clone()).get_type_name();
+                if value_type != arg.type_ref.named_type() {
+                    let err = Error::DataTypeMisMatchError(arg.type_ref.to_string(), value_type);
+                    self.add_error(
+                        Error::InvalidArgumentValue(name.clone(), err.to_string()),
+                        &[directive.position],
+                    );
+                }
+            }
+            for arg in directive_meta.args.iter() {
+                let required =
+                    matches!(arg.type_ref, TypeRef::NonNull(_)) && arg.default_value.is_none();
+                if required
+                    && !directive
+                        .arguments
+                        .iter()
+                        .any(|(name, _)| *name == arg.name)
+                {
+                    self.add_error(
+                        Error::MissingArgument(
+                            coordinate.clone(),
+                            arg.name.clone(),
+                            arg.type_ref.to_string(),
+                        ),
+                        &[directive.position],
+                    );
+                }
+            }
+        }
+    }
+
+    /// the field exists on the type, its arguments are valid,
+    /// and it has a selection of subfields exactly when its type is an object, interface or union
+    fn validate_field(&mut self, field: &'d AstField, type_name: &str) {
+        let field_meta = match self.get_field_meta(type_name, &field.name) {
+            Some(field_meta) => field_meta,
+            None => {
+                self.add_error(
+                    Error::UnknownField(type_name.to_string(), field.name.clone()),
+                    &[field.position],
+                );
+                return;
+            }
+        };
+        self.validate_arguments(field, type_name, &field_meta);
+
+        let field_type_name = field_meta.type_ref.named_type();
+        match self.meta.get_type(field_type_name).map(|t| t.kind) {
+            Some(TypeKind::Object) | Some(TypeKind::Interface) | Some(TypeKind::Union) => {
+                if field.selection_set.items.is_empty() {
+                    self.add_error(
+                        Error::MissingSubselection(
+                            field.name.clone(),
+                            field_meta.type_ref.to_string(),
+                        ),
+                        &[field.position],
+                    );
+                } else {
+                    self.validate_selections(&field.selection_set.items, field_type_name);
+                    self.validate_field_merging(&field.selection_set.items, field_type_name);
+                }
+            }
+            _ => {
+                if !field.selection_set.items.is_empty() {
+                    self.add_error(
+                        Error::LeafFieldSelection(
+                            field.name.clone(),
+                            field_meta.type_ref.to_string(),
+                        ),
+                        &[field.position],
+                    );
+                }
+            }
+        }
+    }
+
+    /// fields with the same response key in a selection set are merged when executed,
+    /// so they must be the same field with the same arguments
+    fn validate_field_merging(&mut self, selections: &'d [Selection], type_name: &str) {
+        let mut fields = vec![];
+        self.collect_type_fields(selections, type_name, &mut HashSet::new(), &mut fields);
+        let mut merged: Vec<&AstField> = vec![];
+        for field in fields {
+            let key = response_key(field);
+            let first = match merged.iter().find(|first| response_key(first) == key) {
+                Some(first) => *first,
+                None => {
+                    merged.push(field);
+                    continue;
+                }
+            };
+            let reason = if first.name != field.name {
+                format!("'{}' and '{}' are different fields", first.name, field.name)
+            } else if sorted_arguments(first) != sorted_arguments(field) {
+                "they have differing arguments".to_string()
+            } else {
+                continue;
+            };
+            self.add_error(
+                Error::FieldsConflict(key.to_string(), reason),
+                &[first.position, field.position],
+            );
+        }
+    }
+
+    /// the fields of the selections and the fragments on the same type, which are spread in place
+    fn collect_type_fields(
+        &self,
+        selections: &'d [Selection],
+        type_name: &str,
+        visited: &mut HashSet<&'d str>,
+        fields: &mut Vec<&'d AstField>,
+    ) {
+        for selection in selections {
+            match selection {
+                Selection::Field(field) => fields.push(field),
+                Selection::FragmentSpread(fs) => {
+                    if let Some(frag) = self.fragments.get(fs.fragment_name.as_str()) {
+                        let TypeCondition::On(condition) = &frag.type_condition;
+                        if condition == type_name && visited.insert(frag.name.as_str()) {
+                            let items = &frag.selection_set.items;
+                            self.collect_type_fields(items, type_name, visited, fields);
+                        }
+                    }
+                }
+                Selection::InlineFragment(inline) => match &inline.type_condition {
+                    Some(TypeCondition::On(condition)) if condition != type_name => {}
+                    _ => {
+                        let items = &inline.selection_set.items;
+                        self.collect_type_fields(items, type_name, visited, fields);
+                    }
+                },
+            }
+        }
+    }
+
+    fn get_field_meta(&self, type_name: &str, field_name: &str) -> Option<FieldMeta> {
+        let type_meta = self.meta.get_type(type_name)?;
+        match (field_name, type_meta.kind) {
+            ("__typename", TypeKind::Object | TypeKind::Interface | TypeKind::Union) => Some(
+                FieldMeta::new("__typename", TypeRef::named("String").non_null()),
+            ),
+            ("__schema", _) if type_name == QUERY_TYPE_NAME => Some(FieldMeta::new(
+                "__schema",
+                TypeRef::named("__Schema").non_null(),
+            )),
+            ("__type", _) if type_name == QUERY_TYPE_NAME => {
+                let mut field = FieldMeta::new("__type", TypeRef::named("__Type"));
+                field.args.push(InputValueMeta::new(
+                    "name",
+                    TypeRef::named("String").non_null(),
+                ));
+                Some(field)
+            }
+            _ => type_meta.get_field(field_name).cloned(),
+        }
+    }
+
+    /// arguments are known and correctly typed, and required arguments are present
+    fn validate_arguments(&mut self, field: &AstField, type_name: &str, field_meta: &FieldMeta) {
+        let coordinate = format!("{}.{}", type_name, field.name);
+        for (name, value) in field.arguments.iter() {
+            let arg = match field_meta.args.iter().find(|arg| arg.name == *name) {
+                Some(arg) => arg,
+                None => {
+                    self.add_error(
+                        Error::UnknownArgument(coordinate.clone(), name.clone()),
+                        &[field.position],
+                    );
+                    continue;
+                }
+            };
+            // values with variables are checked after the variables are coerced
+            if contains_variable(value) {
+                continue;
+            }
+            let checked = self
+                .schema
+                .get_input_type_by_ast(&ast_type(&arg.type_ref))
+                .and_then(|input_type| {
+                    input_type.check_literal(value)?;
+                    input_type.coerce(DataValue::from(value.clone()))
+                });
+            if let Err(err) = checked {
+                self.add_error(
+                    Error::InvalidArgumentValue(name.clone(), err.to_string()),
+                    &[field.position],
+                );
+            }
+        }
+        for arg in field_meta.args.iter() {
+            let required =
+                matches!(arg.type_ref, TypeRef::NonNull(_)) && arg.default_value.is_none();
+            if required && !field.arguments.iter().any(|(name, _)| *name == arg.name) {
+                self.add_error(
+                    Error::MissingArgument(
+                        coordinate.clone(),
+                        arg.name.clone(),
+                        arg.type_ref.to_string(),
+                    ),
+                    &[field.position],
+                );
+            }
+        }
+    }
+
+    /// the variables referenced by the selections and the fragments they spread, along with the field position
+    fn collect_variable_usages(
+        &self,
+        selections: &'d [Selection],
+        visited: &mut HashSet<&'d str>,
+        used: &mut Vec<(String, Pos)>,
+    ) {
+        for selection in selections {
+            match selection {
+                Selection::Field(field) => {
+                    for (_, value) in field.arguments.iter() {
+                        collect_variables(value, field.position, used);
+                    }
+                    for directive in field.directives.iter() {
+                        for (_, value) in directive.arguments.iter() {
+                            collect_variables(value, directive.position, used);
+                        }
+                    }
+                    self.collect_variable_usages(&field.selection_set.items, visited, used);
+                }
+                Selection::FragmentSpread(fs) => {
+                    for directive in fs.directives.iter() {
+                        for (_, value) in directive.arguments.iter() {
+                            collect_variables(value, directive.position, used);
+                        }
+                    }
+                    if let Some(frag) = self.fragments.get(fs.fragment_name.as_str()) {
+                        if visited.insert(frag.name.as_str()) {
+                            self.collect_variable_usages(&frag.selection_set.items, visited, used);
+                        }
+                    }
+                }
+                Selection::InlineFragment(inline) => {
+                    for directive in inline.directives.iter() {
+                        for (_, value) in directive.arguments.iter() {
+                            collect_variables(value, directive.position, used);
+                        }
+                    }
+                    self.collect_variable_usages(&inline.selection_set.items, visited, used);
+                }
+            }
+        }
+    }
+
+    /// names of the fragments spread by the selections, directly or through other fragments
+    fn collect_used_fragments(&self, selections: &'d [Selection], used: &mut HashSet<&'d str>) {
+        for spread in fragment_spreads(selections) {
+            if let Some(frag) = self.fragments.get(spread.fragment_name.as_str()) {
+                if used.insert(frag.name.as_str()) {
+                    self.collect_used_fragments(&frag.selection_set.items, used);
+                }
+            }
+        }
+    }
+
+    /// Walk the fragment spreads depth first, a spread of a fragment on the current path closes a cycle.
+    /// `path` holds the fragments entered and the spreads entering the next ones
+    fn detect_fragment_cycle(
+        &mut self,
+        frag: &'d FragmentDefinition,
+        visited: &mut HashSet<&'d str>,
+        path: &mut Vec<(&'d str, &'d FragmentSpread)>,
+    ) {
+        if !visited.insert(frag.name.as_str()) {
+            return;
+        }
+        for spread in fragment_spreads(&frag.selection_set.items) {
+            let name = spread.fragment_name.as_str();
+            if name == frag.name || path.iter().any(|(entered, _)| *entered == name) {
+                let start = path
+                    .iter()
+                    .position(|(entered, _)| *entered == name)
+                    .unwrap_or(path.len());
+                let mut positions = path[start..]
+                    .iter()
+                    .map(|(_, spread)| spread.position)
+                    .collect::<Vec<_>>();
+                positions.push(spread.position);
+                self.add_error(Error::FragmentCycle(name.to_string()), &positions);
+                continue;
+            }
+            if let Some(next) = self.fragments.get(name).copied() {
+                path.push((frag.name.as_str(), spread));
+                self.detect_fragment_cycle(next, visited, path);
+                path.pop();
+            }
+        }
+    }
+}
+
+/// the fragment spreads of the selections, including those in inline fragments and subfields
+fn fragment_spreads(selections: &[Selection]) -> Vec<&FragmentSpread> {
+    let mut result = vec![];
+    for selection in selections {
+        match selection {
+            Selection::Field(field) => result.extend(fragment_spreads(&field.selection_set.items)),
+            Selection::FragmentSpread(fs) => result.push(fs),
+            Selection::InlineFragment(inline) => {
+                result.extend(fragment_spreads(&inline.selection_set.items))
+            }
+        }
+    }
+    result
+}
+
+fn collect_variables(value: &ParserValue, position: Pos, used: &mut Vec<(String, Pos)>) {
+    match value {
+        ParserValue::Variable(name) => used.push((name.clone(), position)),
+        ParserValue::List(list) => {
+            for item in list {
+                collect_variables(item, position, used);
+            }
+        }
+        ParserValue::Object(map) => {
+            for item in map.values() {
+                collect_variables(item, position, used);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn sorted_arguments(field: &AstField) -> Vec<&(String, ParserValue)> {
+    let mut arguments = field.arguments.iter().collect::<Vec<_>>();
+    arguments.sort_by(|a, b| a.0.cmp(&b.0));
+    arguments
+}
+
+fn contains_variable(value: &ParserValue) -> bool {
+    match value {
+        ParserValue::Variable(_) => true,
+        ParserValue::List(list) => list.iter().any(contains_variable),
+        ParserValue::Object(map) => map.values().any(contains_variable),
+        _ => false,
+    }
+}
+
+fn ast_type(type_ref: &TypeRef) -> Type {
+    match type_ref {
+        TypeRef::Named(name) => Type::NamedType(name.clone()),
+        TypeRef::List(t) => Type::ListType(Box::new(ast_type(t))),
+        TypeRef::NonNull(t) => Type::NonNullType(Box::new(ast_type(t))),
+    }
+}
@@ -4,9 +4,19 @@ use serde::{
     ser::{SerializeMap, SerializeSeq},
     Serialize,
 };
-use std::collections::{BTreeMap, HashMap};
+use std::{
+    collections::{BTreeMap, HashMap},
+    convert::TryFrom,
+};
 
 use gurkle_parser::query::Value as ParserValue;
+use indexmap::IndexMap;
+use serde_json::Value as JsonValue;
+
+use crate::{
+    error::{self, Error},
+    schema::resolve::QLVariables,
+};
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum DataValue {
@@ -18,7 +28,7 @@ pub enum DataValue {
     DateTime(DateTime<Utc>),
     Null,
     List(Vec<DataValue>),
-    Object(BTreeMap<String, DataValue>),
+    Object(IndexMap<String, DataValue>),
 }
 
 impl DataValue {
@@ -46,7 +56,7 @@ impl DataValue {
     pub fn boxed_list(list: Vec<DataValue>) -> Box<DataValue> {
         Box::new(Self::List(list))
     }
-    pub fn boxed_object(map: BTreeMap<String, DataValue>) -> Box<DataValue> {
+    pub fn boxed_object(map: IndexMap<String, DataValue>) -> Box<DataValue> {
         Box::new(Self::Object(map))
     }
 
@@ -75,31 +85,68 @@ impl DataValue {
     }
 }
 
-// From transform from parser value
-impl From<ParserValue> for DataValue {
-    fn from(value: ParserValue) -> Self {
+impl DataValue {
+    /// Transform a parser value, `$variable` references are substituted from `variables`.
+    /// The field of an object with an unknown variable is left out, as if it was not provided,
+    /// otherwise an unknown variable becomes `DataValue::Null`
+    pub fn from_parser_value(value: ParserValue, variables: &QLVariables) -> Self {
         match value {
-            ParserValue::Variable(str) => DataValue::String(str),
+            ParserValue::Variable(name) => variables.get(&name).cloned().unwrap_or(DataValue::Null),
             ParserValue::Int(num) => DataValue::Int(num.as_i64().unwrap()),
             ParserValue::Float(f) => DataValue::Float(f),
             ParserValue::String(str) => DataValue::String(str),
             ParserValue::Boolean(b) => DataValue::Boolean(b),
             ParserValue::Null => DataValue::Null,
             ParserValue::Enum(str) => DataValue::String(str),
-            ParserValue::List(list) => {
-                DataValue::List(list.into_iter().map(|v| DataValue::from(v)).collect())
-            }
+            ParserValue::List(list) => DataValue::List(
+                list.into_iter()
+                    .map(|v| DataValue::from_parser_value(v, variables))
+                    .collect(),
+            ),
             ParserValue::Object(map) => {
                 let new_map = map
                     .into_iter()
-                    .map(|(k, v)| (k, DataValue::from(v)))
-                    .collect::<BTreeMap<String, DataValue>>();
+                    .filter(|(_, v)| {
+                        !matches!(v, ParserValue::Variable(name) if !variables.contains_key(name))
+                    })
+                    .map(|(k, v)| (k, DataValue::from_parser_value(v, variables)))
+                    .collect::<IndexMap<String, DataValue>>();
                 DataValue::Object(new_map)
             }
         }
     }
 }
 
+// From transform from parser value (constant value, without any variables)
+impl From<ParserValue> for DataValue {
+    fn from(value: ParserValue) -> Self {
+        DataValue::from_parser_value(value, &QLVariables::default())
+    }
+}
+
+// From transform from json value, e.g. the `variables` of a graphql http request
+impl From<JsonValue> for DataValue {
+    fn from(value: JsonValue) -> Self {
+        match value {
+            JsonValue::Null => DataValue::Null,
+            JsonValue::Bool(b) => DataValue::Boolean(b),
+            JsonValue::Number(num) => match num.as_i64() {
+                Some(i) => DataValue::Int(i),
+                None => DataValue::Float(num.as_f64().unwrap_or_default()),
+            },
+            JsonValue::String(str) => DataValue::String(str),
+            JsonValue::Array(list) => {
+                DataValue::List(list.into_iter().map(DataValue::from).collect())
+            }
+            JsonValue::Object(map) => DataValue::Object(
+                map.into_iter()
+                    .map(|(k, v)| (k, DataValue::from(v)))
+                    .collect(),
+            ),
+        }
+    }
+}
+
 // Serialize for DataValue to json
 impl Serialize for DataValue {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
@@ -205,7 +252,7 @@ impl ToDataValue for u8 {
 
 impl ToDataValue for f64 {
     fn to_data_value(&self) -> DataValue {
-        DataValue::Float(self.to_owned() as f64)
+        DataValue::Float(self.to_owned())
     }
 }
 
@@ -230,7 +277,7 @@ impl ToDataValue for DateTime<Utc> {
 impl<K: ToString, V: ToDataValue> ToDataValue for HashMap<K, V> {
     fn to_data_value(&self) -> DataValue {
         let btree = self
-            .into_iter()
+            .iter()
             .map(|(k, v)| (k.to_string(), v.to_data_value()))
             .collect();
         DataValue::Object(btree)
@@ -240,7 +287,7 @@ impl<K: ToString, V: ToDataValue> ToDataValue for HashMap<K, V> {
 impl<K: ToString, V: ToDataValue> ToDataValue for BTreeMap<K, V> {
     fn to_data_value(&self) -> DataValue {
         let btree = self
-            .into_iter()
+            .iter()
             .map(|(k, v)| (k.to_string(), v.to_data_value()))
             .collect();
         DataValue::Object(btree)
@@ -249,7 +296,7 @@ impl<K: ToString, V: ToDataValue> ToDataValue for BTreeMap<K, V> {
 
 impl<T: ToDataValue> ToDataValue for Vec<T> {
     fn to_data_value(&self) -> DataValue {
-        let list = self.into_iter().map(|v| v.to_data_value()).collect();
+        let list = self.iter().map(|v| v.to_data_value()).collect();
         DataValue::List(list)
     }
 }
@@ -262,3 +309,156 @@ impl<T: ToDataValue> ToDataValue for Option<T> {
         }
     }
 }
+
+// For custom data struct to be built from DataValue, such as the arguments of a request
+pub trait FromDataValue: Sized {
+    fn from_data_value(value: &DataValue) -> error::Result<Self>;
+}
+
+/// Build the field `name` of an object value, a missing field is taken as `DataValue::Null`.
+/// The errors are prefixed with the path of the field, used by `#[derive(GraphQLFromDataValue)]`
+pub fn from_data_value_field<T: FromDataValue>(
+    map: &IndexMap<String, DataValue>,
+    name: &str,
+) -> error::Result<T> {
+    T::from_data_value(map.get(name).unwrap_or(&DataValue::Null)).map_err(|err| at_path(name, err))
+}
+
+/// prefix the error of a nested value with its field name or list index
+pub(crate) fn at_path(segment: &str, err: Error) -> Error {
+    match err {
+        Error::ConvertDataValueError(path, reason) => {
+            Error::ConvertDataValueError(format!("{}.{}", segment, path), reason)
+        }
+        err => Error::ConvertDataValueError(segment.to_string(), err.to_string()),
+    }
+}
+
+fn mismatch<T>(expect: &str, value: &DataValue) -> error::Result<T> {
+    Err(Error::DataTypeMisMatchError(
+        expect.to_string(),
+        value.get_type_name(),
+    ))
+}
+
+impl FromDataValue for DataValue {
+    fn from_data_value(value: &DataValue) -> error::Result<Self> {
+        Ok(value.clone())
+    }
+}
+
+impl FromDataValue for String {
+    fn from_data_value(value: &DataValue) -> error::Result<Self> {
+        match value {
+            DataValue::ID(s) | DataValue::String(s) => Ok(s.clone()),
+            _ => mismatch("String", value),
+        }
+    }
+}
+
+macro_rules! from_data_value_int {
+    ($($t:ty),*) => {
+        $(
+            impl FromDataValue for $t {
+                fn from_data_value(value: &DataValue) -> error::Result<Self> {
+                    match value {
+                        DataValue::Int(i) => <$t>::try_from(*i).map_err(|_| {
+                            Error::DataTypeMisMatchError(
+                                stringify!($t).to_string(),
+                                format!("Int({})", i),
+                            )
+                        }),
+                        _ => mismatch("Int", value),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+from_data_value_int!(i64, i32, i16, i8, u64, u32, u16, u8);
+
+impl FromDataValue for f64 {
+    fn from_data_value(value: &DataValue) -> error::Result<Self> {
+        match value {
+            DataValue::Float(f) => Ok(*f),
+            DataValue::Int(i) => Ok(*i as f64),
+            _ => mismatch("Float", value),
+        }
+    }
+}
+
+impl FromDataValue for f32 {
+    fn from_data_value(value: &DataValue) -> error::Result<Self> {
+        f64::from_data_value(value).map(|f| f as f32)
+    }
+}
+
+impl FromDataValue for bool {
+    fn from_data_value(value: &DataValue) -> error::Result<Self> {
+        match value {
+            DataValue::Boolean(b) => Ok(*b),
+            _ => mismatch("Boolean", value),
+        }
+    }
+}
+
+impl FromDataValue for DateTime<Utc> {
+    fn from_data_value(value: &DataValue) -> error::Result<Self> {
+        match value {
+            DataValue::DateTime(dt) => Ok(*dt),
+            DataValue::String(s) => DateTime::parse_from_rfc3339(s)
+                .map(|dt| dt.with_timezone(&Utc))
+                .map_err(|_| Error::DataTypeMisMatchError("DateTime".to_string(), s.clone())),
+            _ => mismatch("DateTime", value),
+        }
+    }
+}
+
+impl<T: FromDataValue> FromDataValue for Option<T> {
+    fn from_data_value(value: &DataValue) -> error::Result<Self> {
+        match value {
+            DataValue::Null => Ok(None),
+            _ => T::from_data_value(value).map(Some),
+        }
+    }
+}
+
+impl<T: FromDataValue> FromDataValue for Vec<T> {
+    fn from_data_value(value: &DataValue) -> error::Result<Self> {
+        match value {
+            DataValue::List(list) => list
+                .iter()
+                .enumerate()
+                .map(|(i, item)| {
+                    T::from_data_value(item).map_err(|err| at_path(&i.to_string(), err))
+                })
+                .collect(),
+            _ => mismatch("List", value),
+        }
+    }
+}
+
+impl<V: FromDataValue> FromDataValue for HashMap<String, V> {
+    fn from_data_value(value: &DataValue) -> error::Result<Self> {
+        match value {
+            DataValue::Object(map) => map
+                .keys()
+                .map(|k| Ok((k.clone(), from_data_value_field(map, k)?)))
+                .collect(),
+            _ => mismatch("Object", value),
+        }
+    }
+}
+
+impl<V: FromDataValue> FromDataValue for BTreeMap<String, V> {
+    fn from_data_value(value: &DataValue) -> error::Result<Self> {
+        match value {
+            DataValue::Object(map) => map
+                .keys()
+                .map(|k| Ok((k.clone(), from_data_value_field(map, k)?)))
+                .collect(),
+            _ => mismatch("Object", value),
+        }
+    }
+}
@@ -0,0 +1,232 @@
+mod common;
+
+use rust_graphql_resolver::{
+    builder::{
+        field::{CustomTypeBuilder, QLInterfaceBuilder, QLUnionBuilder},
+        query::QueryBuilder,
+        schema::SchemaBuilder,
+        value::DataValueObjectBuilder,
+    },
+    error::{BuildResult, Error, Result},
+    schema::{
+        field::{Field, FieldType, InputFieldType},
+        query::Query,
+        resolve::{BoxedValue, QLApiParam, QLContext, QLVariables},
+        Schema,
+    },
+    value::DataValue,
+};
+
+use common::{const_field, id, non_null, object, string};
+
+fn id_field() -> Field {
+    Field::simple("id", non_null(Field::basic_id().field_type))
+}
+
+fn item(typename: &str, id: &str) -> DataValue {
+    DataValueObjectBuilder::new()
+        .add_str_field("__typename", typename.to_string())
+        .add_id_field("id", id.to_string())
+        .build()
+}
+
+fn build_schema() -> BuildResult<Schema> {
+    SchemaBuilder::new("abstract_types_schema")
+        .add_interface(
+            QLInterfaceBuilder::new("Node")
+                .set_description("an object with id")
+                .add_field("id", id_field())
+                .set_resolve_type(Box::new(
+                    |_context: &QLContext, data: &DataValue| -> Result<String> {
+                        match data {
+                            DataValue::Object(map) => match map.get("id") {
+                                Some(DataValue::ID(id)) if id.starts_with('u') => {
+                                    Ok("User".to_string())
+                                }
+                                Some(DataValue::ID(id)) if id.starts_with('p') => {
+                                    Ok("Post".to_string())
+                                }
+                                _ => Ok("Comment".to_string()),
+                            },
+                            _ => Err(Error::DefaultResolveError),
+                        }
+                    },
+                ))
+                .build(),
+        )
+        .add_object(
+            CustomTypeBuilder::new("User")
+                .add_interface("Node")
+                .add_field("id", id_field())
+                .add_field("name", const_field("name", "Tom"))
+                .build(),
+        )
+        .add_object(
+            CustomTypeBuilder::new("Post")
+                .add_interface("Node")
+                .add_field("id", id_field())
+                .add_field("title", const_field("title", "Hello"))
+                .build(),
+        )
+        .add_union(
+            QLUnionBuilder::new("SearchResult")
+                .add_type("User")
+                .add_type("Post")
+                .build(),
+        )
+        .add_query("node", |sch| -> BuildResult<Query> {
+            QueryBuilder::new()
+                .set_type(sch.get_interface_type("Node")?)
+                .add_argument("id", InputFieldType::basic_id())
+                .set_resolve(Box::new(
+                    |_context: &mut QLContext, param: &QLApiParam| -> Result<BoxedValue> {
+                        match param.arguments.get(&"id".to_string()) {
+                            Some(DataValue::String(id)) => Ok(DataValueObjectBuilder::new()
+                                .add_id_field("id", id.clone())
+                                .build()
+                                .to_boxed()),
+                            _ => Ok(DataValue::boxed_null()),
+                        }
+                    },
+                ))
+                .build()
+        })?
+        .add_query("search", |sch| -> BuildResult<Query> {
+            QueryBuilder::new()
+                .set_type(FieldType::List(Box::new(
+                    sch.get_union_type("SearchResult")?,
+                )))
+                .set_resolve(Box::new(
+                    |_context: &mut QLContext, _param: &QLApiParam| -> Result<BoxedValue> {
+                        Ok(Box::new(vec![item("User", "u1"), item("Post", "p1")]))
+                    },
+                ))
+                .build()
+        })?
+        .build()
+}
+
+fn run(request: &str) -> Result<DataValue> {
+    common::run(&build_schema().unwrap(), request, QLVariables::default()).into_result()
+}
+
+#[test]
+fn test_union_with_inline_fragments() {
+    let result =
+        run(r#"{ search { __typename ... on User { id name } ... on Post { id title } } }"#)
+            .unwrap();
+    assert_eq!(
+        result,
+        object(vec![(
+            "search",
+            DataValue::List(vec![
+                object(vec![
+                    ("__typename", string("User")),
+                    ("id", id("u1")),
+                    ("name", string("Tom")),
+                ]),
+                object(vec![
+                    ("__typename", string("Post")),
+                    ("id", id("p1")),
+                    ("title", string("Hello")),
+                ]),
+            ])
+        )])
+    );
+}
+
+#[test]
+fn test_interface_with_fragments() {
+    let result = run(r#"
+        {
+            user: node(id: "u1") { ...NodeId ... on User { name } ... on Post { title } }
+            post: node(id: "p1") { ...NodeId ... on User { name } ... on Post { title } }
+        }
+        fragment NodeId on Node { id }
+        "#)
+    .unwrap();
+    assert_eq!(
+        result,
+        object(vec![
+            (
+                "post",
+                object(vec![("id", id("p1")), ("title", string("Hello"))])
+            ),
+            (
+                "user",
+                object(vec![("id", id("u1")), ("name", string("Tom"))])
+            ),
+        ])
+    );
+}
+
+#[test]
+fn test_fragment_on_union_inside_union() {
+    let result = run(r#"{ search { ... on SearchResult { ... on Post { title } } } }"#).unwrap();
+    assert_eq!(
+        result,
+        object(vec![(
+            "search",
+            DataValue::List(vec![
+                object(vec![]),
+                object(vec![("title", string("Hello"))])
+            ])
+        )])
+    );
+}
+
+#[test]
+fn test_not_possible_type() {
+    let result = run(r#"{ node(id: "c1") { id } }"#);
+    assert!(matches!(
+        result,
+        Err(Error::NotPossibleType(abstract_name, name)) if abstract_name == "Node" && name == "Comment"
+    ));
+}
+
+#[test]
+fn test_abstract_types_introspection() {
+    let result = run(r#"
+        {
+            node: __type(name: "Node") { kind description fields { name } possibleTypes { name } }
+            search: __type(name: "SearchResult") { kind possibleTypes { name } }
+            user: __type(name: "User") { interfaces { name } possibleTypes { name } }
+        }
+        "#)
+    .unwrap();
+    let names = |list: &[&str]| {
+        DataValue::List(
+            list.iter()
+                .map(|name| object(vec![("name", string(name))]))
+                .collect(),
+        )
+    };
+    assert_eq!(
+        result,
+        object(vec![
+            (
+                "node",
+                object(vec![
+                    ("kind", string("INTERFACE")),
+                    ("description", string("an object with id")),
+                    ("fields", names(&["id"])),
+                    ("possibleTypes", names(&["Post", "User"])),
+                ])
+            ),
+            (
+                "search",
+                object(vec![
+                    ("kind", string("UNION")),
+                    ("possibleTypes", names(&["Post", "User"])),
+                ])
+            ),
+            (
+                "user",
+                object(vec![
+                    ("interfaces", names(&["Node"])),
+                    ("possibleTypes", DataValue::Null),
+                ])
+            ),
+        ])
+    );
+}
@@ -0,0 +1,223 @@
+mod common;
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use rust_graphql_resolver::{
+    builder::{
+        field::CustomTypeBuilder, query::QueryBuilder, schema::SchemaBuilder,
+        value::DataValueObjectBuilder,
+    },
+    error::{BuildResult, Error, Result},
+    schema::{
+        field::{CustomType, Field, FieldType, InputFieldType},
+        query::Query,
+        resolve::{BoxedValue, QLApiParam, QLContext, QLVariables},
+        Schema,
+    },
+    value::DataValue,
+};
+use serde_json::json;
+
+use common::{non_null_input, run_json};
+
+static PETS_CALLS: AtomicUsize = AtomicUsize::new(0);
+
+fn named(name: &str) -> DataValue {
+    DataValueObjectBuilder::new()
+        .add_str_field("name", name.to_string())
+        .build()
+}
+
+/// `avatar(size: Int!)`, the picture of the source named `name`
+fn avatar_field() -> Field {
+    Field::simple_with_resolve(
+        "avatar",
+        Field::basic_str().field_type,
+        Box::new(
+            |_context: &mut QLContext,
+             source: &DataValue,
+             param: &QLApiParam|
+             -> Result<BoxedValue> {
+                let name = match source {
+                    DataValue::Object(source) => source.get("name").cloned(),
+                    _ => None,
+                };
+                match (name, param.arguments.get(&"size".to_string())) {
+                    (Some(DataValue::String(name)), Some(DataValue::Int(size))) if *size > 0 => {
+                        Ok(Box::new(format!("{}_{}.png", name, size)))
+                    }
+                    _ => Err(Error::UnSupportedYetError("avatar size".to_string())),
+                }
+            },
+        ),
+    )
+    .add_argument("size", non_null_input(InputFieldType::basic_int()))
+}
+
+fn build_schema() -> BuildResult<Schema> {
+    SchemaBuilder::new("aliases_schema")
+        .add_object(
+            CustomTypeBuilder::new("Pet")
+                .add_field("name", Field::basic_str())
+                .add_field("avatar", avatar_field())
+                .build(),
+        )
+        .add_object_with_status(|sch| -> BuildResult<CustomType> {
+            CustomTypeBuilder::new("User")
+                .add_field("name", Field::basic_str())
+                .add_field("avatar", avatar_field())
+                .add_field(
+                    "pets",
+                    Field::simple_with_resolve(
+                        "pets",
+                        FieldType::List(Box::new(sch.get_object_type("Pet")?)),
+                        Box::new(
+                            |_context: &mut QLContext,
+                             _source: &DataValue,
+                             _param: &QLApiParam|
+                             -> Result<BoxedValue> {
+                                PETS_CALLS.fetch_add(1, Ordering::SeqCst);
+                                Ok(Box::new(DataValue::List(vec![named("rex"), named("tom")])))
+                            },
+                        ),
+                    ),
+                )
+                .build_ok()
+        })?
+        .add_query("cachedUser", |sch| -> BuildResult<Query> {
+            QueryBuilder::new()
+                .set_type(sch.get_object_type("User")?)
+                .set_resolve(Box::new(
+                    |_context: &mut QLContext, _param: &QLApiParam| -> Result<BoxedValue> {
+                        Ok(Box::new(
+                            DataValueObjectBuilder::new()
+                                .add_str_field("name", "ann".to_string())
+                                .add_str_field("avatar", "cached.png".to_string())
+                                .build(),
+                        ))
+                    },
+                ))
+                .build()
+        })?
+        .add_query("user", |sch| -> BuildResult<Query> {
+            QueryBuilder::new()
+                .set_type(sch.get_object_type("User")?)
+                .set_resolve(Box::new(
+                    |_context: &mut QLContext, _param: &QLApiParam| -> Result<BoxedValue> {
+                        Ok(Box::new(named("ann")))
+                    },
+                ))
+                .build()
+        })?
+        .build()
+}
+
+fn run(request: &str) -> serde_json::Value {
+    run_json(&build_schema().unwrap(), request, QLVariables::default())
+}
+
+#[test]
+fn test_nested_aliases() {
+    let result = run(r#"{
+            me: user {
+                a: name
+                b: name
+                small: avatar(size: 16)
+                big: avatar(size: 128)
+                animals: pets { called: name pic: avatar(size: 1) }
+            }
+        }"#);
+    assert_eq!(result["errors"], serde_json::Value::Null, "{}", result);
+    let me = &result["data"]["me"];
+    assert_eq!(
+        [&me["a"], &me["b"], &me["small"], &me["big"]],
+        [
+            &json!("ann"),
+            &json!("ann"),
+            &json!("ann_16.png"),
+            &json!("ann_128.png")
+        ]
+    );
+    let pets = me["animals"].as_array().unwrap();
+    assert_eq!(
+        pets.iter()
+            .map(|pet| (&pet["called"], &pet["pic"]))
+            .collect::<Vec<_>>(),
+        vec![
+            (&json!("rex"), &json!("rex_1.png")),
+            (&json!("tom"), &json!("tom_1.png")),
+        ]
+    );
+}
+
+#[test]
+fn test_resolver_runs_over_source_data() {
+    let result = run(r#"{
+            cachedUser { name small: avatar(size: 16) big: avatar(size: 128) }
+        }"#);
+    assert_eq!(
+        result,
+        json!({
+            "data": {
+                "cachedUser": { "name": "ann", "small": "ann_16.png", "big": "ann_128.png" },
+            },
+        })
+    );
+}
+
+#[test]
+fn test_aliased_error_path() {
+    let result = run(r#"{ user { pic: avatar(size: 0) } }"#);
+    assert_eq!(result["data"]["user"]["pic"], json!(null));
+    assert_eq!(result["errors"][0]["path"], json!(["user", "pic"]));
+}
+
+#[test]
+fn test_merged_selections() {
+    let before = PETS_CALLS.load(Ordering::SeqCst);
+    let result = run(r#"{
+            user { pets { name } }
+            user { pets { avatar(size: 2) } }
+            ...extra
+        }
+        fragment extra on Query { user { avatar(size: 3) } }"#);
+    assert_eq!(result["errors"], serde_json::Value::Null, "{}", result);
+    assert_eq!(PETS_CALLS.load(Ordering::SeqCst) - before, 1);
+    let user = &result["data"]["user"];
+    assert_eq!(user["avatar"], json!("ann_3.png"));
+    assert_eq!(
+        user["pets"],
+        json!([
+            { "name": "rex", "avatar": "rex_2.png" },
+            { "name": "tom", "avatar": "tom_2.png" },
+        ])
+    );
+
+    let result = run(r#"{ __type(name: "Pet") { name } __type(name: "Pet") { kind } }"#);
+    assert_eq!(
+        result,
+        json!({ "data": { "__type": { "name": "Pet", "kind": "OBJECT" } } })
+    );
+}
+
+#[test]
+fn test_conflicting_fields() {
+    let messages = |request: &str| -> Vec<String> {
+        let result = run(request);
+        assert!(result.get("data").is_none(), "executed {}", request);
+        result["errors"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|e| e["message"].as_str().unwrap().to_string())
+            .collect()
+    };
+    assert_eq!(
+        messages(r#"{ user { a: name a: avatar(size: 1) } }"#),
+        vec!["Fields 'a' conflict because 'name' and 'avatar' are different fields, use different aliases on the fields"]
+    );
+    assert_eq!(
+        messages(r#"{ user { avatar(size: 1) ... on User { avatar(size: 2) } } }"#),
+        vec!["Fields 'avatar' conflict because they have differing arguments, use different aliases on the fields"]
+    );
+}
@@ -0,0 +1,452 @@
+mod common;
+
+use rust_graphql_resolver::{
+    ast::query::Value as ParserValue,
+    builder::{
+        field::{CustomTypeBuilder, QLEnumBuilder, QLInputBuilder},
+        query::QueryBuilder,
+        schema::SchemaBuilder,
+        value::DataValueObjectBuilder,
+    },
+    error::{BuildResult, Result},
+    indexmap::IndexMap,
+    schema::{
+        field::{ArgumentMap, Field, FieldType, InputField, InputFieldType},
+        query::Query,
+        resolve::{ArgumentValueMap, BoxedValue, QLApiParam, QLContext, QLVariables},
+        Schema,
+    },
+    value::DataValue,
+};
+use serde_json::json;
+
+use common::{non_null_input, run_json};
+
+const NAMES: [&str; 3] = ["Ann", "Bob", "Cid"];
+
+fn build_schema() -> BuildResult<Schema> {
+    SchemaBuilder::new("arguments_schema")
+        .add_enum(
+            QLEnumBuilder::new("Color")
+                .add_value("Red")
+                .add_value("Green")
+                .build(),
+        )
+        .add_input_object(
+            QLInputBuilder::new("Point")
+                .add_field(
+                    "x",
+                    InputField::simple("x", non_null_input(InputFieldType::basic_float())),
+                )
+                .add_field("y", InputField::basic_float())
+                .build(),
+        )
+        .add_object_with_status(|sch| {
+            CustomTypeBuilder::new("Echo")
+                .add_field("float", Field::basic_float())
+                .add_field(
+                    "floats",
+                    Field::simple(
+                        "floats",
+                        FieldType::List(Box::new(Field::basic_float().field_type)),
+                    ),
+                )
+                .add_field("id", Field::basic_id())
+                .add_field("color", Field::simple("color", sch.get_enum_type("Color")?))
+                .add_field("point", Field::basic_str())
+                .build_ok()
+        })?
+        .add_object(
+            CustomTypeBuilder::new("Person")
+                .add_field("name", Field::basic_str())
+                .add_field(
+                    "friends",
+                    Field::simple_with_resolve(
+                        "friends",
+                        FieldType::List(Box::new(Field::basic_str().field_type)),
+                        Box::new(
+                            |_context: &mut QLContext,
+                             _source: &DataValue,
+                             param: &QLApiParam|
+                             -> Result<BoxedValue> {
+                                let first = match param.arguments.get(&"first".to_string()) {
+                                    Some(DataValue::Int(first)) => *first as usize,
+                                    _ => NAMES.len(),
+                                };
+                                let names = NAMES
+                                    .iter()
+                                    .take(first)
+                                    .map(|name| name.to_string())
+                                    .collect::<Vec<_>>();
+                                Ok(Box::new(names))
+                            },
+                        ),
+                    )
+                    .add_argument("first", non_null_input(InputFieldType::basic_int())),
+                )
+                .add_field(
+                    "greeting",
+                    Field::simple_with_resolve(
+                        "greeting",
+                        Field::basic_str().field_type,
+                        Box::new(
+                            |_context: &mut QLContext,
+                             _source: &DataValue,
+                             param: &QLApiParam|
+                             -> Result<BoxedValue> {
+                                let greeting = match param.arguments.get(&"scale".to_string()) {
+                                    Some(DataValue::Float(scale)) => format!("hi x{}", scale),
+                                    _ => "hi".to_string(),
+                                };
+                                let end = match param.arguments.get(&"end".to_string()) {
+                                    Some(DataValue::String(end)) => end.as_str(),
+                                    _ => "",
+                                };
+                                Ok(Box::new(format!("{}{}", greeting, end)))
+                            },
+                        ),
+                    ),
+                )
+                .add_field_argument("greeting", "scale", InputFieldType::basic_float())?
+                .add_field_argument_with_default(
+                    "greeting",
+                    "end",
+                    InputFieldType::basic_str(),
+                    DataValue::String("!".to_string()),
+                )?
+                .build(),
+        )
+        .add_query("echo", |sch| -> BuildResult<Query> {
+            QueryBuilder::new()
+                .set_type(sch.get_object_type("Echo")?)
+                .add_argument("float", InputFieldType::basic_float())
+                .add_argument(
+                    "floats",
+                    InputFieldType::List(Box::new(InputFieldType::basic_float())),
+                )
+                .add_argument("id", InputFieldType::basic_id())
+                .add_argument("color", sch.get_enum_input_type("Color")?)
+                .add_argument("point", sch.get_input_type("Point")?)
+                .set_resolve(Box::new(
+                    |_context: &mut QLContext, param: &QLApiParam| -> Result<BoxedValue> {
+                        let mut map = IndexMap::new();
+                        for name in ["float", "floats", "id", "color"] {
+                            if let Some(value) = param.arguments.get(&name.to_string()) {
+                                map.insert(name.to_string(), value.clone());
+                            }
+                        }
+                        if let Some(point) = param.arguments.get(&"point".to_string()) {
+                            map.insert(
+                                "point".to_string(),
+                                DataValue::String(format!("{:?}", point)),
+                            );
+                        }
+                        Ok(Box::new(DataValue::Object(map)))
+                    },
+                ))
+                .build()
+        })?
+        .add_query("need", |_sch| -> BuildResult<Query> {
+            QueryBuilder::new()
+                .set_type(Field::basic_str().field_type)
+                .add_argument("id", non_null_input(InputFieldType::basic_id()))
+                .set_resolve(Box::new(
+                    |_context: &mut QLContext, param: &QLApiParam| -> Result<BoxedValue> {
+                        Ok(Box::new(param.arguments.get(&"id".to_string()).cloned()))
+                    },
+                ))
+                .build()
+        })?
+        .add_query("me", |sch| -> BuildResult<Query> {
+            QueryBuilder::new()
+                .set_type(sch.get_object_type("Person")?)
+                .set_resolve(Box::new(
+                    |_context: &mut QLContext, _param: &QLApiParam| -> Result<BoxedValue> {
+                        Ok(Box::new(
+                            DataValueObjectBuilder::new()
+                                .add_str_field("name", "Me".to_string())
+                                .build(),
+                        ))
+                    },
+                ))
+                .build()
+        })?
+        .build()
+}
+
+fn run(request: &str, variables: QLVariables) -> serde_json::Value {
+    run_json(&build_schema().unwrap(), request, variables)
+}
+
+#[test]
+fn test_coerced_before_resolve() {
+    let result = run(
+        r#"{ echo(float: 1, floats: 2, id: 3, color: Red) { float floats id color } }"#,
+        QLVariables::default(),
+    );
+    assert_eq!(
+        result,
+        json!({
+            "data": { "echo": { "float": 1.0, "floats": [2.0], "id": "3", "color": "Red" } },
+        })
+    );
+
+    let variables = QLVariables::from([(
+        "point".to_string(),
+        DataValue::Object(IndexMap::from([("x".to_string(), DataValue::Int(1))])),
+    )]);
+    let result = run(
+        r#"query ($point: Point) { echo(point: $point) { point } }"#,
+        variables,
+    );
+    assert_eq!(
+        result,
+        json!({ "data": { "echo": { "point": r#"Object({"x": Float(1.0)})"# } } })
+    );
+}
+
+#[test]
+fn test_errors_of_variable_arguments() {
+    let result = run(
+        r#"query ($id: ID) { need(id: $id) }"#,
+        QLVariables::default(),
+    );
+    assert_eq!(
+        result,
+        json!({
+            "data": { "need": null },
+            "errors": [{
+                "message": "Field Query.need argument 'id' of type ID! is required, but it was not provided",
+                "locations": [{ "line": 1, "column": 19 }],
+                "path": ["need"],
+            }],
+        })
+    );
+
+    let variables = QLVariables::from([("id".to_string(), DataValue::Null)]);
+    let result = run(r#"query ($id: ID) { need(id: $id) }"#, variables);
+    assert_eq!(
+        result["errors"][0]["message"],
+        "Argument 'id' got invalid value: DataTypeMisMatchError expect: ID!, actul: Null"
+    );
+}
+
+#[test]
+fn test_coerced_field_arguments() {
+    let result = run(
+        r#"{ me { friends(first: 2) greeting(scale: 2) } }"#,
+        QLVariables::default(),
+    );
+    assert_eq!(
+        result,
+        json!({
+            "data": {
+                "me": { "friends": ["Ann", "Bob"], "greeting": "hi x2!" },
+            },
+        })
+    );
+
+    let variables = QLVariables::from([("first".to_string(), DataValue::Int(1))]);
+    let result = run(
+        r#"query ($first: Int!) { me { friends(first: $first) } }"#,
+        variables,
+    );
+    assert_eq!(result, json!({ "data": { "me": { "friends": ["Ann"] } } }));
+}
+
+#[test]
+fn test_validated_field_arguments() {
+    let messages = |request: &str| -> Vec<String> {
+        let result = run(request, QLVariables::default());
+        assert!(result.get("data").is_none(), "executed {}", request);
+        result["errors"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|e| e["message"].as_str().unwrap().to_string())
+            .collect()
+    };
+    assert_eq!(
+        messages(r#"{ me { friends(first: 1, after: "Ann") } }"#),
+        vec!["Unknown argument 'after' on field Person.friends"]
+    );
+    assert_eq!(
+        messages(r#"{ me { friends } }"#),
+        vec!["Field Person.friends argument 'first' of type Int! is required, but it was not provided"]
+    );
+    assert_eq!(
+        messages(r#"{ me { greeting(scale: "big") } }"#),
+        vec!["Argument 'scale' got invalid value: DataTypeMisMatchError expect: Float, actul: String"]
+    );
+
+    let result = run(
+        r#"query ($first: Int) { me { friends(first: $first) } }"#,
+        QLVariables::default(),
+    );
+    assert_eq!(
+        result,
+        json!({
+            "data": { "me": { "friends": null } },
+            "errors": [{
+                "message": "Field Person.friends argument 'first' of type Int! is required, but it was not provided",
+                "locations": [{ "line": 1, "column": 28 }],
+                "path": ["me", "friends"],
+            }],
+        })
+    );
+}
+
+#[test]
+fn test_field_arguments_introspection() {
+    let result = run(
+        r#"{ __type(name: "Person") { fields { name args { name type { kind name ofType { name } } } } } }"#,
+        QLVariables::default(),
+    );
+    assert_eq!(
+        result,
+        json!({
+            "data": {
+                "__type": {
+                    "fields": [
+                        {
+                            "name": "friends",
+                            "args": [{
+                                "name": "first",
+                                "type": { "kind": "NON_NULL", "name": null, "ofType": { "name": "Int" } },
+                            }],
+                        },
+                        {
+                            "name": "greeting",
+                            "args": [
+                                {
+                                    "name": "end",
+                                    "type": { "kind": "SCALAR", "name": "String", "ofType": null },
+                                },
+                                {
+                                    "name": "scale",
+                                    "type": { "kind": "SCALAR", "name": "Float", "ofType": null },
+                                },
+                            ],
+                        },
+                        { "name": "name", "args": [] },
+                    ],
+                },
+            },
+        })
+    );
+}
+
+#[test]
+fn test_unknown_field_argument() {
+    let error = CustomTypeBuilder::new("Person")
+        .add_field("greeting", Field::basic_str())
+        .add_field_argument("greting", "scale", InputFieldType::basic_float())
+        .err()
+        .unwrap();
+    assert_eq!(
+        error.to_string(),
+        "No such field Person.greting, set it first"
+    );
+}
+
+fn coerce(arguments: Vec<(&str, ParserValue)>) -> Result<ArgumentValueMap> {
+    let schema = build_schema().unwrap();
+    let declared: ArgumentMap = schema.queries().get("echo").unwrap().arguments.clone();
+    ArgumentValueMap::coerce(
+        arguments
+            .into_iter()
+            .map(|(name, value)| (name.to_string(), value))
+            .collect(),
+        &QLVariables::default(),
+        &declared,
+        "Query.echo",
+    )
+}
+
+fn point(fields: Vec<(&str, ParserValue)>) -> ParserValue {
+    ParserValue::Object(
+        fields
+            .into_iter()
+            .map(|(name, value)| (name.to_string(), value))
+            .collect(),
+    )
+}
+
+#[test]
+fn test_coerce_argument_value_map() {
+    let arguments = coerce(vec![
+        ("floats", ParserValue::Float(1.5)),
+        ("color", ParserValue::Enum("Green".to_string())),
+        ("point", point(vec![("x", ParserValue::Float(0.5))])),
+    ])
+    .unwrap();
+    assert_eq!(
+        arguments.get(&"floats".to_string()),
+        Some(&DataValue::List(vec![DataValue::Float(1.5)]))
+    );
+    assert_eq!(
+        arguments.get(&"color".to_string()),
+        Some(&DataValue::String("Green".to_string()))
+    );
+    assert_eq!(arguments.get(&"id".to_string()), None);
+
+    let messages = vec![
+        coerce(vec![("color", ParserValue::Enum("Blue".to_string()))]),
+        coerce(vec![("point", point(vec![("y", ParserValue::Float(0.5))]))]),
+        coerce(vec![(
+            "point",
+            point(vec![
+                ("x", ParserValue::Float(0.5)),
+                ("z", ParserValue::Null),
+            ]),
+        )]),
+        coerce(vec![("size", ParserValue::Null)]),
+    ]
+    .into_iter()
+    .map(|result| result.err().unwrap().to_string())
+    .collect::<Vec<_>>();
+    assert_eq!(
+        messages,
+        vec![
+            "Argument 'color' got invalid value: Value 'Blue' is not a member of enum Color",
+            "Argument 'point' got invalid value: Field 'x' of required type Float! on input type Point was not provided",
+            "Argument 'point' got invalid value: Unknown field 'z' on input type Point",
+            "Unknown argument 'size' on field Query.echo",
+        ]
+    );
+}
+
+#[test]
+fn test_enum_literals() {
+    let messages = vec![
+        coerce(vec![("color", ParserValue::String("Red".to_string()))]),
+        coerce(vec![("id", ParserValue::Enum("abc".to_string()))]),
+    ]
+    .into_iter()
+    .map(|result| result.err().unwrap().to_string())
+    .collect::<Vec<_>>();
+    assert_eq!(
+        messages,
+        vec![
+            "Argument 'color' got invalid value: DataTypeMisMatchError expect: Color, actul: String",
+            "Argument 'id' got invalid value: DataTypeMisMatchError expect: ID, actul: Enum(abc)",
+        ]
+    );
+
+    // rejected by validation, the variables of an enum type are strings
+    let result = run(
+        r#"{ echo(color: "Red") { color } }"#,
+        QLVariables::default(),
+    );
+    assert_eq!(
+        result["errors"][0]["message"],
+        "Argument 'color' got invalid value: DataTypeMisMatchError expect: Color, actul: String"
+    );
+    let variables =
+        QLVariables::from([("color".to_string(), DataValue::String("Red".to_string()))]);
+    let result = run(
+        r#"query ($color: Color) { echo(color: $color) { color } }"#,
+        variables,
+    );
+    assert_eq!(result, json!({ "data": { "echo": { "color": "Red" } } }));
+}
@@ -0,0 +1,300 @@
+use std::{
+    future::Future,
+    pin::Pin,
+    sync::{Arc, Mutex},
+    task::{Context, Poll},
+};
+
+use futures::executor::block_on;
+use rust_graphql_resolver::{
+    builder::{
+        field::CustomTypeBuilder, mutation::MutationBuilder, query::QueryBuilder,
+        schema::SchemaBuilder, value::DataValueObjectBuilder,
+    },
+    error::{BuildResult, Error, Result},
+    execute, execute_async,
+    schema::{
+        field::{Field, InputFieldType},
+        mutation::Mutation,
+        query::Query,
+        resolve::{AsyncApiResolveFunc, BoxedValue, QLApiParam, QLContext, QLVariables},
+        Schema,
+    },
+    value::DataValue,
+};
+use serde_json::json;
+
+type Log = Arc<Mutex<Vec<String>>>;
+
+/// pending once, so that other futures get polled in between
+struct YieldNow(bool);
+
+impl Future for YieldNow {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        if self.0 {
+            Poll::Ready(())
+        } else {
+            self.0 = true;
+            cx.waker().wake_by_ref();
+            Poll::Pending
+        }
+    }
+}
+
+async fn logged(log: Log, name: String) {
+    log.lock().unwrap().push(format!("start {}", name));
+    YieldNow(false).await;
+    log.lock().unwrap().push(format!("end {}", name));
+}
+
+fn async_field(name: &'static str, log: &Log) -> Field {
+    let log = log.clone();
+    Field::simple_with_async_resolve(
+        name,
+        Field::basic_str().field_type,
+        Box::new(
+            move |_context: QLContext, _source: DataValue, _param: QLApiParam| {
+                let log = log.clone();
+                async move {
+                    logged(log, name.to_string()).await;
+                    Ok(Box::new(name.to_uppercase()) as BoxedValue)
+                }
+            },
+        ),
+    )
+}
+
+fn async_api(name: &'static str, log: &Log) -> Box<dyn AsyncApiResolveFunc> {
+    let log = log.clone();
+    Box::new(move |_context: QLContext, param: QLApiParam| {
+        let log = log.clone();
+        async move {
+            let id = match param.arguments.get(&"id".to_string()) {
+                Some(DataValue::String(id)) => id.clone(),
+                _ => name.to_string(),
+            };
+            logged(log, id.clone()).await;
+            if id == "fail" {
+                return Err(Error::NotFoundError("fail item".to_string()));
+            }
+            let item = DataValueObjectBuilder::new().add_id_field("id", id).build();
+            Ok(Box::new(item) as BoxedValue)
+        }
+    })
+}
+
+fn build_schema(log: &Log) -> BuildResult<Schema> {
+    SchemaBuilder::new("async_resolvers_schema")
+        .add_object(
+            CustomTypeBuilder::new("Item")
+                .add_field("id", Field::basic_id())
+                .add_field("first", async_field("first", log))
+                .add_field("second", async_field("second", log))
+                .add_field(
+                    "plain",
+                    Field::simple_with_resolve(
+                        "plain",
+                        Field::basic_str().field_type,
+                        Box::new(
+                            |_context: &mut QLContext,
+                             _source: &DataValue,
+                             _param: &QLApiParam|
+                             -> Result<BoxedValue> {
+                                Ok(Box::new("plain".to_string()))
+                            },
+                        ),
+                    ),
+                )
+                .build(),
+        )
+        .add_query("item", |sch| -> BuildResult<Query> {
+            QueryBuilder::new()
+                .set_type(sch.get_object_type("Item")?)
+                .add_argument("id", InputFieldType::basic_id())
+                .set_async_resolve(async_api("item", log))
+                .build()
+        })?
+        .add_query("version", |_sch| -> BuildResult<Query> {
+            QueryBuilder::new()
+                .set_type(Field::basic_str().field_type)
+                .set_resolve(Box::new(
+                    |_context: &mut QLContext, _param: &QLApiParam| -> Result<BoxedValue> {
+                        Ok(Box::new("1.0".to_string()))
+                    },
+                ))
+                .build()
+        })?
+        .add_mutation("save", |sch| -> BuildResult<Mutation> {
+            MutationBuilder::new()
+                .set_type(sch.get_object_type("Item")?)
+                .add_argument("id", InputFieldType::basic_id())
+                .set_async_resolve(async_api("save", log))
+                .build()
+        })?
+        .build()
+}
+
+fn run(request: &str) -> (serde_json::Value, Vec<String>) {
+    let log = Log::default();
+    let schema = build_schema(&log).unwrap();
+    let response = block_on(execute_async(
+        QLContext::default(),
+        request,
+        &schema,
+        None,
+        QLVariables::default(),
+    ));
+    let result = serde_json::to_value(&response).unwrap();
+    let log = log.lock().unwrap().clone();
+    (result, log)
+}
+
+#[test]
+fn test_async_with_plain_resolvers() {
+    let (result, _) = run(r#"{ version item(id: "a") { id plain first } }"#);
+    assert_eq!(
+        result,
+        json!({
+            "data": {
+                "version": "1.0",
+                "item": { "id": "a", "plain": "plain", "first": "FIRST" },
+            },
+        })
+    );
+}
+
+#[test]
+fn test_query_fields_run_concurrently() {
+    let (_, log) = run(r#"{ a: item(id: "a") { id } b: item(id: "b") { id } }"#);
+    assert_eq!(log, vec!["start a", "start b", "end a", "end b"]);
+
+    let (_, log) = run(r#"{ item(id: "a") { first second } }"#);
+    assert_eq!(
+        log,
+        vec![
+            "start a",
+            "end a",
+            "start first",
+            "start second",
+            "end first",
+            "end second"
+        ]
+    );
+}
+
+#[test]
+fn test_mutations_run_serially() {
+    let (result, log) = run(r#"mutation { a: save(id: "a") { id } b: save(id: "b") { id } }"#);
+    assert_eq!(
+        result,
+        json!({ "data": { "a": { "id": "a" }, "b": { "id": "b" } } })
+    );
+    assert_eq!(log, vec!["start a", "end a", "start b", "end b"]);
+}
+
+#[test]
+fn test_async_errors() {
+    let (result, _) = run(r#"{ a: item(id: "a") { id } fail: item(id: "fail") { id } }"#);
+    assert_eq!(
+        result,
+        json!({
+            "data": { "a": { "id": "a" }, "fail": null },
+            "errors": [{
+                "message": "NotFound: fail item",
+                "locations": [{ "line": 1, "column": 27 }],
+                "path": ["fail"],
+            }],
+        })
+    );
+}
+
+#[test]
+fn test_sync_execute_awaits_async_resolvers() {
+    let log = Log::default();
+    let schema = build_schema(&log).unwrap();
+    let data = execute(
+        QLContext::default(),
+        r#"{ item(id: "a") { id first } }"#,
+        &schema,
+        None,
+        QLVariables::default(),
+    )
+    .into_result()
+    .unwrap();
+    assert_eq!(
+        serde_json::to_value(&data).unwrap(),
+        json!({ "item": { "id": "a", "first": "FIRST" } })
+    );
+}
+
+fn counter_schema() -> BuildResult<Schema> {
+    SchemaBuilder::new("counter_schema")
+        .add_query("count", |_sch| -> BuildResult<Query> {
+            QueryBuilder::new()
+                .set_type(Field::basic_int().field_type)
+                .set_resolve(Box::new(
+                    |context: &mut QLContext, _param: &QLApiParam| -> Result<BoxedValue> {
+                        let count = match context.get("count") {
+                            Some(DataValue::Int(count)) => count + 1,
+                            _ => 1,
+                        };
+                        context.insert("count".to_string(), DataValue::Int(count));
+                        Ok(Box::new(count))
+                    },
+                ))
+                .build()
+        })?
+        .build()
+}
+
+#[test]
+fn test_context_sharing() {
+    let schema = counter_schema().unwrap();
+    let request = "{ a: count b: count }";
+
+    // the sync executor shares the context, a write is seen by the next field
+    let response = execute(
+        QLContext::default(),
+        request,
+        &schema,
+        None,
+        QLVariables::default(),
+    );
+    assert_eq!(
+        serde_json::to_value(&response).unwrap(),
+        json!({ "data": { "a": 1, "b": 2 } })
+    );
+
+    // concurrent query fields get their own copy
+    let response = block_on(execute_async(
+        QLContext::default(),
+        request,
+        &schema,
+        None,
+        QLVariables::default(),
+    ));
+    assert_eq!(
+        serde_json::to_value(&response).unwrap(),
+        json!({ "data": { "a": 1, "b": 1 } })
+    );
+}
+
+#[test]
+fn test_sync_execute_inside_an_executor() {
+    let schema = counter_schema().unwrap();
+    let response = block_on(async {
+        execute(
+            QLContext::default(),
+            "{ count }",
+            &schema,
+            None,
+            QLVariables::default(),
+        )
+    });
+    assert_eq!(
+        serde_json::to_value(&response).unwrap(),
+        json!({ "data": { "count": 1 } })
+    );
+}
@@ -0,0 +1,63 @@
+//! Helpers shared by the integration tests, each test crate uses a part of them
+#![allow(dead_code)]
+
+use std::iter::FromIterator;
+
+use rust_graphql_resolver::{
+    error::Result,
+    execute,
+    indexmap::IndexMap,
+    response::Response,
+    schema::{
+        field::{Field, FieldType, InputFieldType},
+        resolve::{BoxedValue, QLApiParam, QLContext, QLVariables},
+        Schema,
+    },
+    value::DataValue,
+};
+
+/// Execute the request with a default context
+pub fn run(schema: &Schema, request: &str, variables: QLVariables) -> Response {
+    execute(QLContext::default(), request, schema, None, variables)
+}
+
+/// Execute the request with a default context, the response as json
+pub fn run_json(schema: &Schema, request: &str, variables: QLVariables) -> serde_json::Value {
+    serde_json::to_value(run(schema, request, variables)).unwrap()
+}
+
+/// A string field always resolving to the value
+pub fn const_field(name: &str, value: &'static str) -> Field {
+    Field::simple_with_resolve(
+        name,
+        Field::basic_str().field_type,
+        Box::new(
+            move |_context: &mut QLContext,
+                  _source: &DataValue,
+                  _param: &QLApiParam|
+                  -> Result<BoxedValue> { Ok(Box::new(value.to_string())) },
+        ),
+    )
+}
+
+pub fn non_null(field_type: FieldType) -> FieldType {
+    FieldType::NonNullType(Box::new(field_type))
+}
+
+pub fn non_null_input(input_type: InputFieldType) -> InputFieldType {
+    InputFieldType::NonNullType(Box::new(input_type))
+}
+
+pub fn object(list: Vec<(&str, DataValue)>) -> DataValue {
+    DataValue::Object(IndexMap::from_iter(
+        list.into_iter().map(|(k, v)| (k.to_string(), v)),
+    ))
+}
+
+pub fn string(s: &str) -> DataValue {
+    DataValue::String(s.to_string())
+}
+
+pub fn id(s: &str) -> DataValue {
+    DataValue::ID(s.to_string())
+}
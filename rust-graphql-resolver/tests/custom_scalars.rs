@@ -0,0 +1,217 @@
+mod common;
+
+use std::iter::FromIterator;
+
+use chrono::{NaiveDate, TimeZone, Utc};
+use rust_graphql_resolver::{
+    builder::{
+        field::{CustomTypeBuilder, QLScalarBuilder},
+        query::QueryBuilder,
+        schema::SchemaBuilder,
+        value::DataValueObjectBuilder,
+    },
+    error::{BuildResult, Error, Result},
+    schema::{
+        field::Field,
+        query::Query,
+        resolve::{BoxedValue, QLApiParam, QLContext, QLVariables},
+        Schema,
+    },
+    value::DataValue,
+};
+use serde_json::json;
+
+use common::{non_null_input, run_json};
+
+fn parse_email(value: DataValue) -> Result<DataValue> {
+    match value {
+        DataValue::String(s) if s.contains('@') => Ok(DataValue::String(s)),
+        other => Err(Error::InvalidScalarValue(
+            "Email".to_string(),
+            format!("{:?} is not an email address", other),
+        )),
+    }
+}
+
+fn serialize_date(value: DataValue) -> Result<DataValue> {
+    match value {
+        DataValue::DateTime(dt) => Ok(DataValue::String(dt.format("%Y-%m-%d").to_string())),
+        other => Err(Error::InvalidScalarValue(
+            "Date".to_string(),
+            other.get_type_name(),
+        )),
+    }
+}
+
+fn parse_date(value: DataValue) -> Result<DataValue> {
+    let date = match &value {
+        DataValue::String(s) => NaiveDate::parse_from_str(s, "%Y-%m-%d").ok(),
+        _ => None,
+    };
+    match date {
+        Some(date) => Ok(DataValue::DateTime(
+            Utc.from_utc_datetime(&date.and_hms(0, 0, 0)),
+        )),
+        None => Err(Error::InvalidScalarValue(
+            "Date".to_string(),
+            format!("{:?} is not a date", value),
+        )),
+    }
+}
+
+fn build_schema() -> BuildResult<Schema> {
+    let builder = SchemaBuilder::new("custom_scalars_schema")
+        .add_scalar(
+            QLScalarBuilder::new("Email")
+                .set_description("An email address")
+                .set_specified_by_url("https://www.rfc-editor.org/rfc/rfc5322")
+                .set_serialize(Box::new(parse_email))
+                .set_parse(Box::new(parse_email))
+                .build(),
+        )
+        .add_scalar(
+            QLScalarBuilder::new("Date")
+                .set_serialize(Box::new(serialize_date))
+                .set_parse(Box::new(parse_date))
+                .build(),
+        )
+        // any value is accepted and returned as it is
+        .add_scalar(QLScalarBuilder::new("JSON").build());
+    let user = CustomTypeBuilder::new("User")
+        .add_field(
+            "email",
+            Field::simple("email", builder.get_scalar_type("Email")?),
+        )
+        .add_field(
+            "birthday",
+            Field::simple("birthday", builder.get_scalar_type("Date")?),
+        )
+        .add_field(
+            "settings",
+            Field::simple("settings", builder.get_scalar_type("JSON")?),
+        )
+        .build();
+    builder
+        .add_object(user)
+        .add_query("user", |sch| -> BuildResult<Query> {
+            QueryBuilder::new()
+                .set_type(sch.get_object_type("User")?)
+                .add_argument("email", non_null_input(sch.get_scalar_input_type("Email")?))
+                .add_argument("birthday", sch.get_scalar_input_type("Date")?)
+                .set_resolve(Box::new(
+                    |_context: &mut QLContext, param: &QLApiParam| -> Result<BoxedValue> {
+                        let birthday = param
+                            .arguments
+                            .get(&"birthday".to_string())
+                            .cloned()
+                            .unwrap_or_else(|| {
+                                DataValue::DateTime(Utc.ymd(2000, 1, 2).and_hms(3, 4, 5))
+                            });
+                        Ok(Box::new(
+                            DataValueObjectBuilder::new()
+                                .add_any_field(
+                                    "email",
+                                    param.arguments.get(&"email".to_string()).unwrap().clone(),
+                                )
+                                .add_any_field("birthday", birthday)
+                                .add_any_field(
+                                    "settings",
+                                    DataValueObjectBuilder::new()
+                                        .add_str_field("theme", "dark".to_string())
+                                        .add_list_field("tags", vec![DataValue::Int(1)])
+                                        .build(),
+                                )
+                                .build(),
+                        ))
+                    },
+                ))
+                .build()
+        })?
+        .build()
+}
+
+fn run_with(request: &str, variables: QLVariables) -> serde_json::Value {
+    run_json(&build_schema().unwrap(), request, variables)
+}
+
+fn run(request: &str) -> serde_json::Value {
+    run_with(request, QLVariables::default())
+}
+
+#[test]
+fn test_serialize() {
+    assert_eq!(
+        run(r#"{ user(email: "ann@example.com") { email birthday settings } }"#),
+        json!({
+            "data": {
+                "user": {
+                    "email": "ann@example.com",
+                    "birthday": "2000-01-02",
+                    "settings": { "theme": "dark", "tags": [1] },
+                },
+            },
+        })
+    );
+}
+
+#[test]
+fn test_parse_literal() {
+    assert_eq!(
+        run(r#"{ user(email: "ann@example.com", birthday: "1999-12-31") { birthday } }"#),
+        json!({ "data": { "user": { "birthday": "1999-12-31" } } })
+    );
+    let result = run(r#"{ user(email: "ann") { email } }"#);
+    assert!(result.get("data").is_none());
+    assert_eq!(
+        result["errors"][0]["message"],
+        r#"Argument 'email' got invalid value: Invalid value for scalar Email: String("ann") is not an email address"#
+    );
+}
+
+#[test]
+fn test_parse_variable() {
+    let request = r#"
+    query User($email: Email!, $birthday: Date) {
+        user(email: $email, birthday: $birthday) { email birthday }
+    }
+    "#;
+    let variables = |email: &str, birthday: &str| {
+        QLVariables::from_iter(vec![
+            ("email".to_string(), DataValue::String(email.to_string())),
+            (
+                "birthday".to_string(),
+                DataValue::String(birthday.to_string()),
+            ),
+        ])
+    };
+    assert_eq!(
+        run_with(request, variables("bob@example.com", "2001-02-03")),
+        json!({ "data": { "user": { "email": "bob@example.com", "birthday": "2001-02-03" } } })
+    );
+    let result = run_with(request, variables("bob@example.com", "tomorrow"));
+    assert_eq!(
+        result["errors"][0]["message"],
+        r#"Variable '$birthday' got invalid value: Invalid value for scalar Date: String("tomorrow") is not a date"#
+    );
+}
+
+#[test]
+fn test_introspection() {
+    assert_eq!(
+        run(r#"{
+            email: __type(name: "Email") { kind name description specifiedByURL }
+            json: __type(name: "JSON") { kind name description specifiedByURL }
+        }"#),
+        json!({
+            "data": {
+                "email": {
+                    "kind": "SCALAR",
+                    "name": "Email",
+                    "description": "An email address",
+                    "specifiedByURL": "https://www.rfc-editor.org/rfc/rfc5322",
+                },
+                "json": { "kind": "SCALAR", "name": "JSON", "description": null, "specifiedByURL": null },
+            },
+        })
+    );
+}
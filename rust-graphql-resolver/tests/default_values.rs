@@ -0,0 +1,190 @@
+mod common;
+
+use rust_graphql_resolver::{
+    builder::{
+        field::{QLEnumBuilder, QLInputBuilder},
+        query::QueryBuilder,
+        schema::SchemaBuilder,
+    },
+    error::{BuildResult, Result},
+    indexmap::IndexMap,
+    schema::{
+        field::{Field, InputField, InputFieldType, QLInput},
+        query::Query,
+        resolve::{BoxedValue, QLApiParam, QLContext, QLVariables},
+        Schema,
+    },
+    value::DataValue,
+};
+use serde_json::json;
+
+use common::{non_null_input, run_json};
+
+const ARGUMENTS: [&str; 4] = ["limit", "order", "page", "scale"];
+
+fn build_schema() -> BuildResult<Schema> {
+    SchemaBuilder::new("default_values_schema")
+        .add_enum(
+            QLEnumBuilder::new("Order")
+                .add_value("Asc")
+                .add_value("Desc")
+                .build(),
+        )
+        .add_input_object_with_status(|sch| -> BuildResult<QLInput> {
+            QLInputBuilder::new("Page")
+                .add_field(
+                    "offset",
+                    InputField::simple("offset", non_null_input(InputFieldType::basic_int())),
+                )
+                .add_field_with_default("size", InputFieldType::basic_int(), DataValue::Int(10))
+                .add_field_with_default(
+                    "order",
+                    sch.get_enum_input_type("Order")?,
+                    DataValue::String("Asc".to_string()),
+                )
+                .build_ok()
+        })?
+        .add_query("items", |sch| -> BuildResult<Query> {
+            QueryBuilder::new()
+                .set_type(Field::basic_str().field_type)
+                .add_argument_with_default(
+                    "limit",
+                    non_null_input(InputFieldType::basic_int()),
+                    DataValue::Int(2),
+                )
+                .add_argument_with_default(
+                    "order",
+                    sch.get_enum_input_type("Order")?,
+                    DataValue::String("Desc".to_string()),
+                )
+                .add_argument_with_default(
+                    "page",
+                    sch.get_input_type("Page")?,
+                    DataValue::Object(IndexMap::from([("offset".to_string(), DataValue::Int(0))])),
+                )
+                .add_argument_with_default(
+                    "scale",
+                    InputFieldType::basic_float(),
+                    DataValue::Int(1),
+                )
+                .set_resolve(Box::new(
+                    |_context: &mut QLContext, param: &QLApiParam| -> Result<BoxedValue> {
+                        let arguments = ARGUMENTS
+                            .iter()
+                            .filter_map(|name| {
+                                let value = param.arguments.get(&name.to_string())?;
+                                Some((name.to_string(), value.clone()))
+                            })
+                            .collect::<IndexMap<_, _>>();
+                        let arguments = serde_json::to_string(&DataValue::Object(arguments))
+                            .unwrap_or_default();
+                        Ok(Box::new(arguments))
+                    },
+                ))
+                .build()
+        })?
+        .build()
+}
+
+fn run(request: &str, variables: QLVariables) -> serde_json::Value {
+    run_json(&build_schema().unwrap(), request, variables)
+}
+
+/// the arguments passed to `items`
+fn arguments(request: &str, variables: QLVariables) -> serde_json::Value {
+    let result = run(request, variables);
+    let items = result["data"]["items"]
+        .as_str()
+        .unwrap_or_else(|| panic!("{}", result));
+    serde_json::from_str(items).unwrap()
+}
+
+#[test]
+fn test_default_arguments() {
+    assert_eq!(
+        arguments("{ items }", QLVariables::default()),
+        json!({
+            "limit": 2,
+            "order": "Desc",
+            "page": { "offset": 0, "order": "Asc", "size": 10 },
+            "scale": 1.0,
+        })
+    );
+    assert_eq!(
+        arguments(
+            r#"{ items(limit: 5, order: Asc, page: { offset: 3, size: 1 }, scale: null) }"#,
+            QLVariables::default()
+        ),
+        json!({
+            "limit": 5,
+            "order": "Asc",
+            "page": { "offset": 3, "order": "Asc", "size": 1 },
+            "scale": null,
+        })
+    );
+}
+
+#[test]
+fn test_default_with_variables() {
+    // a variable which is not provided leaves the argument to its default
+    assert_eq!(
+        arguments(
+            "query ($limit: Int) { items(limit: $limit) }",
+            QLVariables::default()
+        )["limit"],
+        json!(2)
+    );
+
+    let variables = QLVariables::from([(
+        "page".to_string(),
+        DataValue::Object(IndexMap::from([("offset".to_string(), DataValue::Int(7))])),
+    )]);
+    assert_eq!(
+        arguments("query ($page: Page) { items(page: $page) }", variables)["page"],
+        json!({ "offset": 7, "order": "Asc", "size": 10 })
+    );
+
+    let result = run(r#"{ items(page: { size: 1 }) }"#, QLVariables::default());
+    assert_eq!(
+        result["errors"][0]["message"],
+        "Argument 'page' got invalid value: Field 'offset' of required type Int! on input type Page was not provided"
+    );
+}
+
+#[test]
+fn test_default_values_introspection() {
+    let result = run(
+        r#"{
+            __schema { queryType { fields { name args { name defaultValue } } } }
+            __type(name: "Page") { inputFields { name defaultValue } }
+        }"#,
+        QLVariables::default(),
+    );
+    assert_eq!(
+        result,
+        json!({
+            "data": {
+                "__schema": {
+                    "queryType": {
+                        "fields": [{
+                            "name": "items",
+                            "args": [
+                                { "name": "limit", "defaultValue": "2" },
+                                { "name": "order", "defaultValue": "Desc" },
+                                { "name": "page", "defaultValue": "{offset: 0}" },
+                                { "name": "scale", "defaultValue": "1" },
+                            ],
+                        }],
+                    },
+                },
+                "__type": {
+                    "inputFields": [
+                        { "name": "offset", "defaultValue": null },
+                        { "name": "order", "defaultValue": "Asc" },
+                        { "name": "size", "defaultValue": "10" },
+                    ],
+                },
+            },
+        })
+    );
+}
@@ -0,0 +1,183 @@
+mod common;
+
+use std::iter::FromIterator;
+
+use rust_graphql_resolver::{
+    builder::{
+        field::CustomTypeBuilder, query::QueryBuilder, schema::SchemaBuilder,
+        value::DataValueObjectBuilder,
+    },
+    error::{BuildResult, Result},
+    schema::{
+        field::Field,
+        query::Query,
+        resolve::{BoxedValue, QLApiParam, QLContext, QLVariables},
+        Schema,
+    },
+    value::DataValue,
+};
+use serde_json::json;
+
+use common::run_json;
+
+fn build_schema() -> BuildResult<Schema> {
+    SchemaBuilder::new("directives_schema")
+        .add_object(
+            CustomTypeBuilder::new("User")
+                .add_field("name", Field::basic_str())
+                .add_field("email", Field::basic_str())
+                .add_field(
+                    "secret",
+                    Field::simple_with_resolve(
+                        "secret",
+                        Field::basic_str().field_type,
+                        Box::new(
+                            |_context: &mut QLContext,
+                             _source: &DataValue,
+                             _param: &QLApiParam|
+                             -> Result<BoxedValue> {
+                                panic!("a skipped field must not be resolved")
+                            },
+                        ),
+                    ),
+                )
+                .build(),
+        )
+        .add_query("me", |sch| -> BuildResult<Query> {
+            QueryBuilder::new()
+                .set_type(sch.get_object_type("User")?)
+                .set_resolve(Box::new(
+                    |_context: &mut QLContext, _param: &QLApiParam| -> Result<BoxedValue> {
+                        Ok(Box::new(
+                            DataValueObjectBuilder::new()
+                                .add_str_field("name", "Ann".to_string())
+                                .add_str_field("email", "ann@example.com".to_string())
+                                .build(),
+                        ))
+                    },
+                ))
+                .build()
+        })?
+        .build()
+}
+
+fn run_with(request: &str, variables: QLVariables) -> serde_json::Value {
+    run_json(&build_schema().unwrap(), request, variables)
+}
+
+fn run(request: &str) -> serde_json::Value {
+    run_with(request, QLVariables::default())
+}
+
+fn flag(value: bool) -> QLVariables {
+    QLVariables::from_iter(vec![("flag".to_string(), DataValue::Boolean(value))])
+}
+
+#[test]
+fn test_literal_conditions() {
+    assert_eq!(
+        run("{ me { name email @skip(if: true) secret @include(if: false) } }"),
+        json!({ "data": { "me": { "name": "Ann" } } })
+    );
+    assert_eq!(
+        run("{ me { name @skip(if: false) email @include(if: true) } }"),
+        json!({ "data": { "me": { "name": "Ann", "email": "ann@example.com" } } })
+    );
+    // both must allow the field
+    assert_eq!(
+        run("{ me { name email @include(if: true) @skip(if: true) } }"),
+        json!({ "data": { "me": { "name": "Ann" } } })
+    );
+    assert_eq!(
+        run("{ me { name } other: me @skip(if: true) { name } }"),
+        json!({ "data": { "me": { "name": "Ann" } } })
+    );
+}
+
+#[test]
+fn test_variable_conditions() {
+    let request = r#"
+    query Toggle($flag: Boolean!) {
+        me {
+            name
+            ... on User @include(if: $flag) { email }
+            ...Secret @skip(if: $flag)
+        }
+    }
+    fragment Secret on User { secret }
+    "#;
+    assert_eq!(
+        run_with(request, flag(true)),
+        json!({ "data": { "me": { "name": "Ann", "email": "ann@example.com" } } })
+    );
+    let request = r#"
+    query Toggle($flag: Boolean!) {
+        me { name @include(if: $flag) email @skip(if: $flag) }
+    }
+    "#;
+    assert_eq!(
+        run_with(request, flag(false)),
+        json!({ "data": { "me": { "email": "ann@example.com" } } })
+    );
+}
+
+#[test]
+fn test_skipped_in_one_place_only() {
+    assert_eq!(
+        run("{ me { name @skip(if: true) ... on User { name } } }"),
+        json!({ "data": { "me": { "name": "Ann" } } })
+    );
+}
+
+#[test]
+fn test_invalid_directives() {
+    let messages = |request: &str| -> Vec<String> {
+        let result = run(request);
+        assert!(result.get("data").is_none(), "executed {}", request);
+        result["errors"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|e| e["message"].as_str().unwrap().to_string())
+            .collect()
+    };
+    assert_eq!(
+        messages("{ me { name @hidden } }"),
+        vec!["Unknown directive '@hidden'"]
+    );
+    assert_eq!(
+        messages("{ me { name @skip } }"),
+        vec!["Field @skip argument 'if' of type Boolean! is required, but it was not provided"]
+    );
+    assert_eq!(
+        messages(r#"{ me { name @include(if: "yes", when: 1) } }"#),
+        vec![
+            "Argument 'if' got invalid value: DataTypeMisMatchError expect: Boolean!, actul: String",
+            "Unknown argument 'when' on field @include",
+        ]
+    );
+}
+
+#[test]
+fn test_introspection() {
+    let directive = |name: &str| {
+        json!({
+            "name": name,
+            "locations": ["FIELD", "FRAGMENT_SPREAD", "INLINE_FRAGMENT"],
+            "isRepeatable": false,
+            "args": [{ "name": "if", "type": { "kind": "NON_NULL", "ofType": { "name": "Boolean" } } }],
+        })
+    };
+    assert_eq!(
+        run(r#"{
+            __schema {
+                directives { name locations isRepeatable args { name type { kind ofType { name } } } }
+            }
+        }"#),
+        json!({
+            "data": {
+                "__schema": { "directives": [directive("include"), directive("skip")] },
+            },
+        })
+    );
+}
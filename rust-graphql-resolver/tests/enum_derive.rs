@@ -0,0 +1,166 @@
+mod common;
+
+use macros::{GraphQLDataValue, GraphQLEnum, GraphQLObject, GraphQLUnion};
+use rust_graphql_resolver::{
+    builder::{
+        query::QueryBuilder,
+        schema::SchemaBuilder,
+        types::{EnumType, ObjectType, OutputType, UnionType},
+    },
+    error::{BuildResult, Result},
+    schema::{
+        query::Query,
+        resolve::{BoxedValue, QLApiParam, QLContext, QLVariables},
+        Schema,
+    },
+    value::{DataValue, ToDataValue},
+};
+use serde_json::json;
+
+use common::run_json;
+
+/// colors of fur
+#[derive(Debug, Clone, GraphQLDataValue, GraphQLEnum)]
+enum Color {
+    /// like a fox
+    Red,
+    #[graphql(rename = "BLACK")]
+    Black,
+}
+
+#[derive(Debug, Clone, GraphQLDataValue, GraphQLObject)]
+struct Pet {
+    name: String,
+    color: Color,
+}
+
+#[derive(Debug, Clone, GraphQLDataValue, GraphQLObject)]
+struct Robot {
+    model: String,
+}
+
+#[derive(Debug, Clone, GraphQLDataValue, GraphQLUnion)]
+#[graphql(description = "anything living in the house")]
+enum Resident {
+    Pet(Pet),
+    #[graphql(rename = "Robot")]
+    Machine(Robot),
+    Ghost {
+        name: String,
+    },
+}
+
+#[derive(Debug, Clone, GraphQLDataValue, GraphQLObject)]
+struct Ghost {
+    name: String,
+}
+
+fn residents() -> Vec<Resident> {
+    vec![
+        Resident::Pet(Pet {
+            name: "rex".to_string(),
+            color: Color::Black,
+        }),
+        Resident::Machine(Robot {
+            model: "r2".to_string(),
+        }),
+        Resident::Ghost {
+            name: "boo".to_string(),
+        },
+    ]
+}
+
+fn build_schema() -> BuildResult<Schema> {
+    SchemaBuilder::new("enum_derive_schema")
+        .add_enum(Color::enum_type())
+        .add_object_with_status(Pet::object_type)?
+        .add_object_with_status(Robot::object_type)?
+        .add_object_with_status(Ghost::object_type)?
+        .add_union(Resident::union_type())
+        .add_query("residents", |sch| -> BuildResult<Query> {
+            QueryBuilder::new()
+                .set_type(Vec::<Resident>::field_type(sch)?)
+                .set_resolve(Box::new(
+                    |_context: &mut QLContext, _param: &QLApiParam| -> Result<BoxedValue> {
+                        Ok(Box::new(residents()))
+                    },
+                ))
+                .build()
+        })?
+        .build()
+}
+
+fn run(request: &str) -> serde_json::Value {
+    run_json(&build_schema().unwrap(), request, QLVariables::default())
+}
+
+#[test]
+fn test_enum_data_values() {
+    assert_eq!(
+        Color::Red.to_data_value(),
+        DataValue::String("Red".to_string())
+    );
+    assert_eq!(
+        Color::Black.to_data_value(),
+        DataValue::String("BLACK".to_string())
+    );
+    let values = residents()
+        .iter()
+        .map(|resident| serde_json::to_value(resident.to_data_value()).unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        values,
+        vec![
+            json!({ "__typename": "Pet", "name": "rex", "color": "BLACK" }),
+            json!({ "__typename": "Robot", "model": "r2" }),
+            json!({ "__typename": "Ghost", "name": "boo" }),
+        ]
+    );
+}
+
+#[test]
+fn test_union_resolve() {
+    let result = run(r#"{
+            residents {
+                __typename
+                ... on Pet { name color }
+                ... on Robot { model }
+                ... on Ghost { name }
+            }
+        }"#);
+    assert_eq!(result["errors"], serde_json::Value::Null, "{}", result);
+    assert_eq!(
+        result["data"]["residents"],
+        json!([
+            { "__typename": "Pet", "name": "rex", "color": "BLACK" },
+            { "__typename": "Robot", "model": "r2" },
+            { "__typename": "Ghost", "name": "boo" },
+        ])
+    );
+}
+
+#[test]
+fn test_enum_introspection() {
+    let result = run(r#"{
+            color: __type(name: "Color") { description enumValues { name description } }
+            resident: __type(name: "Resident") { description possibleTypes { name } }
+        }"#);
+    assert_eq!(
+        result,
+        json!({
+            "data": {
+                "color": {
+                    "description": "colors of fur",
+                    "enumValues": [
+                        { "name": "Red", "description": "like a fox" },
+                        { "name": "BLACK", "description": null },
+                    ],
+                },
+                "resident": {
+                    "description": "anything living in the house",
+                    "possibleTypes": [{ "name": "Ghost" }, { "name": "Pet" }, { "name": "Robot" }],
+                },
+            },
+        })
+    );
+}
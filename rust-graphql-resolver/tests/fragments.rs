@@ -0,0 +1,138 @@
+mod common;
+
+use rust_graphql_resolver::{
+    builder::{
+        field::CustomTypeBuilder, mutation::MutationBuilder, query::QueryBuilder,
+        schema::SchemaBuilder, value::DataValueObjectBuilder,
+    },
+    error::{BuildResult, Error, Result},
+    schema::{
+        field::Field,
+        mutation::Mutation,
+        query::Query,
+        resolve::{BoxedValue, QLApiParam, QLContext, QLVariables},
+        Schema,
+    },
+    value::DataValue,
+};
+
+use common::{const_field, object, string};
+
+fn build_schema() -> BuildResult<Schema> {
+    SchemaBuilder::new("fragments_schema")
+        .add_object(
+            CustomTypeBuilder::new("Cat")
+                .add_field("name", const_field("name", "Tom"))
+                .add_field("sound", const_field("sound", "meow"))
+                .build(),
+        )
+        .add_object(
+            CustomTypeBuilder::new("Dog")
+                .add_field("sound", const_field("sound", "woof"))
+                .build(),
+        )
+        .add_query("cat", |sch| -> BuildResult<Query> {
+            QueryBuilder::new()
+                .set_type(sch.get_object_type("Cat")?)
+                .set_resolve(Box::new(
+                    |_context: &mut QLContext, _param: &QLApiParam| -> Result<BoxedValue> {
+                        Ok(DataValueObjectBuilder::new().build().to_boxed())
+                    },
+                ))
+                .build()
+        })?
+        .add_query("version", |_sch| -> BuildResult<Query> {
+            QueryBuilder::new()
+                .set_type(Field::basic_str().field_type)
+                .set_resolve(Box::new(
+                    |_context: &mut QLContext, _param: &QLApiParam| -> Result<BoxedValue> {
+                        Ok(Box::new("1.0".to_string()))
+                    },
+                ))
+                .build()
+        })?
+        .add_mutation("feed", |sch| -> BuildResult<Mutation> {
+            MutationBuilder::new()
+                .set_type(sch.get_object_type("Cat")?)
+                .set_resolve(Box::new(
+                    |_context: &mut QLContext, _param: &QLApiParam| -> Result<BoxedValue> {
+                        Ok(DataValueObjectBuilder::new().build().to_boxed())
+                    },
+                ))
+                .build()
+        })?
+        .build()
+}
+
+fn run(request: &str) -> Result<DataValue> {
+    common::run(&build_schema().unwrap(), request, QLVariables::default()).into_result()
+}
+
+#[test]
+fn test_inline_fragment_in_object() {
+    let result = run(r#"{ cat { name ... on Cat { sound } } }"#).unwrap();
+    assert_eq!(
+        result,
+        object(vec![(
+            "cat",
+            object(vec![("name", string("Tom")), ("sound", string("meow"))])
+        )])
+    );
+
+    let result = run(r#"{ cat { ... { name } } }"#).unwrap();
+    assert_eq!(
+        result,
+        object(vec![("cat", object(vec![("name", string("Tom"))]))])
+    );
+}
+
+#[test]
+fn test_type_condition_not_match() {
+    let result = run(r#"
+        { cat { name ... on Dog { sound } ...DogSound } }
+        fragment DogSound on Dog { sound }
+        "#)
+    .unwrap();
+    assert_eq!(
+        result,
+        object(vec![("cat", object(vec![("name", string("Tom"))]))])
+    );
+}
+
+#[test]
+fn test_fragments_at_root() {
+    let result = run(r#"
+        query { ...Root ... on Query { version } ... on Mutation { feed { name } } }
+        fragment Root on Query { cat { sound } }
+        "#)
+    .unwrap();
+    assert_eq!(
+        result,
+        object(vec![
+            ("cat", object(vec![("sound", string("meow"))])),
+            ("version", string("1.0")),
+        ])
+    );
+
+    let result = run(r#"{ ... { version } }"#).unwrap();
+    assert_eq!(result, object(vec![("version", string("1.0"))]));
+}
+
+#[test]
+fn test_fragments_in_mutation() {
+    let result = run(r#"
+        mutation { ... on Mutation { feed { ...CatSound } } }
+        fragment CatSound on Cat { sound }
+        "#)
+    .unwrap();
+    assert_eq!(
+        result,
+        object(vec![("feed", object(vec![("sound", string("meow"))]))])
+    );
+}
+
+#[test]
+fn test_no_such_fragment() {
+    let result = run(r#"{ ...Missing }"#);
+    assert!(matches!(result, Err(Error::NoSuchFragment(name)) if name == "Missing"));
+}
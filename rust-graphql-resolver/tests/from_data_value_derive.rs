@@ -0,0 +1,131 @@
+use std::collections::{BTreeMap, HashMap};
+
+use chrono::{TimeZone, Utc};
+use macros::GraphQLFromDataValue;
+use rust_graphql_resolver::{
+    ast::query::Value as ParserValue,
+    builder::value::DataValueObjectBuilder,
+    error::Result,
+    indexmap::IndexMap,
+    schema::resolve::ArgumentValueMap,
+    value::{DataValue, FromDataValue},
+};
+
+#[derive(Debug, PartialEq, GraphQLFromDataValue)]
+struct Range {
+    from: i32,
+    to: Option<i32>,
+}
+
+#[derive(Debug, PartialEq, GraphQLFromDataValue)]
+struct SearchInput {
+    name: String,
+    ratio: f64,
+    tags: Vec<String>,
+    ranges: Vec<Range>,
+    after: Option<chrono::DateTime<Utc>>,
+    extra: HashMap<String, bool>,
+}
+
+fn parse<T: FromDataValue>(value: DataValue) -> Result<T> {
+    T::from_data_value(&value)
+}
+
+fn range(from: i64, to: DataValue) -> DataValue {
+    DataValue::Object(IndexMap::from([
+        ("from".to_string(), DataValue::Int(from)),
+        ("to".to_string(), to),
+    ]))
+}
+
+#[test]
+fn test_parse_derived_struct() {
+    let value = DataValueObjectBuilder::new()
+        .add_str_field("name", "rust".to_string())
+        .add_int_field("ratio", 2)
+        .add_list_field("tags", vec![DataValue::ID("a".to_string())])
+        .add_list_field(
+            "ranges",
+            vec![range(1, DataValue::Int(2)), range(3, DataValue::Null)],
+        )
+        .add_str_field("after", "2021-01-02T03:04:05Z".to_string())
+        .add_object_field(
+            "extra",
+            DataValue::Object(IndexMap::from([(
+                "strict".to_string(),
+                DataValue::Boolean(true),
+            )])),
+        )
+        .build();
+    assert_eq!(
+        parse::<SearchInput>(value).unwrap(),
+        SearchInput {
+            name: "rust".to_string(),
+            ratio: 2.0,
+            tags: vec!["a".to_string()],
+            ranges: vec![
+                Range {
+                    from: 1,
+                    to: Some(2)
+                },
+                Range { from: 3, to: None }
+            ],
+            after: Some(Utc.ymd(2021, 1, 2).and_hms(3, 4, 5)),
+            extra: HashMap::from([("strict".to_string(), true)]),
+        }
+    );
+}
+
+#[test]
+fn test_parse_errors() {
+    let messages = vec![
+        parse::<Range>(DataValue::Int(1)).unwrap_err(),
+        parse::<Range>(DataValue::Object(IndexMap::new())).unwrap_err(),
+        parse::<Range>(range(1 << 40, DataValue::Null)).unwrap_err(),
+        parse::<Vec<Range>>(DataValue::List(vec![
+            range(1, DataValue::Null),
+            range(2, DataValue::String("3".to_string())),
+        ]))
+        .unwrap_err(),
+    ]
+    .into_iter()
+    .map(|err| err.to_string())
+    .collect::<Vec<_>>();
+    assert_eq!(
+        messages,
+        vec![
+            "DataTypeMisMatchError expect: Range, actul: Int",
+            "Cannot convert the value of 'from': DataTypeMisMatchError expect: Int, actul: Null",
+            "Cannot convert the value of 'from': DataTypeMisMatchError expect: i32, actul: Int(1099511627776)",
+            "Cannot convert the value of '1.to': DataTypeMisMatchError expect: Int, actul: String",
+        ]
+    );
+    assert_eq!(parse::<Option<Range>>(DataValue::Null).unwrap(), None);
+}
+
+#[test]
+fn test_parse_arguments() {
+    let arguments = ArgumentValueMap::from(vec![
+        ("limit".to_string(), ParserValue::Int(3.into())),
+        (
+            "range".to_string(),
+            ParserValue::Object(BTreeMap::from([(
+                "from".to_string(),
+                ParserValue::String("1".to_string()),
+            )])),
+        ),
+    ]);
+    assert_eq!(arguments.parse::<u8>("limit").unwrap(), 3);
+    assert_eq!(arguments.parse::<Option<String>>("missing").unwrap(), None);
+    assert_eq!(
+        arguments.parse::<Range>("range").unwrap_err().to_string(),
+        "Cannot convert the value of 'range.from': DataTypeMisMatchError expect: Int, actul: String"
+    );
+    assert_eq!(
+        arguments
+            .parse::<String>("missing")
+            .unwrap_err()
+            .to_string(),
+        "Cannot convert the value of 'missing': DataTypeMisMatchError expect: String, actul: Null"
+    );
+}
@@ -1,7 +1,11 @@
-use std::{array::IntoIter, collections::BTreeMap, iter::FromIterator};
+use std::iter::FromIterator;
 
-use macros::GraphQLDataValue;
-use rust_graphql_resolver::value::{DataValue, ToDataValue};
+use macros::{GraphQLDataValue, GraphQLFromDataValue};
+use rust_graphql_resolver::{
+    indexmap::IndexMap,
+    value::{DataValue, FromDataValue, ToDataValue},
+};
+use serde_json::json;
 
 #[derive(Debug, Clone, GraphQLDataValue)]
 struct HelloWorld {
@@ -9,6 +13,60 @@ struct HelloWorld {
     greeting: String,
 }
 
+#[derive(Debug, PartialEq, GraphQLDataValue, GraphQLFromDataValue)]
+struct Audit {
+    created_by: String,
+}
+
+#[derive(Debug, PartialEq, GraphQLDataValue, GraphQLFromDataValue)]
+#[graphql(rename_all = "camelCase")]
+struct Post {
+    post_id: UserId,
+    #[graphql(rename = "heading")]
+    title: String,
+    r#type: String,
+    #[graphql(skip)]
+    cache_key: u64,
+    #[graphql(flatten)]
+    audit: Audit,
+}
+
+#[derive(Debug, PartialEq, GraphQLDataValue, GraphQLFromDataValue)]
+struct UserId(String);
+
+#[derive(Debug, GraphQLDataValue)]
+struct Point(i32, #[graphql(skip)] i32, i32);
+
+#[derive(Debug, PartialEq, GraphQLDataValue, GraphQLFromDataValue)]
+struct Nothing;
+
+#[derive(Debug, PartialEq, GraphQLDataValue, GraphQLFromDataValue)]
+struct Page<T> {
+    items: Vec<T>,
+    total: i64,
+}
+
+#[derive(Debug, GraphQLDataValue)]
+struct Secret {
+    #[graphql(with = "masked")]
+    password: String,
+}
+
+fn masked(password: &str) -> DataValue {
+    DataValue::String("*".repeat(password.len()))
+}
+
+#[derive(Debug, Clone, GraphQLDataValue)]
+#[graphql(rename_all = "SCREAMING_SNAKE_CASE")]
+enum Status {
+    InReview,
+    Published,
+}
+
+fn to_json<T: ToDataValue>(value: &T) -> serde_json::Value {
+    serde_json::to_value(value.to_data_value()).unwrap()
+}
+
 #[test]
 fn test_generate_datavalue_derive() {
     let h = HelloWorld {
@@ -18,7 +76,7 @@ fn test_generate_datavalue_derive() {
     let dv = h.to_data_value();
     assert_eq!(
         dv,
-        DataValue::Object(BTreeMap::from_iter(IntoIter::new([
+        DataValue::Object(IndexMap::from_iter(IntoIterator::into_iter([
             ("hello".to_string(), DataValue::String("hello".to_string())),
             (
                 "greeting".to_string(),
@@ -27,3 +85,69 @@ fn test_generate_datavalue_derive() {
         ])))
     )
 }
+
+#[test]
+fn test_renamed_fields() {
+    let post = Post {
+        post_id: UserId("p1".to_string()),
+        title: "hello".to_string(),
+        r#type: "note".to_string(),
+        cache_key: 42,
+        audit: Audit {
+            created_by: "ann".to_string(),
+        },
+    };
+    assert_eq!(
+        to_json(&post),
+        json!({ "postId": "p1", "heading": "hello", "type": "note", "created_by": "ann" })
+    );
+    assert_eq!(
+        Post::from_data_value(&post.to_data_value()).unwrap(),
+        Post {
+            cache_key: 0,
+            ..post
+        }
+    );
+    assert_eq!(to_json(&Status::InReview), json!("IN_REVIEW"));
+    assert_eq!(to_json(&Status::Published), json!("PUBLISHED"));
+}
+
+#[test]
+fn test_struct_shapes() {
+    assert_eq!(to_json(&UserId("u1".to_string())), json!("u1"));
+    let point = Point(1, 2, 3);
+    assert_eq!(point.1, 2);
+    assert_eq!(to_json(&point), json!([1, 3]));
+    assert_eq!(to_json(&Nothing), json!(null));
+    assert_eq!(Nothing::from_data_value(&DataValue::Null).unwrap(), Nothing);
+    assert_eq!(
+        to_json(&Secret {
+            password: "hunter2".to_string()
+        }),
+        json!({ "password": "*******" })
+    );
+}
+
+#[test]
+fn test_generic_struct() {
+    let page = Page {
+        items: vec![UserId("a".to_string()), UserId("b".to_string())],
+        total: 2,
+    };
+    assert_eq!(to_json(&page), json!({ "items": ["a", "b"], "total": 2 }));
+
+    let value = DataValue::Object(IndexMap::from([
+        (
+            "items".to_string(),
+            DataValue::List(vec![DataValue::Int(1)]),
+        ),
+        ("total".to_string(), DataValue::Int(1)),
+    ]));
+    assert_eq!(
+        Page::<i32>::from_data_value(&value).unwrap(),
+        Page {
+            items: vec![1],
+            total: 1
+        }
+    );
+}
@@ -0,0 +1,224 @@
+mod common;
+
+use macros::{GraphQLDataValue, GraphQLObject};
+use rust_graphql_resolver::{
+    builder::{query::QueryBuilder, schema::SchemaBuilder},
+    error::{BuildResult, Result},
+    schema::{
+        query::Query,
+        resolve::{BoxedValue, FieldResolveFunc, QLApiParam, QLContext, QLVariables},
+        Schema,
+    },
+    value::{DataValue, ToDataValue},
+};
+use serde_json::json;
+
+use common::run_json;
+
+/// a pet of a user
+#[derive(Debug, Clone, GraphQLDataValue, GraphQLObject)]
+struct Pet {
+    name: String,
+    age: Option<i32>,
+}
+
+#[derive(Debug, Clone, GraphQLDataValue, GraphQLObject)]
+#[graphql(rename = "Owner", description = "someone with pets")]
+struct User {
+    /// the user name
+    name: String,
+    #[graphql(rename = "nickNames")]
+    nick_names: Vec<Option<String>>,
+    pets: Vec<Pet>,
+    best_friend: Option<Pet>,
+    #[graphql(resolve = "shout_resolve")]
+    shout: Option<String>,
+}
+
+fn shout_resolve() -> Box<dyn FieldResolveFunc> {
+    Box::new(
+        |_context: &mut QLContext, source: &DataValue, _param: &QLApiParam| -> Result<BoxedValue> {
+            let shout = match source {
+                DataValue::Object(user) => match user.get("name") {
+                    Some(DataValue::String(name)) => name.to_uppercase(),
+                    _ => String::default(),
+                },
+                _ => String::default(),
+            };
+            Ok(Box::new(shout))
+        },
+    )
+}
+
+fn build_schema() -> BuildResult<Schema> {
+    SchemaBuilder::new("graphql_object_schema")
+        .add_object_type::<Pet>()?
+        .add_object_type::<User>()?
+        .add_query("me", |sch| -> BuildResult<Query> {
+            QueryBuilder::new()
+                .set_type(sch.get_object_type("Owner")?)
+                .set_resolve(Box::new(
+                    |_context: &mut QLContext, _param: &QLApiParam| -> Result<BoxedValue> {
+                        let mut user = match (User {
+                            name: "ann".to_string(),
+                            nick_names: vec![Some("a".to_string()), None],
+                            pets: vec![Pet {
+                                name: "rex".to_string(),
+                                age: Some(3),
+                            }],
+                            best_friend: None,
+                            shout: None,
+                        })
+                        .to_data_value()
+                        {
+                            DataValue::Object(user) => user,
+                            _ => unreachable!(),
+                        };
+                        // let `shout` be resolved from the source
+                        user.shift_remove("shout");
+                        Ok(Box::new(DataValue::Object(user)))
+                    },
+                ))
+                .build()
+        })?
+        .build()
+}
+
+fn run(request: &str) -> serde_json::Value {
+    run_json(&build_schema().unwrap(), request, QLVariables::default())
+}
+
+#[test]
+fn test_derived_object_introspection() {
+    let result = run(r#"{
+            __type(name: "Owner") {
+                name
+                description
+                fields { name description type { kind name ofType { kind name ofType { kind name } } } }
+            }
+        }"#);
+    assert_eq!(
+        result,
+        json!({
+            "data": {
+                "__type": {
+                    "name": "Owner",
+                    "description": "someone with pets",
+                    "fields": [
+                        {
+                            "name": "best_friend",
+                            "description": null,
+                            "type": { "kind": "OBJECT", "name": "Pet", "ofType": null },
+                        },
+                        {
+                            "name": "name",
+                            "description": "the user name",
+                            "type": {
+                                "kind": "NON_NULL",
+                                "name": null,
+                                "ofType": { "kind": "SCALAR", "name": "String", "ofType": null },
+                            },
+                        },
+                        {
+                            "name": "nickNames",
+                            "description": null,
+                            "type": {
+                                "kind": "NON_NULL",
+                                "name": null,
+                                "ofType": {
+                                    "kind": "LIST",
+                                    "name": null,
+                                    "ofType": { "kind": "SCALAR", "name": "String" },
+                                },
+                            },
+                        },
+                        {
+                            "name": "pets",
+                            "description": null,
+                            "type": {
+                                "kind": "NON_NULL",
+                                "name": null,
+                                "ofType": {
+                                    "kind": "LIST",
+                                    "name": null,
+                                    "ofType": { "kind": "NON_NULL", "name": null },
+                                },
+                            },
+                        },
+                        {
+                            "name": "shout",
+                            "description": null,
+                            "type": { "kind": "SCALAR", "name": "String", "ofType": null },
+                        },
+                    ],
+                },
+            },
+        })
+    );
+
+    let result = run(r#"{ __type(name: "Pet") { description } }"#);
+    assert_eq!(
+        result,
+        json!({ "data": { "__type": { "description": "a pet of a user" } } })
+    );
+}
+
+#[test]
+fn test_derived_object_resolve() {
+    let result = run(r#"{ me { name shout pets { name age } } }"#);
+    assert_eq!(result["errors"], serde_json::Value::Null, "{}", result);
+    assert_eq!(result["data"]["me"]["shout"], json!("ANN"));
+    assert_eq!(
+        result["data"]["me"]["pets"],
+        json!([{ "name": "rex", "age": 3 }])
+    );
+}
+
+#[derive(Debug, Clone, GraphQLDataValue, GraphQLObject)]
+struct Person {
+    name: String,
+    friends: Vec<Person>,
+}
+
+#[test]
+fn test_self_referential_object() {
+    let schema = SchemaBuilder::new("self_referential_schema")
+        .add_object_type::<Person>()
+        .unwrap()
+        .add_query("person", |sch| -> BuildResult<Query> {
+            QueryBuilder::new()
+                .set_type(sch.get_object_type("Person")?)
+                .set_resolve(Box::new(
+                    |_context: &mut QLContext, _param: &QLApiParam| -> Result<BoxedValue> {
+                        let bob = Person {
+                            name: "bob".to_string(),
+                            friends: vec![],
+                        };
+                        Ok(Box::new(Person {
+                            name: "ann".to_string(),
+                            friends: vec![bob],
+                        }))
+                    },
+                ))
+                .build()
+        })
+        .unwrap()
+        .build()
+        .unwrap();
+    let result = run_json(
+        &schema,
+        "{ person { name friends { name friends { name } } } }",
+        QLVariables::default(),
+    );
+    assert_eq!(
+        result,
+        json!({
+            "data": {
+                "person": {
+                    "name": "ann",
+                    "friends": [{ "name": "bob", "friends": [] }],
+                },
+            },
+        })
+    );
+}
@@ -0,0 +1,306 @@
+mod common;
+
+use rust_graphql_resolver::{
+    builder::{
+        field::{CustomTypeBuilder, QLEnumBuilder, QLInputBuilder},
+        mutation::MutationBuilder,
+        query::QueryBuilder,
+        schema::SchemaBuilder,
+        value::DataValueObjectBuilder,
+    },
+    error::{BuildResult, Result},
+    schema::{
+        field::{CustomType, Field, FieldType, InputField, InputFieldType, QLInput},
+        mutation::Mutation,
+        query::Query,
+        resolve::{BoxedValue, QLApiParam, QLContext, QLVariables},
+        Schema,
+    },
+    value::DataValue,
+};
+
+use common::{non_null, object, string};
+
+fn build_schema() -> BuildResult<Schema> {
+    SchemaBuilder::new("introspection_schema")
+        .add_enum(
+            QLEnumBuilder::new("Role")
+                .set_description("role of a user")
+                .add_value_with_desc("Admin", "can do anything")
+                .add_value("Guest")
+                .build(),
+        )
+        .add_object_with_status(|sch| -> BuildResult<CustomType> {
+            CustomTypeBuilder::new("User")
+                .set_description("a user")
+                .add_field(
+                    "id",
+                    Field::simple("id", non_null(Field::basic_id().field_type)),
+                )
+                .add_field(
+                    "name",
+                    Field::simple_with_description(
+                        "name",
+                        Field::basic_str().field_type,
+                        "name of user",
+                    ),
+                )
+                .add_field("role", Field::simple("role", sch.get_enum_type("Role")?))
+                .build_ok()
+        })?
+        .add_input_object_with_status(|sch| -> BuildResult<QLInput> {
+            QLInputBuilder::new("UserInput")
+                .add_field("name", InputField::basic_str())
+                .add_field(
+                    "role",
+                    InputField::simple("role", sch.get_enum_input_type("Role")?),
+                )
+                .build_ok()
+        })?
+        .add_query("users", |sch| -> BuildResult<Query> {
+            QueryBuilder::new()
+                .set_type(FieldType::List(Box::new(sch.get_object_type("User")?)))
+                .set_description("all users")
+                .add_argument("first", InputFieldType::basic_int())
+                .set_resolve(Box::new(
+                    |_context: &mut QLContext, _param: &QLApiParam| -> Result<BoxedValue> {
+                        Ok(Box::new(vec![DataValueObjectBuilder::new()
+                            .add_id_field("id", "1".to_string())
+                            .add_str_field("name", "Alice".to_string())
+                            .add_str_field("role", "Admin".to_string())
+                            .build()]))
+                    },
+                ))
+                .build()
+        })?
+        .add_mutation("addUser", |sch| -> BuildResult<Mutation> {
+            MutationBuilder::new()
+                .set_type(sch.get_object_type("User")?)
+                .add_argument("user", sch.get_input_type("UserInput")?)
+                .set_resolve(Box::new(
+                    |_context: &mut QLContext, _param: &QLApiParam| -> Result<BoxedValue> {
+                        Ok(DataValueObjectBuilder::new()
+                            .add_id_field("id", "2".to_string())
+                            .build()
+                            .to_boxed())
+                    },
+                ))
+                .build()
+        })?
+        .build()
+}
+
+fn run(request: &str) -> DataValue {
+    common::run(&build_schema().unwrap(), request, QLVariables::default())
+        .into_result()
+        .unwrap()
+}
+
+fn get<'a>(value: &'a DataValue, key: &str) -> &'a DataValue {
+    match value {
+        DataValue::Object(map) => map.get(key).unwrap(),
+        other => panic!("expect object, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_schema_root_types() {
+    let result = run(
+        r#"{ __schema { queryType { name } mutationType { name } subscriptionType { name } } }"#,
+    );
+    assert_eq!(
+        get(&result, "__schema"),
+        &object(vec![
+            ("queryType", object(vec![("name", string("Query"))])),
+            ("mutationType", object(vec![("name", string("Mutation"))])),
+            ("subscriptionType", DataValue::Null),
+        ])
+    );
+}
+
+#[test]
+fn test_schema_types() {
+    let result = run(r#"{ __schema { types { name kind } } }"#);
+    let types = match get(get(&result, "__schema"), "types") {
+        DataValue::List(list) => list.clone(),
+        other => panic!("expect list, got {:?}", other),
+    };
+    for (name, kind) in [
+        ("Query", "OBJECT"),
+        ("Mutation", "OBJECT"),
+        ("User", "OBJECT"),
+        ("Role", "ENUM"),
+        ("UserInput", "INPUT_OBJECT"),
+        ("String", "SCALAR"),
+        ("__Type", "OBJECT"),
+        ("__TypeKind", "ENUM"),
+    ] {
+        let expect = object(vec![("kind", string(kind)), ("name", string(name))]);
+        assert!(types.contains(&expect), "missing type {}", name);
+    }
+}
+
+#[test]
+fn test_type_fields() {
+    let result = run(r#"{
+            __type(name: "User") {
+                kind
+                name
+                description
+                fields { name description type { kind name ofType { kind name } } }
+            }
+        }"#);
+    let scalar = |name: &str| object(vec![("kind", string("SCALAR")), ("name", string(name))]);
+    assert_eq!(
+        get(&result, "__type"),
+        &object(vec![
+            ("kind", string("OBJECT")),
+            ("name", string("User")),
+            ("description", string("a user")),
+            (
+                "fields",
+                DataValue::List(vec![
+                    object(vec![
+                        ("name", string("id")),
+                        ("description", DataValue::Null),
+                        (
+                            "type",
+                            object(vec![
+                                ("kind", string("NON_NULL")),
+                                ("name", DataValue::Null),
+                                ("ofType", scalar("ID")),
+                            ]),
+                        ),
+                    ]),
+                    object(vec![
+                        ("name", string("name")),
+                        ("description", string("name of user")),
+                        (
+                            "type",
+                            object(vec![
+                                ("kind", string("SCALAR")),
+                                ("name", string("String")),
+                                ("ofType", DataValue::Null),
+                            ]),
+                        ),
+                    ]),
+                    object(vec![
+                        ("name", string("role")),
+                        ("description", DataValue::Null),
+                        (
+                            "type",
+                            object(vec![
+                                ("kind", string("ENUM")),
+                                ("name", string("Role")),
+                                ("ofType", DataValue::Null),
+                            ]),
+                        ),
+                    ]),
+                ]),
+            ),
+        ])
+    );
+}
+
+#[test]
+fn test_type_enum_values_and_input_fields() {
+    let result = run(r#"{
+            role: __type(name: "Role") { description enumValues { name description isDeprecated } fields { name } }
+            input: __type(name: "UserInput") { inputFields { name type { name } defaultValue } }
+            missing: __type(name: "Missing") { name }
+        }"#);
+    assert_eq!(
+        get(&result, "role"),
+        &object(vec![
+            ("description", string("role of a user")),
+            (
+                "enumValues",
+                DataValue::List(vec![
+                    object(vec![
+                        ("name", string("Admin")),
+                        ("description", string("can do anything")),
+                        ("isDeprecated", DataValue::Boolean(false)),
+                    ]),
+                    object(vec![
+                        ("name", string("Guest")),
+                        ("description", DataValue::Null),
+                        ("isDeprecated", DataValue::Boolean(false)),
+                    ]),
+                ]),
+            ),
+            ("fields", DataValue::Null),
+        ])
+    );
+    assert_eq!(
+        get(&result, "input"),
+        &object(vec![(
+            "inputFields",
+            DataValue::List(vec![
+                object(vec![
+                    ("name", string("name")),
+                    ("type", object(vec![("name", string("String"))])),
+                    ("defaultValue", DataValue::Null),
+                ]),
+                object(vec![
+                    ("name", string("role")),
+                    ("type", object(vec![("name", string("Role"))])),
+                    ("defaultValue", DataValue::Null),
+                ]),
+            ]),
+        )])
+    );
+    assert_eq!(get(&result, "missing"), &DataValue::Null);
+}
+
+#[test]
+fn test_query_root_arguments_with_fragment() {
+    let result = run(r#"
+        query {
+            __type(name: "Query") { ...TypeInfo }
+        }
+        fragment TypeInfo on __Type {
+            name
+            fields { name description args { name type { name } } }
+        }
+        "#);
+    assert_eq!(
+        get(&result, "__type"),
+        &object(vec![
+            ("name", string("Query")),
+            (
+                "fields",
+                DataValue::List(vec![object(vec![
+                    ("name", string("users")),
+                    ("description", string("all users")),
+                    (
+                        "args",
+                        DataValue::List(vec![object(vec![
+                            ("name", string("first")),
+                            ("type", object(vec![("name", string("Int"))])),
+                        ])]),
+                    ),
+                ])]),
+            ),
+        ])
+    );
+}
+
+#[test]
+fn test_typename() {
+    let result = run(r#"{ __typename users { __typename name } }"#);
+    assert_eq!(get(&result, "__typename"), &string("Query"));
+    assert_eq!(
+        get(&result, "users"),
+        &DataValue::List(vec![object(vec![
+            ("__typename", string("User")),
+            ("name", string("Alice")),
+        ])])
+    );
+
+    let result = run(r#"mutation { __typename addUser(user: {name: "Bob"}) { __typename } }"#);
+    assert_eq!(get(&result, "__typename"), &string("Mutation"));
+    assert_eq!(
+        get(&result, "addUser"),
+        &object(vec![("__typename", string("User"))])
+    );
+}
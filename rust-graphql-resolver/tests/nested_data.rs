@@ -0,0 +1,183 @@
+mod common;
+
+use rust_graphql_resolver::{
+    builder::{
+        field::CustomTypeBuilder, query::QueryBuilder, schema::SchemaBuilder,
+        value::DataValueObjectBuilder,
+    },
+    error::{BuildResult, Result},
+    schema::{
+        field::{Field, FieldType},
+        query::Query,
+        resolve::{BoxedValue, QLApiParam, QLContext, QLVariables},
+        Schema,
+    },
+    value::DataValue,
+};
+use serde_json::json;
+
+use common::{non_null, run_json};
+
+fn pet(name: DataValue, weight: DataValue) -> DataValue {
+    DataValueObjectBuilder::new()
+        .add_any_field("name", name)
+        .add_any_field("weight", weight)
+        .add_str_field("secret", "hidden".to_string())
+        .build()
+}
+
+fn owner(pet_name: DataValue) -> DataValue {
+    DataValueObjectBuilder::new()
+        .add_id_field("id", "o1".to_string())
+        .add_str_field("name", "Ann".to_string())
+        // weights given as Int are widened to Float
+        .add_object_field("pet", pet(pet_name, DataValue::Int(3)))
+        .add_list_field(
+            "pets",
+            vec![
+                pet(DataValue::String("Rex".to_string()), DataValue::Float(1.5)),
+                pet(DataValue::String("Tom".to_string()), DataValue::Int(2)),
+            ],
+        )
+        .build()
+}
+
+fn owner_query(field_type: FieldType, pet_name: DataValue) -> BuildResult<Query> {
+    QueryBuilder::new()
+        .set_type(field_type)
+        .set_resolve(Box::new(
+            move |_context: &mut QLContext, _param: &QLApiParam| -> Result<BoxedValue> {
+                Ok(Box::new(owner(pet_name.clone())))
+            },
+        ))
+        .build()
+}
+
+fn build_schema() -> BuildResult<Schema> {
+    let builder = SchemaBuilder::new("nested_data_schema").add_object(
+        CustomTypeBuilder::new("Pet")
+            .add_field("name", Field::basic_str())
+            .add_field("weight", Field::basic_float())
+            // no pet data has these keys
+            .add_field("nickname", Field::basic_str())
+            .add_field(
+                "tag",
+                Field::simple("tag", non_null(Field::basic_str().field_type)),
+            )
+            .add_field(
+                "greeting",
+                Field::simple_with_resolve(
+                    "greeting",
+                    Field::basic_str().field_type,
+                    Box::new(
+                        |_context: &mut QLContext,
+                         source: &DataValue,
+                         _param: &QLApiParam|
+                         -> Result<BoxedValue> {
+                            match source {
+                                DataValue::Object(map) => match map.get("name") {
+                                    Some(DataValue::String(name)) => {
+                                        Ok(Box::new(format!("hi {}", name)))
+                                    }
+                                    _ => Ok(DataValue::boxed_null()),
+                                },
+                                _ => Ok(DataValue::boxed_null()),
+                            }
+                        },
+                    ),
+                ),
+            )
+            .build(),
+    );
+    let pet_ref = builder.get_object_type("Pet")?;
+    builder
+        .add_object(
+            CustomTypeBuilder::new("Owner")
+                .add_field("id", Field::basic_id())
+                .add_field("name", Field::basic_str())
+                .add_field("pet", Field::simple("pet", pet_ref.clone()))
+                .add_field(
+                    "pets",
+                    Field::simple("pets", FieldType::List(Box::new(pet_ref))),
+                )
+                .build(),
+        )
+        .add_query("owner", |sch| {
+            owner_query(
+                sch.get_object_type("Owner")?,
+                DataValue::String("Bob".to_string()),
+            )
+        })?
+        .add_query("badOwner", |sch| {
+            owner_query(sch.get_object_type("Owner")?, DataValue::Boolean(true))
+        })?
+        .build()
+}
+
+fn run(request: &str) -> serde_json::Value {
+    run_json(&build_schema().unwrap(), request, QLVariables::default())
+}
+
+#[test]
+fn test_nested_object_selection() {
+    assert_eq!(
+        run("{ owner { id pet { name n: name greeting } } }"),
+        json!({
+            "data": {
+                "owner": {
+                    "id": "o1",
+                    "pet": { "name": "Bob", "n": "Bob", "greeting": "hi Bob" },
+                },
+            },
+        })
+    );
+}
+
+#[test]
+fn test_nested_list_selection() {
+    assert_eq!(
+        run("{ owner { pets { weight } pet { weight __typename } } }"),
+        json!({
+            "data": {
+                "owner": {
+                    "pets": [{ "weight": 1.5 }, { "weight": 2.0 }],
+                    "pet": { "weight": 3.0, "__typename": "Pet" },
+                },
+            },
+        })
+    );
+}
+
+#[test]
+fn test_nested_leaf_mismatch() {
+    assert_eq!(
+        run("{ badOwner { name pet { name } } }"),
+        json!({
+            "data": { "badOwner": { "name": "Ann", "pet": { "name": null } } },
+            "errors": [{
+                "message": "DataTypeMisMatchError expect: String, actul: Boolean(true)",
+                "locations": [{ "line": 1, "column": 25 }],
+                "path": ["badOwner", "pet", "name"],
+            }],
+        })
+    );
+}
+
+#[test]
+fn test_missing_source_keys() {
+    assert_eq!(
+        run("{ owner { pet { name nickname } } }"),
+        json!({ "data": { "owner": { "pet": { "name": "Bob", "nickname": null } } } })
+    );
+    assert_eq!(
+        run("{ owner { name pet { name tag } } }"),
+        json!({
+            "data": { "owner": { "name": "Ann", "pet": null } },
+            "errors": [{
+                "message": "Cannot return null for non-nullable type String!",
+                "locations": [{ "line": 1, "column": 27 }],
+                "path": ["owner", "pet", "tag"],
+            }],
+        })
+    );
+}
@@ -0,0 +1,201 @@
+mod common;
+
+use rust_graphql_resolver::{
+    builder::{
+        field::CustomTypeBuilder, query::QueryBuilder, schema::SchemaBuilder,
+        value::DataValueObjectBuilder,
+    },
+    error::{BuildResult, Result},
+    schema::{
+        field::{CustomType, Field, FieldType},
+        query::Query,
+        resolve::{BoxedValue, QLApiParam, QLContext, QLVariables},
+        Schema,
+    },
+    value::DataValue,
+};
+use serde_json::json;
+
+use common::{non_null, run_json};
+
+fn person(id: &str) -> DataValue {
+    DataValueObjectBuilder::new()
+        .add_id_field("id", id.to_string())
+        .build()
+}
+
+fn people() -> DataValue {
+    DataValue::List(vec![person("a"), person("bad"), person("b")])
+}
+
+fn name_field() -> Field {
+    Field::simple_with_resolve(
+        "name",
+        non_null(Field::basic_str().field_type),
+        Box::new(
+            |_context: &mut QLContext,
+             source: &DataValue,
+             _param: &QLApiParam|
+             -> Result<BoxedValue> {
+                match source {
+                    DataValue::Object(map) => match map.get("id") {
+                        Some(DataValue::ID(id)) if id != "bad" => Ok(Box::new(id.to_uppercase())),
+                        _ => Ok(DataValue::boxed_null()),
+                    },
+                    _ => Ok(DataValue::boxed_null()),
+                }
+            },
+        ),
+    )
+}
+
+fn friends_field(name: &str, field_type: FieldType) -> Field {
+    Field::simple_with_resolve(
+        name,
+        field_type,
+        Box::new(
+            |_context: &mut QLContext,
+             _source: &DataValue,
+             _param: &QLApiParam|
+             -> Result<BoxedValue> { Ok(Box::new(people())) },
+        ),
+    )
+}
+
+fn person_type(sch: &SchemaBuilder) -> BuildResult<CustomType> {
+    let friend_ref = sch.get_object_type("Friend")?;
+    CustomTypeBuilder::new("Person")
+        .add_field("id", Field::basic_id())
+        .add_field("name", name_field())
+        .add_field(
+            "nickname",
+            Field::simple_with_resolve(
+                "nickname",
+                Field::basic_str().field_type,
+                Box::new(
+                    |_context: &mut QLContext,
+                     _source: &DataValue,
+                     _param: &QLApiParam|
+                     -> Result<BoxedValue> { Ok(DataValue::boxed_null()) },
+                ),
+            ),
+        )
+        .add_field(
+            "friends",
+            friends_field("friends", FieldType::List(Box::new(friend_ref.clone()))),
+        )
+        .add_field(
+            "strictFriends",
+            friends_field(
+                "strictFriends",
+                FieldType::List(Box::new(non_null(friend_ref))),
+            ),
+        )
+        .build_ok()
+}
+
+fn person_query(field_type: FieldType, id: &'static str) -> BuildResult<Query> {
+    QueryBuilder::new()
+        .set_type(field_type)
+        .set_resolve(Box::new(
+            move |_context: &mut QLContext, _param: &QLApiParam| -> Result<BoxedValue> {
+                Ok(Box::new(person(id)))
+            },
+        ))
+        .build()
+}
+
+fn build_schema() -> BuildResult<Schema> {
+    let builder = SchemaBuilder::new("null_propagation_schema").add_object(
+        CustomTypeBuilder::new("Friend")
+            .add_field("id", Field::basic_id())
+            .add_field("name", name_field())
+            .build(),
+    );
+    let person = person_type(&builder)?;
+    let builder = builder.add_object(person);
+    builder
+        .add_query("person", |sch| {
+            person_query(sch.get_object_type("Person")?, "a")
+        })?
+        .add_query("badPerson", |sch| {
+            person_query(sch.get_object_type("Person")?, "bad")
+        })?
+        .add_query("mustBadPerson", |sch| {
+            person_query(non_null(sch.get_object_type("Person")?), "bad")
+        })?
+        .build()
+}
+
+fn run(request: &str) -> serde_json::Value {
+    run_json(&build_schema().unwrap(), request, QLVariables::default())
+}
+
+#[test]
+fn test_nullable_field_accepts_null() {
+    let result = run("{ person { name nickname } }");
+    assert_eq!(
+        result,
+        json!({ "data": { "person": { "name": "A", "nickname": null } } })
+    );
+}
+
+#[test]
+fn test_non_null_field_nulls_parent() {
+    let result = run("{ person { name } badPerson { name } }");
+    assert_eq!(
+        result,
+        json!({
+            "data": { "person": { "name": "A" }, "badPerson": null },
+            "errors": [{
+                "message": "Cannot return null for non-nullable type String!",
+                "locations": [{ "line": 1, "column": 31 }],
+                "path": ["badPerson", "name"],
+            }],
+        })
+    );
+}
+
+#[test]
+fn test_non_null_root_field_nulls_data() {
+    let result = run("{ person { name } mustBadPerson { name } }");
+    assert_eq!(
+        result,
+        json!({
+            "data": null,
+            "errors": [{
+                "message": "Cannot return null for non-nullable type String!",
+                "locations": [{ "line": 1, "column": 35 }],
+                "path": ["mustBadPerson", "name"],
+            }],
+        })
+    );
+}
+
+#[test]
+fn test_list_items() {
+    let result = run("{ person { friends { name } strictFriends { name } } }");
+    assert_eq!(
+        result,
+        json!({
+            "data": {
+                "person": {
+                    "friends": [{ "name": "A" }, null, { "name": "B" }],
+                    "strictFriends": null,
+                },
+            },
+            "errors": [
+                {
+                    "message": "Cannot return null for non-nullable type String!",
+                    "locations": [{ "line": 1, "column": 22 }],
+                    "path": ["person", "friends", 1, "name"],
+                },
+                {
+                    "message": "Cannot return null for non-nullable type String!",
+                    "locations": [{ "line": 1, "column": 45 }],
+                    "path": ["person", "strictFriends", 1, "name"],
+                },
+            ],
+        })
+    );
+}
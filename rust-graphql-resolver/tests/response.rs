@@ -0,0 +1,236 @@
+mod common;
+
+use rust_graphql_resolver::{
+    builder::{
+        field::CustomTypeBuilder, query::QueryBuilder, schema::SchemaBuilder,
+        value::DataValueObjectBuilder,
+    },
+    error::{BuildResult, Error, Result},
+    response::{Location, PathSegment},
+    schema::{
+        field::{Field, FieldType},
+        query::Query,
+        resolve::{BoxedValue, QLApiParam, QLContext, QLVariables},
+        Schema,
+    },
+    value::DataValue,
+};
+use serde_json::json;
+
+use common::run_json;
+
+fn build_schema() -> BuildResult<Schema> {
+    SchemaBuilder::new("response_schema")
+        .add_object(
+            CustomTypeBuilder::new("Item")
+                .add_field("id", Field::basic_id())
+                .add_field(
+                    "broken",
+                    Field::simple_with_resolve(
+                        "broken",
+                        Field::basic_str().field_type,
+                        Box::new(
+                            |_context: &mut QLContext,
+                             _source: &DataValue,
+                             _param: &QLApiParam|
+                             -> Result<BoxedValue> {
+                                Err(Error::NotFoundError("broken value".to_string()))
+                            },
+                        ),
+                    ),
+                )
+                .build(),
+        )
+        .add_object(
+            CustomTypeBuilder::new("User")
+                .add_field("name", Field::basic_str())
+                .add_field("email", Field::basic_str())
+                .add_field(
+                    "greeting",
+                    Field::simple_with_resolve(
+                        "greeting",
+                        Field::basic_str().field_type,
+                        Box::new(
+                            |_context: &mut QLContext,
+                             _source: &DataValue,
+                             _param: &QLApiParam|
+                             -> Result<BoxedValue> {
+                                Ok(Box::new("hi".to_string()))
+                            },
+                        ),
+                    ),
+                )
+                .build(),
+        )
+        .add_query("items", |sch| -> BuildResult<Query> {
+            QueryBuilder::new()
+                .set_type(FieldType::List(Box::new(sch.get_object_type("Item")?)))
+                .set_resolve(Box::new(
+                    |_context: &mut QLContext, _param: &QLApiParam| -> Result<BoxedValue> {
+                        Ok(Box::new(vec![
+                            DataValueObjectBuilder::new()
+                                .add_id_field("id", "1".to_string())
+                                .build(),
+                            DataValueObjectBuilder::new()
+                                .add_id_field("id", "2".to_string())
+                                .build(),
+                        ]))
+                    },
+                ))
+                .build()
+        })?
+        .add_query("hello", |_sch| -> BuildResult<Query> {
+            QueryBuilder::new()
+                .set_type(Field::basic_str().field_type)
+                .set_resolve(Box::new(
+                    |_context: &mut QLContext, _param: &QLApiParam| -> Result<BoxedValue> {
+                        Ok(Box::new("world".to_string()))
+                    },
+                ))
+                .build()
+        })?
+        .add_query("fail", |_sch| -> BuildResult<Query> {
+            QueryBuilder::new()
+                .set_type(Field::basic_str().field_type)
+                .set_resolve(Box::new(
+                    |_context: &mut QLContext, _param: &QLApiParam| -> Result<BoxedValue> {
+                        Err(Error::NotFoundError("fail value".to_string()))
+                    },
+                ))
+                .build()
+        })?
+        .add_query("me", |sch| -> BuildResult<Query> {
+            QueryBuilder::new()
+                .set_type(sch.get_object_type("User")?)
+                .set_resolve(Box::new(
+                    |_context: &mut QLContext, _param: &QLApiParam| -> Result<BoxedValue> {
+                        // `password` is not a field of User, it must never be returned
+                        Ok(Box::new(
+                            DataValueObjectBuilder::new()
+                                .add_str_field("password", "secret".to_string())
+                                .add_str_field("email", "ann@example.com".to_string())
+                                .add_str_field("name", "Ann".to_string())
+                                .build(),
+                        ))
+                    },
+                ))
+                .build()
+        })?
+        .build()
+}
+
+fn run(request: &str) -> serde_json::Value {
+    run_json(&build_schema().unwrap(), request, QLVariables::default())
+}
+
+/// the serialized data, to check the order of the fields
+fn data(request: &str) -> String {
+    let response = common::run(&build_schema().unwrap(), request, QLVariables::default());
+    serde_json::to_string(&response.data).unwrap()
+}
+
+#[test]
+fn test_failed_root_field_is_null() {
+    let result = run("{ hello\n  fail }");
+    assert_eq!(
+        result,
+        json!({
+            "data": { "hello": "world", "fail": null },
+            "errors": [{
+                "message": "NotFound: fail value",
+                "locations": [{ "line": 2, "column": 3 }],
+                "path": ["fail"],
+            }],
+        })
+    );
+}
+
+#[test]
+fn test_failed_nested_field_path() {
+    let result = run("{ list: items { id broken } }");
+    assert_eq!(
+        result,
+        json!({
+            "data": {
+                "list": [
+                    { "id": "1", "broken": null },
+                    { "id": "2", "broken": null },
+                ],
+            },
+            "errors": [
+                {
+                    "message": "NotFound: broken value",
+                    "locations": [{ "line": 1, "column": 20 }],
+                    "path": ["list", 0, "broken"],
+                },
+                {
+                    "message": "NotFound: broken value",
+                    "locations": [{ "line": 1, "column": 20 }],
+                    "path": ["list", 1, "broken"],
+                },
+            ],
+        })
+    );
+}
+
+#[test]
+fn test_request_error_without_data() {
+    let result = run("{ hello ");
+    assert!(result.get("data").is_none());
+    assert_eq!(result["errors"].as_array().unwrap().len(), 1);
+
+    let result = run("query A { hello } query B { hello }");
+    assert_eq!(
+        result,
+        json!({
+            "errors": [{
+                "message": "Must provide operation name if query contains multiple operations",
+            }],
+        })
+    );
+}
+
+#[test]
+fn test_response_struct() {
+    let schema = build_schema().unwrap();
+    let response = common::run(&schema, "{ hello fail }", QLVariables::default())
+        .add_extension("cost", DataValue::Int(2));
+    assert!(!response.is_ok());
+    assert_eq!(
+        response.errors[0].path,
+        vec![PathSegment::Field("fail".to_string())]
+    );
+    assert_eq!(
+        response.errors[0].locations,
+        vec![Location { line: 1, column: 9 }]
+    );
+    assert!(matches!(response.errors[0].error, Error::NotFoundError(_)));
+
+    let result = serde_json::to_value(&response).unwrap();
+    assert_eq!(result["extensions"], json!({ "cost": 2 }));
+}
+
+#[test]
+fn test_selection_order() {
+    assert_eq!(
+        data("{ me { name __typename greeting z: name } }"),
+        r#"{"me":{"name":"Ann","__typename":"User","greeting":"hi","z":"Ann"}}"#
+    );
+    assert_eq!(
+        data("{ b: me { greeting } a: me { email } }"),
+        r#"{"b":{"greeting":"hi"},"a":{"email":"ann@example.com"}}"#
+    );
+    assert_eq!(
+        data("{ me { ... on User { greeting } name ...Email } } fragment Email on User { email }"),
+        r#"{"me":{"greeting":"hi","name":"Ann","email":"ann@example.com"}}"#
+    );
+}
+
+#[test]
+fn test_only_selected_fields() {
+    assert_eq!(data("{ me { name } }"), r#"{"me":{"name":"Ann"}}"#);
+    assert_eq!(
+        data("{ me { __typename } }"),
+        r#"{"me":{"__typename":"User"}}"#
+    );
+}
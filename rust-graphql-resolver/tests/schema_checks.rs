@@ -0,0 +1,233 @@
+use rust_graphql_resolver::{
+    builder::{
+        field::{CustomTypeBuilder, QLEnumBuilder, QLInputBuilder, QLUnionBuilder},
+        query::QueryBuilder,
+        schema::SchemaBuilder,
+    },
+    error::BuildError,
+    schema::field::{CustomType, Field, FieldType, InputField, InputFieldType},
+};
+
+fn pet() -> CustomType {
+    CustomTypeBuilder::new("Pet")
+        .add_field("name", Field::basic_str())
+        .build()
+}
+
+fn messages(errors: &[BuildError]) -> Vec<String> {
+    errors.iter().map(ToString::to_string).collect()
+}
+
+#[test]
+fn test_consistent_schema() {
+    let builder = SchemaBuilder::new("consistent_schema")
+        .add_object(pet())
+        .add_query("pet", |sch| {
+            QueryBuilder::new()
+                .set_type(sch.get_object_type("Pet")?)
+                .build()
+        })
+        .unwrap();
+    assert!(builder.check().is_empty());
+    assert!(builder.build().is_ok());
+}
+
+#[test]
+fn test_problems_are_all_reported() {
+    let builder = SchemaBuilder::new("inconsistent_schema")
+        .add_object(pet())
+        .add_enum(
+            QLEnumBuilder::new("Color")
+                .add_value("RED")
+                .add_value("dark-blue")
+                .add_value("true")
+                .build(),
+        )
+        .add_input_object(
+            QLInputBuilder::new("Color")
+                .add_field(
+                    "hex",
+                    InputField::simple("hex", InputFieldType::basic_str()),
+                )
+                .build(),
+        )
+        .add_input_object(
+            QLInputBuilder::new("Filter")
+                .add_field(
+                    "name",
+                    InputField::simple("name", InputFieldType::basic_str()),
+                )
+                .build(),
+        )
+        .add_object(CustomTypeBuilder::new("Empty").build())
+        .add_union(QLUnionBuilder::new("Animal").add_type("Cat").build());
+    let pet_type = builder.get_object_type("Pet").unwrap();
+    // the type referenced above is replaced
+    let builder = builder
+        .add_object(pet())
+        .add_query("pet", |_sch| {
+            QueryBuilder::new().set_type(pet_type.clone()).build()
+        })
+        .unwrap()
+        .add_query("filter", |_sch| {
+            QueryBuilder::new()
+                .set_type(FieldType::CustomType(
+                    CustomTypeBuilder::new("Filter")
+                        .add_field("name", Field::basic_str())
+                        .build(),
+                ))
+                .add_argument("bad-arg", InputFieldType::basic_int())
+                .build()
+        })
+        .unwrap();
+    let errors = builder.check();
+    assert_eq!(
+        messages(&errors),
+        vec![
+            "Type Color is defined more than once",
+            "Input type Filter cannot be the type of Query.filter",
+            "Query.filter(bad-arg:) is not a valid GraphQL name",
+            "The type referenced by Query.pet is no longer in the schema",
+            "Type Empty must define one or more fields, values or member types",
+            "Color.dark-blue is not a valid GraphQL name",
+            "Color.true is not a valid GraphQL name",
+            "Unknown type Cat referenced by Animal",
+        ]
+    );
+    match builder.build() {
+        Err(BuildError::InvalidSchema(build_errors)) => {
+            assert_eq!(messages(&build_errors), messages(&errors))
+        }
+        other => panic!("unexpected {:?}", other.map(|_| ())),
+    }
+}
+
+#[test]
+fn test_builtin_type_names_are_reserved() {
+    let errors = SchemaBuilder::new("reserved_names_schema")
+        .add_object(
+            CustomTypeBuilder::new("String")
+                .add_field("value", Field::basic_str())
+                .build(),
+        )
+        .add_object(
+            CustomTypeBuilder::new("Query")
+                .add_field("value", Field::basic_str())
+                .build(),
+        )
+        .check();
+    assert_eq!(
+        messages(&errors),
+        vec![
+            "Type Query is defined more than once",
+            "Type String is defined more than once",
+        ]
+    );
+}
+
+#[test]
+fn test_inline_types_are_checked() {
+    let builder = SchemaBuilder::new("inline_types_schema")
+        .add_object(pet())
+        .add_input_object(
+            QLInputBuilder::new("PetFilter")
+                .add_field(
+                    "name",
+                    InputField::simple("name", InputFieldType::basic_str()),
+                )
+                .build(),
+        );
+    let pet_type = builder.get_object_type("Pet").unwrap();
+    let filter_type = builder.get_input_type("PetFilter").unwrap();
+    // both referenced types are replaced, the references nested in the inline types dangle
+    let errors = builder
+        .add_object(pet())
+        .add_input_object(QLInputBuilder::new("PetFilter").build())
+        .add_query("owner", |_sch| {
+            QueryBuilder::new()
+                .set_type(FieldType::CustomType(
+                    CustomTypeBuilder::new("Owner")
+                        .add_field("pet", Field::simple("pet", pet_type.clone()))
+                        .add_field(
+                            "kind",
+                            Field::simple(
+                                "kind",
+                                FieldType::Enum(
+                                    QLEnumBuilder::new("Kind").add_value("null").build(),
+                                ),
+                            ),
+                        )
+                        .build(),
+                ))
+                .add_argument(
+                    "filter",
+                    InputFieldType::QLInput(
+                        QLInputBuilder::new("OwnerFilter")
+                            .add_field("pet", InputField::simple("pet", filter_type.clone()))
+                            .add_field(
+                                "bad-name",
+                                InputField::simple("bad-name", InputFieldType::basic_str()),
+                            )
+                            .build(),
+                    ),
+                )
+                .build()
+        })
+        .unwrap()
+        .check();
+    assert_eq!(
+        messages(&errors),
+        vec![
+            "Kind.null is not a valid GraphQL name",
+            "The type referenced by Owner.pet is no longer in the schema",
+            "OwnerFilter.bad-name is not a valid GraphQL name",
+            "The type referenced by OwnerFilter.pet is no longer in the schema",
+            "Type PetFilter must define one or more fields, values or member types",
+        ]
+    );
+}
+
+#[test]
+fn test_one_error_per_name_clash() {
+    let errors = SchemaBuilder::new("name_clash_schema")
+        .add_object(pet())
+        .add_input_object(
+            QLInputBuilder::new("Pet")
+                .add_field(
+                    "name",
+                    InputField::simple("name", InputFieldType::basic_str()),
+                )
+                .build(),
+        )
+        .add_object(
+            CustomTypeBuilder::new("Owner")
+                .add_field("name", Field::basic_str())
+                .build(),
+        )
+        .add_query("pet", |sch| {
+            QueryBuilder::new()
+                .set_type(sch.get_object_type("Pet")?)
+                // an inline input type clashing with an object
+                .add_argument(
+                    "owner",
+                    InputFieldType::QLInput(
+                        QLInputBuilder::new("Owner")
+                            .add_field(
+                                "name",
+                                InputField::simple("name", InputFieldType::basic_str()),
+                            )
+                            .build(),
+                    ),
+                )
+                .build()
+        })
+        .unwrap()
+        .check();
+    assert_eq!(
+        messages(&errors),
+        vec![
+            "Type Owner is defined more than once",
+            "Type Pet is defined more than once",
+        ]
+    );
+}
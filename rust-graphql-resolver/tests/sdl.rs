@@ -0,0 +1,533 @@
+mod common;
+
+use rust_graphql_resolver::{
+    ast::parse_schema,
+    builder::{
+        field::{
+            CustomTypeBuilder, QLEnumBuilder, QLInputBuilder, QLInterfaceBuilder, QLScalarBuilder,
+            QLUnionBuilder,
+        },
+        mutation::MutationBuilder,
+        query::QueryBuilder,
+        schema::SchemaBuilder,
+        sdl::{ResolverMap, ResolverMapBuilder},
+        value::DataValueObjectBuilder,
+    },
+    error::{BuildError, BuildResult, Result},
+    schema::{
+        field::{Field, FieldType, InputField, InputFieldType},
+        resolve::{BoxedValue, QLApiParam, QLContext, QLVariables},
+        Schema,
+    },
+    value::DataValue,
+};
+use serde_json::json;
+
+use common::{non_null, non_null_input, run_json};
+
+fn build_schema() -> BuildResult<Schema> {
+    let builder = SchemaBuilder::new("sdl_schema")
+        .add_scalar(
+            QLScalarBuilder::new("Email")
+                .set_specified_by_url("https://www.rfc-editor.org/rfc/rfc5322")
+                .build(),
+        )
+        .add_enum(
+            QLEnumBuilder::new("Role")
+                .set_description("What a user may do")
+                .add_value_with_desc("ADMIN", "Everything")
+                .add_value("GUEST")
+                .build(),
+        )
+        .add_interface(
+            QLInterfaceBuilder::new("Node")
+                .add_field(
+                    "id",
+                    Field::simple("id", non_null(Field::basic_id().field_type)),
+                )
+                .build(),
+        );
+    let mut posts = Field::simple_with_description(
+        "posts",
+        non_null(FieldType::List(Box::new(non_null(
+            Field::basic_str().field_type,
+        )))),
+        "Titles of the posts,\nnewest first",
+    );
+    posts.arguments.insert(
+        "first".to_string(),
+        InputField::simple_with_description("first", InputFieldType::basic_int(), "At most"),
+    );
+    let user = CustomTypeBuilder::new("User")
+        .set_description("A \"registered\" user")
+        .add_interface("Node")
+        .add_field(
+            "id",
+            Field::simple("id", non_null(Field::basic_id().field_type)),
+        )
+        .add_field(
+            "email",
+            Field::simple("email", builder.get_scalar_type("Email")?),
+        )
+        .add_field(
+            "role",
+            Field::simple("role", builder.get_enum_type("Role")?),
+        )
+        .add_field("posts", posts)
+        .add_field(
+            "avatar",
+            Field::basic_str()
+                .add_argument("size", InputFieldType::basic_int())
+                .add_argument("round", non_null_input(InputFieldType::basic_bool())),
+        )
+        .build();
+    let builder = builder
+        .add_object(user)
+        .add_object(
+            CustomTypeBuilder::new("Bot")
+                .add_field(
+                    "id",
+                    Field::simple("id", non_null(Field::basic_id().field_type)),
+                )
+                .build(),
+        )
+        .add_union(
+            QLUnionBuilder::new("Actor")
+                .add_type("User")
+                .add_type("Bot")
+                .build(),
+        );
+    let filter = QLInputBuilder::new("UserFilter")
+        .add_field_with_default(
+            "roles",
+            InputFieldType::List(Box::new(builder.get_enum_input_type("Role")?)),
+            DataValue::List(vec![DataValue::String("GUEST".to_string())]),
+        )
+        .add_field(
+            "email",
+            InputField::simple_with_description(
+                "email",
+                builder.get_scalar_input_type("Email")?,
+                "Exact match",
+            ),
+        )
+        .build();
+    builder
+        .add_input_object(filter)
+        .add_query("users", |sch| {
+            QueryBuilder::new()
+                .set_description("All the users")
+                .set_type(non_null(FieldType::List(Box::new(
+                    sch.get_object_type("User")?,
+                ))))
+                .add_argument("filter", sch.get_input_type("UserFilter")?)
+                .build()
+        })?
+        .add_query("actor", |sch| {
+            QueryBuilder::new()
+                .set_type(sch.get_union_type("Actor")?)
+                .add_argument("id", non_null_input(InputFieldType::basic_id()))
+                .build()
+        })?
+        .add_mutation("promote", |sch| {
+            MutationBuilder::new()
+                .set_type(sch.get_object_type("User")?)
+                .add_argument_with_default(
+                    "role",
+                    sch.get_enum_input_type("Role")?,
+                    DataValue::String("ADMIN".to_string()),
+                )
+                .build()
+        })?
+        .build()
+}
+
+const EXPECTED: &str = r#"type Query {
+  actor(id: ID!): Actor
+  """All the users"""
+  users(filter: UserFilter): [User]!
+}
+
+type Mutation {
+  promote(role: Role = ADMIN): User
+}
+
+union Actor = Bot | User
+
+type Bot {
+  id: ID!
+}
+
+scalar Email @specifiedBy(url: "https://www.rfc-editor.org/rfc/rfc5322")
+
+interface Node {
+  id: ID!
+}
+
+"""What a user may do"""
+enum Role {
+  """Everything"""
+  ADMIN
+  GUEST
+}
+
+"""A "registered" user"""
+type User implements Node {
+  avatar(round: Boolean!, size: Int): String
+  email: Email
+  id: ID!
+  """
+  Titles of the posts,
+  newest first
+  """
+  posts(
+    """At most"""
+    first: Int
+  ): [String!]!
+  role: Role
+}
+
+input UserFilter {
+  """Exact match"""
+  email: Email
+  roles: [Role] = [GUEST]
+}
+"#;
+
+#[test]
+fn test_to_sdl() {
+    let sdl = build_schema().unwrap().to_sdl();
+    assert_eq!(sdl, EXPECTED);
+}
+
+#[test]
+fn test_sdl_parses() {
+    let sdl = build_schema().unwrap().to_sdl();
+    let doc = parse_schema(&sdl).unwrap();
+    assert_eq!(doc.definitions.len(), 9);
+}
+
+#[test]
+fn test_datetime_printed_when_used() {
+    let schema = SchemaBuilder::new("datetime_schema")
+        .add_query("now", |_sch| {
+            QueryBuilder::new()
+                .set_type(Field::basic_datetime().field_type)
+                .build()
+        })
+        .unwrap()
+        .build()
+        .unwrap();
+    assert_eq!(
+        schema.to_sdl(),
+        "type Query {\n  now: DateTime\n}\n\nscalar DateTime\n"
+    );
+}
+
+const LOADED_SDL: &str = r#"
+"""A registered user"""
+type User {
+  id: ID!
+  name: String
+  role: Role
+  posts(first: Int = 2): [Post!]!
+}
+
+type Post {
+  title: String!
+}
+
+type Bot {
+  id: ID!
+}
+
+union Actor = User | Bot
+
+enum Role {
+  ADMIN
+  GUEST
+}
+
+input UserFilter {
+  roles: [Role] = [GUEST]
+}
+
+type Query {
+  users(filter: UserFilter): [User]!
+}
+
+extend type Query {
+  actor(id: ID!): Actor
+}
+
+type Mutation {
+  rename(name: String!): User
+}
+"#;
+
+fn user(name: &str) -> DataValue {
+    DataValueObjectBuilder::new()
+        .add_str_field("__typename", "User".to_string())
+        .add_id_field("id", "u1".to_string())
+        .add_str_field("name", name.to_string())
+        .add_str_field("role", "ADMIN".to_string())
+        .build()
+}
+
+fn resolvers() -> ResolverMapBuilder {
+    ResolverMapBuilder::new()
+        .resolve_from_source()
+        .add_api_resolve(
+            "Query.users",
+            Box::new(
+                |_context: &mut QLContext, param: &QLApiParam| -> Result<BoxedValue> {
+                    let filter = param.arguments.get(&"filter".to_string()).unwrap();
+                    let roles = match filter {
+                        DataValue::Object(map) => map.get("roles").cloned().unwrap(),
+                        _ => DataValue::Null,
+                    };
+                    assert_eq!(
+                        roles,
+                        DataValue::List(vec![DataValue::String("GUEST".to_string())])
+                    );
+                    Ok(Box::new(DataValue::List(vec![user("Ann")])))
+                },
+            ),
+        )
+        .add_api_resolve(
+            "Query.actor",
+            Box::new(
+                |_context: &mut QLContext, _param: &QLApiParam| -> Result<BoxedValue> {
+                    Ok(Box::new(
+                        DataValueObjectBuilder::new()
+                            .add_str_field("__typename", "Bot".to_string())
+                            .add_id_field("id", "b1".to_string())
+                            .build(),
+                    ))
+                },
+            ),
+        )
+        .add_api_resolve(
+            "Mutation.rename",
+            Box::new(
+                |_context: &mut QLContext, param: &QLApiParam| -> Result<BoxedValue> {
+                    match param.arguments.get(&"name".to_string()) {
+                        Some(DataValue::String(name)) => Ok(Box::new(user(name))),
+                        _ => Ok(DataValue::boxed_null()),
+                    }
+                },
+            ),
+        )
+        .add_field_resolve(
+            "User.posts",
+            Box::new(
+                |_context: &mut QLContext,
+                 _source: &DataValue,
+                 param: &QLApiParam|
+                 -> Result<BoxedValue> {
+                    let first = match param.arguments.get(&"first".to_string()) {
+                        Some(DataValue::Int(first)) => *first as usize,
+                        _ => 0,
+                    };
+                    let posts = ["a", "b", "c"]
+                        .iter()
+                        .take(first)
+                        .map(|title| {
+                            DataValueObjectBuilder::new()
+                                .add_str_field("title", title.to_string())
+                                .build()
+                        })
+                        .collect();
+                    Ok(Box::new(DataValue::List(posts)))
+                },
+            ),
+        )
+}
+
+fn load_schema(sdl: &str, resolvers: ResolverMap) -> BuildResult<Schema> {
+    SchemaBuilder::new("sdl_loaded_schema")
+        .add_sdl(sdl, resolvers)?
+        .build()
+}
+
+fn run(request: &str) -> serde_json::Value {
+    let schema = load_schema(LOADED_SDL, resolvers().build()).unwrap();
+    run_json(&schema, request, QLVariables::default())
+}
+
+#[test]
+fn test_execute() {
+    assert_eq!(
+        run(r#"{
+            users(filter: {}) { id name role posts { title } }
+            actor(id: "b1") { ... on Bot { id } ... on User { name } }
+        }"#),
+        json!({
+            "data": {
+                "users": [{
+                    "id": "u1",
+                    "name": "Ann",
+                    "role": "ADMIN",
+                    "posts": [{ "title": "a" }, { "title": "b" }],
+                }],
+                "actor": { "id": "b1" },
+            },
+        })
+    );
+    assert_eq!(
+        run(r#"mutation { rename(name: "Bob") { name posts(first: 1) { title } } }"#),
+        json!({ "data": { "rename": { "name": "Bob", "posts": [{ "title": "a" }] } } })
+    );
+}
+
+#[test]
+fn test_loaded_to_sdl() {
+    let schema = load_schema(LOADED_SDL, resolvers().build()).unwrap();
+    let sdl = schema.to_sdl();
+    assert!(sdl.starts_with(
+        "type Query {\n  actor(id: ID!): Actor\n  users(filter: UserFilter): [User]!\n}\n"
+    ));
+    assert!(sdl.contains("\"\"\"A registered user\"\"\"\ntype User {\n"));
+    assert!(sdl.contains("input UserFilter {\n  roles: [Role] = [GUEST]\n}"));
+}
+
+#[test]
+fn test_extend_schema_types() {
+    let schema = SchemaBuilder::new("extended_schema")
+        .add_sdl(LOADED_SDL, resolvers().build())
+        .unwrap()
+        .add_sdl(
+            "extend type User { nickname: String }",
+            ResolverMapBuilder::new().resolve_from_source().build(),
+        )
+        .unwrap()
+        .build()
+        .unwrap();
+    assert!(schema.to_sdl().contains("  nickname: String\n"));
+}
+
+#[test]
+fn test_missing_field_resolver() {
+    let sdl = "type Query { user: User } type User { name: String }";
+    let user_resolve = Box::new(
+        |_context: &mut QLContext, _param: &QLApiParam| -> Result<BoxedValue> {
+            Ok(Box::new(user("Ann")))
+        },
+    );
+    let without_default = ResolverMapBuilder::new()
+        .add_api_resolve("Query.user", user_resolve.clone())
+        .build();
+    assert_eq!(
+        errors(sdl, without_default),
+        vec!["No resolver for field User.name"]
+    );
+    let with_default = ResolverMapBuilder::new()
+        .add_api_resolve("Query.user", user_resolve)
+        .resolve_from_source()
+        .build();
+    assert!(load_schema(sdl, with_default).is_ok());
+}
+
+fn errors(sdl: &str, resolvers: ResolverMap) -> Vec<String> {
+    match load_schema(sdl, resolvers) {
+        Err(BuildError::InvalidSchema(errors)) => errors.iter().map(ToString::to_string).collect(),
+        other => panic!("unexpected {:?}", other.map(|_| ())),
+    }
+}
+
+#[test]
+fn test_build_errors() {
+    let default = || ResolverMapBuilder::new().resolve_from_source().build();
+    let parse_errors = errors("type Query {", default());
+    assert_eq!(parse_errors.len(), 1);
+    assert!(parse_errors[0].starts_with("Parse schema error"));
+    assert_eq!(
+        errors("type Query { user: User }", default()),
+        vec![
+            "Unknown type User referenced by Query.user",
+            "No resolver for field Query.user",
+        ]
+    );
+    let node_resolve =
+        |_context: &QLContext, _data: &DataValue| -> Result<String> { Ok("User".to_string()) };
+    assert_eq!(
+        errors(
+            LOADED_SDL,
+            resolvers()
+                .set_type_resolve("Node", Box::new(node_resolve))
+                .build(),
+        ),
+        vec!["Resolver Node does not match any field or type"]
+    );
+    assert_eq!(
+        errors("enum A { X } enum A { Y }", default()),
+        vec!["Type A is defined more than once"]
+    );
+    assert_eq!(
+        errors("extend type Missing { x: Int }", default()),
+        vec!["No such object type Missing, set it first"]
+    );
+}
+
+#[test]
+fn test_problems_are_all_reported() {
+    let sdl = r#"
+        type Query { name: String, post: Post }
+        type Post { title: Title, author(role: Role): String }
+        union Media = Post | Video
+    "#;
+    let resolvers = ResolverMapBuilder::new()
+        .add_api_resolve(
+            "Query.unknown",
+            Box::new(
+                |_context: &mut QLContext, _param: &QLApiParam| -> Result<BoxedValue> {
+                    Ok(DataValue::boxed_null())
+                },
+            ),
+        )
+        .set_type_resolve(
+            "Actor",
+            Box::new(
+                |_context: &QLContext, _data: &DataValue| -> Result<String> {
+                    Ok("Post".to_string())
+                },
+            ),
+        )
+        .build();
+    assert_eq!(
+        errors(sdl, resolvers),
+        vec![
+            "No resolver for field Query.name",
+            "No resolver for field Query.post",
+            "Unknown type Title referenced by Post.title",
+            "No resolver for field Post.title",
+            "Unknown type Role referenced by Post.author(role:)",
+            "No resolver for field Post.author",
+            "Unknown type Video referenced by Media",
+            "Resolver Actor does not match any field or type",
+            "Resolver Query.unknown does not match any field or type",
+        ]
+    );
+}
+
+#[test]
+fn test_unsupported_extensions() {
+    let builder = SchemaBuilder::new("unsupported_extensions_schema")
+        .add_sdl(LOADED_SDL, resolvers().build())
+        .unwrap();
+    let errors = match builder.add_sdl(
+        "extend enum Role { OWNER } extend union Actor = Post extend scalar String @deprecated",
+        ResolverMap::default(),
+    ) {
+        Err(BuildError::InvalidSchema(errors)) => errors,
+        other => panic!("unexpected {:?}", other.map(|_| ())),
+    };
+    assert_eq!(
+        errors.iter().map(ToString::to_string).collect::<Vec<_>>(),
+        vec![
+            "Type Role of the schema cannot be extended, only objects, interfaces and input objects can",
+            "Type Actor of the schema cannot be extended, only objects, interfaces and input objects can",
+            "Type String of the schema cannot be extended, only objects, interfaces and input objects can",
+        ]
+    );
+}
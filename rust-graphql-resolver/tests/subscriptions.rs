@@ -0,0 +1,270 @@
+mod common;
+
+use std::sync::{Arc, Mutex};
+
+use futures::{
+    channel::mpsc::{unbounded, UnboundedSender},
+    executor::block_on,
+    future::ready,
+    stream::StreamExt,
+};
+use rust_graphql_resolver::{
+    builder::{
+        field::CustomTypeBuilder, query::QueryBuilder, schema::SchemaBuilder,
+        subscription::SubscriptionBuilder, value::DataValueObjectBuilder,
+    },
+    error::{BuildResult, Error, Result},
+    schema::{
+        field::{Field, InputFieldType},
+        query::Query,
+        resolve::{BoxedValue, QLApiParam, QLContext, QLVariables},
+        subscription::Subscription,
+        Schema,
+    },
+    subscribe,
+    value::DataValue,
+};
+use serde_json::json;
+
+type Event = std::result::Result<DataValue, String>;
+
+/// in-memory pub/sub, every subscriber gets its own channel
+#[derive(Clone, Default)]
+struct Hub(Arc<Mutex<Vec<UnboundedSender<Event>>>>);
+
+impl Hub {
+    fn publish(&self, event: Event) {
+        for sender in self.0.lock().unwrap().iter() {
+            sender.unbounded_send(event.clone()).unwrap();
+        }
+    }
+
+    /// end the streams of every subscriber
+    fn close(&self) {
+        self.0.lock().unwrap().clear();
+    }
+}
+
+fn message(id: &str, room: &str, text: &str) -> Event {
+    Ok(DataValueObjectBuilder::new()
+        .add_id_field("id", id.to_string())
+        .add_str_field("room", room.to_string())
+        .add_str_field("text", text.to_string())
+        .build())
+}
+
+fn build_schema(hub: &Hub) -> BuildResult<Schema> {
+    let hub = hub.clone();
+    SchemaBuilder::new("subscriptions_schema")
+        .add_object(
+            CustomTypeBuilder::new("Message")
+                .add_field("id", Field::basic_id())
+                .add_field("room", Field::basic_str())
+                .add_field("text", Field::basic_str())
+                .add_field(
+                    "upper",
+                    Field::simple_with_async_resolve(
+                        "upper",
+                        Field::basic_str().field_type,
+                        Box::new(
+                            |_context: QLContext, source: DataValue, _param: QLApiParam| async move {
+                                let text = match source {
+                                    DataValue::Object(map) => match map.get("text") {
+                                        Some(DataValue::String(text)) => text.to_uppercase(),
+                                        _ => String::default(),
+                                    },
+                                    _ => String::default(),
+                                };
+                                Ok(Box::new(text) as BoxedValue)
+                            },
+                        ),
+                    ),
+                )
+                .build(),
+        )
+        .add_query("hello", |_sch| -> BuildResult<Query> {
+            QueryBuilder::new()
+                .set_type(Field::basic_str().field_type)
+                .set_resolve(Box::new(
+                    |_context: &mut QLContext, _param: &QLApiParam| -> Result<BoxedValue> {
+                        Ok(Box::new("world".to_string()))
+                    },
+                ))
+                .build()
+        })?
+        .add_subscription("messageAdded", |sch| -> BuildResult<Subscription> {
+            let hub = hub.clone();
+            SubscriptionBuilder::new()
+                .set_type(sch.get_object_type("Message")?)
+                .add_argument("room", InputFieldType::basic_str())
+                .set_resolve(Box::new(move |_context: QLContext, param: QLApiParam| {
+                    let room = match param.arguments.get(&"room".to_string()) {
+                        Some(DataValue::String(room)) => room.clone(),
+                        _ => return Err(Error::NotFoundError("argument: 'room'".to_string())),
+                    };
+                    let (sender, receiver) = unbounded::<Event>();
+                    hub.0.lock().unwrap().push(sender);
+                    Ok(receiver
+                        .filter(move |event| {
+                            ready(match event {
+                                Ok(DataValue::Object(map)) => {
+                                    map.get("room") == Some(&DataValue::String(room.clone()))
+                                }
+                                _ => true,
+                            })
+                        })
+                        .map(|event| match event {
+                            Ok(message) => Ok(Box::new(message) as BoxedValue),
+                            Err(err) => Err(Error::NotFoundError(err)),
+                        }))
+                }))
+                .build()
+        })?
+        .build()
+}
+
+fn collect(schema: &Schema, hub: &Hub, request: &str, events: Vec<Event>) -> serde_json::Value {
+    let responses = subscribe(
+        QLContext::default(),
+        request,
+        schema,
+        None,
+        QLVariables::default(),
+    );
+    for event in events {
+        hub.publish(event);
+    }
+    hub.close();
+    let responses = block_on(
+        responses
+            .map(|response| serde_json::to_value(response).unwrap())
+            .collect::<Vec<_>>(),
+    );
+    serde_json::Value::Array(responses)
+}
+
+#[test]
+fn test_subscription_stream() {
+    let hub = Hub::default();
+    let schema = build_schema(&hub).unwrap();
+    let result = collect(
+        &schema,
+        &hub,
+        r#"subscription { added: messageAdded(room: "a") { id text upper } }"#,
+        vec![
+            message("1", "a", "hello"),
+            message("2", "b", "ignored"),
+            message("3", "a", "bye"),
+        ],
+    );
+    assert_eq!(
+        result,
+        json!([
+            { "data": { "added": { "id": "1", "text": "hello", "upper": "HELLO" } } },
+            { "data": { "added": { "id": "3", "text": "bye", "upper": "BYE" } } },
+        ])
+    );
+}
+
+#[test]
+fn test_subscription_event_error() {
+    let hub = Hub::default();
+    let schema = build_schema(&hub).unwrap();
+    let result = collect(
+        &schema,
+        &hub,
+        r#"subscription { messageAdded(room: "a") { id } }"#,
+        vec![Err("lost message".to_string()), message("1", "a", "hello")],
+    );
+    assert_eq!(
+        result,
+        json!([
+            {
+                "data": { "messageAdded": null },
+                "errors": [{
+                    "message": "NotFound: lost message",
+                    "locations": [{ "line": 1, "column": 16 }],
+                    "path": ["messageAdded"],
+                }],
+            },
+            { "data": { "messageAdded": { "id": "1" } } },
+        ])
+    );
+}
+
+#[test]
+fn test_subscription_request_errors() {
+    let hub = Hub::default();
+    let schema = build_schema(&hub).unwrap();
+    let result = collect(
+        &schema,
+        &hub,
+        r#"subscription { a: messageAdded(room: "a") { id } b: messageAdded(room: "b") { id } }"#,
+        vec![message("1", "a", "hello")],
+    );
+    assert_eq!(
+        result,
+        json!([{
+            "errors": [{
+                "message": "Subscription operation must select only one top level field",
+            }],
+        }])
+    );
+
+    let result = collect(
+        &schema,
+        &hub,
+        "subscription { messageAdded { id } }",
+        vec![],
+    );
+    assert_eq!(
+        result,
+        json!([{ "errors": [{ "message": "NotFound: argument: 'room'" }] }])
+    );
+}
+
+#[test]
+fn test_subscribe_query_and_execute_subscription() {
+    let hub = Hub::default();
+    let schema = build_schema(&hub).unwrap();
+    let result = collect(&schema, &hub, "{ hello }", vec![]);
+    assert_eq!(result, json!([{ "data": { "hello": "world" } }]));
+
+    let response = common::run(
+        &schema,
+        r#"subscription { messageAdded(room: "a") { id } }"#,
+        QLVariables::default(),
+    );
+    assert_eq!(
+        serde_json::to_value(response).unwrap(),
+        json!({
+            "errors": [{
+                "message": "Subscription operation must be executed as a stream, use `subscribe`",
+            }],
+        })
+    );
+}
+
+#[test]
+fn test_subscription_introspection() {
+    let hub = Hub::default();
+    let schema = build_schema(&hub).unwrap();
+    let response = common::run(
+        &schema,
+        r#"{ __schema { subscriptionType { name fields { name } } } }"#,
+        QLVariables::default(),
+    );
+    assert_eq!(
+        serde_json::to_value(response).unwrap(),
+        json!({
+            "data": {
+                "__schema": {
+                    "subscriptionType": {
+                        "name": "Subscription",
+                        "fields": [{ "name": "messageAdded" }],
+                    },
+                },
+            },
+        })
+    );
+}
@@ -0,0 +1,113 @@
+mod common;
+
+use std::{sync::Arc, thread};
+
+use futures::executor::block_on;
+use rust_graphql_resolver::{
+    builder::{
+        field::CustomTypeBuilder, query::QueryBuilder, schema::SchemaBuilder,
+        value::DataValueObjectBuilder,
+    },
+    error::{BuildResult, Result},
+    execute_async,
+    schema::{
+        field::{Field, InputFieldType},
+        query::Query,
+        resolve::{BoxedValue, QLApiParam, QLContext, QLVariables},
+        Schema,
+    },
+    value::DataValue,
+};
+use serde_json::json;
+
+fn assert_send_sync<T: Send + Sync>(_: &T) {}
+
+fn assert_send<T: Send>(_: &T) {}
+
+fn build_schema() -> BuildResult<Schema> {
+    SchemaBuilder::new("thread_safety_schema")
+        .add_object(
+            CustomTypeBuilder::new("Item")
+                .add_field("id", Field::basic_id())
+                .add_field(
+                    "label",
+                    Field::simple_with_async_resolve(
+                        "label",
+                        Field::basic_str().field_type,
+                        Box::new(
+                            |_context: QLContext, source: DataValue, _param: QLApiParam| async move {
+                                let label = match source {
+                                    DataValue::Object(map) => match map.get("id") {
+                                        Some(DataValue::ID(id)) => format!("item {}", id),
+                                        _ => String::default(),
+                                    },
+                                    _ => String::default(),
+                                };
+                                Ok(Box::new(label) as BoxedValue)
+                            },
+                        ),
+                    ),
+                )
+                .build(),
+        )
+        .add_query("item", |sch| -> BuildResult<Query> {
+            QueryBuilder::new()
+                .set_type(sch.get_object_type("Item")?)
+                .add_argument("id", InputFieldType::basic_id())
+                .set_resolve(Box::new(
+                    |_context: &mut QLContext, param: &QLApiParam| -> Result<BoxedValue> {
+                        let id = match param.arguments.get(&"id".to_string()) {
+                            Some(DataValue::String(id)) | Some(DataValue::ID(id)) => id.clone(),
+                            _ => String::default(),
+                        };
+                        Ok(Box::new(
+                            DataValueObjectBuilder::new().add_id_field("id", id).build(),
+                        ))
+                    },
+                ))
+                .build()
+        })?
+        .build()
+}
+
+#[test]
+fn test_schema_is_send_and_sync() {
+    let schema = build_schema().unwrap();
+    assert_send_sync(&schema);
+
+    let future = execute_async(
+        QLContext::default(),
+        r#"{ item(id: "1") { id label } }"#,
+        &schema,
+        None,
+        QLVariables::default(),
+    );
+    assert_send(&future);
+    let response = serde_json::to_value(block_on(future)).unwrap();
+    assert_eq!(
+        response,
+        json!({ "data": { "item": { "id": "1", "label": "item 1" } } })
+    );
+}
+
+#[test]
+fn test_shared_schema_across_threads() {
+    let schema = Arc::new(build_schema().unwrap());
+    let handles = (0..4)
+        .map(|i| {
+            let schema = schema.clone();
+            thread::spawn(move || {
+                let request = format!(r#"{{ item(id: "{}") {{ id label }} }}"#, i);
+                let response = common::run(&schema, &request, QLVariables::default());
+                serde_json::to_value(&response).unwrap()
+            })
+        })
+        .collect::<Vec<_>>();
+    for (i, handle) in handles.into_iter().enumerate() {
+        let id = i.to_string();
+        assert_eq!(
+            handle.join().unwrap(),
+            json!({ "data": { "item": { "id": id, "label": format!("item {}", i) } } })
+        );
+    }
+}
@@ -0,0 +1,310 @@
+mod common;
+
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
+
+use rust_graphql_resolver::{
+    ast::parse_query,
+    builder::{
+        field::{CustomTypeBuilder, QLEnumBuilder, QLInputBuilder},
+        mutation::MutationBuilder,
+        query::QueryBuilder,
+        schema::SchemaBuilder,
+        value::DataValueObjectBuilder,
+    },
+    error::{BuildResult, Result},
+    schema::{
+        field::{Field, FieldType, InputField, InputFieldType, QLInput},
+        mutation::Mutation,
+        query::Query,
+        resolve::{ApiResolveFunc, BoxedValue, QLApiParam, QLContext, QLVariables},
+        Schema,
+    },
+    validation::validate,
+};
+use serde_json::json;
+
+use common::non_null_input;
+
+fn dog_resolve(calls: &Arc<AtomicUsize>) -> Box<dyn ApiResolveFunc> {
+    let calls = calls.clone();
+    Box::new(
+        move |_context: &mut QLContext, _param: &QLApiParam| -> Result<BoxedValue> {
+            calls.fetch_add(1, Ordering::SeqCst);
+            Ok(Box::new(
+                DataValueObjectBuilder::new()
+                    .add_id_field("id", "1".to_string())
+                    .add_str_field("name", "Rex".to_string())
+                    .build(),
+            ))
+        },
+    )
+}
+
+fn build_schema(calls: &Arc<AtomicUsize>) -> BuildResult<Schema> {
+    SchemaBuilder::new("validation_schema")
+        .add_enum(
+            QLEnumBuilder::new("Color")
+                .add_value("Brown")
+                .add_value("Black")
+                .build(),
+        )
+        .add_input_object_with_status(|sch| -> BuildResult<QLInput> {
+            QLInputBuilder::new("DogFilter")
+                .add_field(
+                    "name",
+                    InputField::simple("name", non_null_input(InputFieldType::basic_str())),
+                )
+                .add_field(
+                    "colors",
+                    InputField::simple(
+                        "colors",
+                        InputFieldType::List(Box::new(sch.get_enum_input_type("Color")?)),
+                    ),
+                )
+                .build_ok()
+        })?
+        .add_object(
+            CustomTypeBuilder::new("Dog")
+                .add_field("id", Field::basic_id())
+                .add_field("name", Field::basic_str())
+                .build(),
+        )
+        .add_query("dog", |sch| -> BuildResult<Query> {
+            QueryBuilder::new()
+                .set_type(sch.get_object_type("Dog")?)
+                .add_argument("id", non_null_input(InputFieldType::basic_id()))
+                .set_resolve(dog_resolve(calls))
+                .build()
+        })?
+        .add_query("dogs", |sch| -> BuildResult<Query> {
+            QueryBuilder::new()
+                .set_type(FieldType::List(Box::new(sch.get_object_type("Dog")?)))
+                .add_argument("color", sch.get_enum_input_type("Color")?)
+                .add_argument("filter", sch.get_input_type("DogFilter")?)
+                .set_resolve(dog_resolve(calls))
+                .build()
+        })?
+        .add_query("version", |_sch| -> BuildResult<Query> {
+            QueryBuilder::new()
+                .set_type(Field::basic_str().field_type)
+                .set_resolve(Box::new(
+                    |_context: &mut QLContext, _param: &QLApiParam| -> Result<BoxedValue> {
+                        Ok(Box::new("1.0".to_string()))
+                    },
+                ))
+                .build()
+        })?
+        .add_mutation("rename", |sch| -> BuildResult<Mutation> {
+            MutationBuilder::new()
+                .set_type(sch.get_object_type("Dog")?)
+                .add_argument("name", non_null_input(InputFieldType::basic_str()))
+                .set_resolve(dog_resolve(calls))
+                .build()
+        })?
+        .build()
+}
+
+/// the validation errors of a request, without executing it
+fn validate_request(request: &str) -> Vec<String> {
+    let calls = Arc::new(AtomicUsize::new(0));
+    let schema = build_schema(&calls).unwrap();
+    let doc = parse_query(request).unwrap();
+    validate(&schema, &doc)
+        .into_iter()
+        .map(|err| err.message())
+        .collect()
+}
+
+fn run(request: &str) -> serde_json::Value {
+    let calls = Arc::new(AtomicUsize::new(0));
+    let schema = build_schema(&calls).unwrap();
+    let response = common::run(&schema, request, QLVariables::default());
+    let result = serde_json::to_value(&response).unwrap();
+    if !response.is_ok() {
+        assert_eq!(
+            calls.load(Ordering::SeqCst),
+            0,
+            "resolvers ran for {}",
+            request
+        );
+    }
+    result
+}
+
+/// the messages of the validation errors, the request must fail before executing
+fn messages(request: &str) -> Vec<String> {
+    let result = run(request);
+    assert!(result.get("data").is_none(), "executed {}", request);
+    result["errors"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|e| e["message"].as_str().unwrap().to_string())
+        .collect()
+}
+
+#[test]
+fn test_valid_document() {
+    let result = run(r#"
+        query Dog { dog(id: 1) { ...DogName } version }
+        fragment DogName on Dog { id name __typename }
+        "#);
+    assert_eq!(
+        result,
+        json!({
+            "data": {
+                "dog": { "id": "1", "name": "Rex", "__typename": "Dog" },
+                "version": "1.0",
+            },
+        })
+    );
+
+    assert!(
+        validate_request(r#"{ dogs(color: Black, filter: { name: "Rex" }) { id } }"#).is_empty()
+    );
+    assert!(validate_request(
+        r#"{ __schema { queryType { name } } __type(name: "Dog") { name } }"#
+    )
+    .is_empty());
+}
+
+#[test]
+fn test_fields_exist_on_type() {
+    let result = run(r#"{ dog(id: 1) { id barks } }"#);
+    assert_eq!(
+        result,
+        json!({
+            "errors": [{
+                "message": "Cannot query field 'barks' on type Dog",
+                "locations": [{ "line": 1, "column": 19 }],
+            }],
+        })
+    );
+    assert_eq!(
+        messages(r#"{ unknown ... on Dog { owner } }"#),
+        vec![
+            "Cannot query field 'unknown' on type Query",
+            "Cannot query field 'owner' on type Dog",
+        ]
+    );
+}
+
+#[test]
+fn test_arguments() {
+    assert_eq!(
+        messages(r#"{ version(format: "short") }"#),
+        vec!["Unknown argument 'format' on field Query.version"]
+    );
+    assert_eq!(
+        messages(r#"{ dog { id } }"#),
+        vec!["Field Query.dog argument 'id' of type ID! is required, but it was not provided"]
+    );
+    assert_eq!(
+        messages(r#"{ dog(id: 1.5) { id } }"#),
+        vec!["Argument 'id' got invalid value: DataTypeMisMatchError expect: ID, actul: Float"]
+    );
+    assert_eq!(
+        messages(r#"{ dogs(color: Purple) { id } }"#),
+        vec!["Argument 'color' got invalid value: Value 'Purple' is not a member of enum Color"]
+    );
+    assert_eq!(
+        messages(r#"{ dogs(filter: { colors: [Brown] }) { id } }"#),
+        vec!["Argument 'filter' got invalid value: Field 'name' of required type String! on input type DogFilter was not provided"]
+    );
+}
+
+#[test]
+fn test_leaf_and_object_selections() {
+    assert_eq!(
+        messages(r#"{ version { major } dog(id: 1) }"#),
+        vec![
+            "Field 'version' must not have a selection since type String has no subfields",
+            "Field 'dog' of type Dog must have a selection of subfields",
+        ]
+    );
+}
+
+#[test]
+fn test_fragments() {
+    assert_eq!(
+        messages(
+            r#"
+            { dog(id: 1) { ...Missing } }
+            fragment Unused on Dog { id }
+            "#
+        ),
+        vec!["No such Fragment Missing", "Fragment Unused is never used"]
+    );
+
+    let result = run(r#"
+        { dog(id: 1) { ...A } }
+        fragment A on Dog { id ...B }
+        fragment B on Dog { name ...A }
+        "#);
+    assert_eq!(
+        result,
+        json!({
+            "errors": [{
+                "message": "Cannot spread fragment A within itself",
+                "locations": [{ "line": 3, "column": 35 }, { "line": 4, "column": 37 }],
+            }],
+        })
+    );
+
+    assert_eq!(
+        messages(
+            r#"
+            { dog(id: 1) { ...F ... on Cat { id } } }
+            fragment F on Color { id }
+            "#
+        ),
+        vec![
+            "Unknown type Cat",
+            "Fragment cannot condition on non composite type Color",
+        ]
+    );
+}
+
+#[test]
+fn test_variables() {
+    assert_eq!(
+        messages(r#"query ($unused: Int) { dog(id: $id) { id } }"#),
+        vec![
+            "Variable '$id' is not defined",
+            "Variable '$unused' is never used"
+        ]
+    );
+    // variables used by fragments count for every operation spreading them
+    assert!(validate_request(
+        r#"
+        query Dog($id: ID!) { ...Root }
+        fragment Root on Query { dog(id: $id) { id } }
+        "#
+    )
+    .is_empty());
+}
+
+#[test]
+fn test_operations() {
+    assert_eq!(
+        messages(r#"query A { version } query A { version }"#),
+        vec!["There can only be one operation named A"]
+    );
+    assert_eq!(
+        messages(r#"{ version } query A { version }"#),
+        vec!["This anonymous operation must be the only defined operation"]
+    );
+    assert_eq!(
+        messages(r#"subscription { version }"#),
+        vec!["Subscription schema not defined"]
+    );
+    assert_eq!(
+        messages(r#"mutation { rename { id } }"#),
+        vec![
+            "Field Mutation.rename argument 'name' of type String! is required, but it was not provided"
+        ]
+    );
+}
@@ -0,0 +1,198 @@
+mod common;
+
+use std::iter::FromIterator;
+
+use rust_graphql_resolver::{
+    builder::{
+        field::{QLEnumBuilder, QLInputBuilder},
+        query::QueryBuilder,
+        schema::SchemaBuilder,
+    },
+    error::{BuildResult, Error, Result},
+    schema::{
+        field::{FieldType, InputField, InputFieldType, QLInput, StaticType},
+        query::Query,
+        resolve::{BoxedValue, QLApiParam, QLContext, QLVariables},
+        Schema,
+    },
+    value::DataValue,
+};
+
+use common::non_null_input;
+
+fn build_schema() -> BuildResult<Schema> {
+    SchemaBuilder::new("variables_schema")
+        .add_enum(
+            QLEnumBuilder::new("Color")
+                .add_value("Red")
+                .add_value("Green")
+                .build(),
+        )
+        .add_input_object_with_status(|sch| -> BuildResult<QLInput> {
+            QLInputBuilder::new("Paint")
+                .add_field(
+                    "color",
+                    InputField::simple("color", non_null_input(sch.get_enum_input_type("Color")?)),
+                )
+                .add_field("ratio", InputField::basic_float())
+                .add_field_with_default("coats", InputFieldType::basic_int(), DataValue::Int(2))
+                .build_ok()
+        })?
+        .add_query("echo", |sch| -> BuildResult<Query> {
+            QueryBuilder::new()
+                .set_type(FieldType::StaticType(StaticType::String))
+                .add_argument("text", non_null_input(InputFieldType::basic_str()))
+                .add_argument("paint", sch.get_input_type("Paint")?)
+                .set_resolve(Box::new(
+                    |_context: &mut QLContext, param: &QLApiParam| -> Result<BoxedValue> {
+                        let text = match param.arguments.get(&"text".to_string()) {
+                            Some(DataValue::String(s)) => s.to_owned(),
+                            other => format!("{:?}", other),
+                        };
+                        let paint = match param.arguments.get(&"paint".to_string()) {
+                            Some(DataValue::Object(map)) => format!("{:?}", map),
+                            _ => String::default(),
+                        };
+                        Ok(Box::new(format!("{}{}", text, paint)))
+                    },
+                ))
+                .build()
+        })?
+        .build()
+}
+
+fn variables(list: Vec<(&str, DataValue)>) -> QLVariables {
+    QLVariables::from_iter(list.into_iter().map(|(k, v)| (k.to_string(), v)))
+}
+
+fn echo_of(result: DataValue) -> DataValue {
+    match result {
+        DataValue::Object(mut map) => map.shift_remove("echo").unwrap(),
+        other => panic!("unexpected result {:?}", other),
+    }
+}
+
+#[test]
+fn test_variable_substituted_into_arguments() {
+    let schema = build_schema().unwrap();
+    let request = r#"query Echo($text: String!) { echo(text: $text) }"#;
+    let result = common::run(
+        &schema,
+        request,
+        variables(vec![("text", DataValue::String("hello".to_string()))]),
+    )
+    .into_result()
+    .unwrap();
+    assert_eq!(echo_of(result), DataValue::String("hello".to_string()));
+}
+
+#[test]
+fn test_variable_default_value() {
+    let schema = build_schema().unwrap();
+    let request = r#"query Echo($text: String = "fallback") { echo(text: $text) }"#;
+    let result = common::run(&schema, request, QLVariables::default())
+        .into_result()
+        .unwrap();
+    assert_eq!(echo_of(result), DataValue::String("fallback".to_string()));
+}
+
+#[test]
+fn test_variable_nested_in_input_object() {
+    let schema = build_schema().unwrap();
+    let request = r#"
+    query Echo($color: Color!, $ratio: Float) {
+        echo(text: "paint", paint: {color: $color, ratio: $ratio})
+    }
+    "#;
+    let result = common::run(
+        &schema,
+        request,
+        variables(vec![
+            ("color", DataValue::String("Red".to_string())),
+            ("ratio", DataValue::Int(1)),
+        ]),
+    )
+    .into_result()
+    .unwrap();
+    assert_eq!(
+        echo_of(result),
+        DataValue::String(
+            r#"paint{"coats": Int(2), "color": String("Red"), "ratio": Float(1.0)}"#.to_string()
+        )
+    );
+}
+
+#[test]
+fn test_variable_input_object_from_json() {
+    let schema = build_schema().unwrap();
+    let request = r#"query Echo($paint: Paint) { echo(text: "paint", paint: $paint) }"#;
+    let json = serde_json::json!({ "paint": { "color": "Green" } });
+    let vars = match DataValue::from(json) {
+        DataValue::Object(map) => QLVariables::from_iter(map),
+        _ => unreachable!(),
+    };
+    let result = common::run(&schema, request, vars).into_result().unwrap();
+    assert_eq!(
+        echo_of(result),
+        DataValue::String(r#"paint{"coats": Int(2), "color": String("Green")}"#.to_string())
+    );
+}
+
+#[test]
+fn test_missing_required_variable() {
+    let schema = build_schema().unwrap();
+    let request = r#"query Echo($text: String!) { echo(text: $text) }"#;
+    let result = common::run(&schema, request, QLVariables::default()).into_result();
+    assert!(matches!(result, Err(Error::MissingVariable(name, _)) if name == "text"));
+}
+
+#[test]
+fn test_variable_type_mismatch() {
+    let schema = build_schema().unwrap();
+    let request = r#"query Echo($text: String!) { echo(text: $text) }"#;
+    let result = common::run(
+        &schema,
+        request,
+        variables(vec![("text", DataValue::Int(1))]),
+    )
+    .into_result();
+    assert!(matches!(result, Err(Error::InvalidVariableValue(name, _)) if name == "text"));
+
+    let request = r#"query Echo($color: Color!) { echo(text: "x", paint: {color: $color}) }"#;
+    let result = common::run(
+        &schema,
+        request,
+        variables(vec![("color", DataValue::String("Blue".to_string()))]),
+    )
+    .into_result();
+    assert!(matches!(result, Err(Error::InvalidVariableValue(name, _)) if name == "color"));
+}
+
+#[test]
+fn test_absent_variable_in_input_object() {
+    let schema = build_schema().unwrap();
+    // the fields of absent variables are not provided, the default applies
+    let request = r#"
+    query Echo($color: Color!, $coats: Int) {
+        echo(text: "paint", paint: {color: $color, coats: $coats})
+    }
+    "#;
+    let result = common::run(
+        &schema,
+        request,
+        variables(vec![("color", DataValue::String("Red".to_string()))]),
+    )
+    .into_result()
+    .unwrap();
+    assert_eq!(
+        echo_of(result),
+        DataValue::String(r#"paint{"coats": Int(2), "color": String("Red")}"#.to_string())
+    );
+
+    let request = r#"query Echo($color: Color) { echo(text: "paint", paint: {color: $color}) }"#;
+    let result = common::run(&schema, request, QLVariables::default());
+    assert_eq!(
+        result.errors[0].message(),
+        "Argument 'paint' got invalid value: Field 'color' of required type Color! on input type Paint was not provided"
+    );
+}